@@ -88,6 +88,14 @@ function test_token() {
   aptos move test --package-dir ./token $LN1_ADDRESS_MATHING --ignore-compile-warnings
 }
 
+function test_token_collateral() {
+  aptos move test --package-dir ./token-collateral $LN1_ADDRESS_MATHING --ignore-compile-warnings
+}
+
+function test_token_synthetic() {
+  aptos move test --package-dir ./token-synthetic $LN1_ADDRESS_MATHING --ignore-compile-warnings
+}
+
 function fund() {
   aptos account fund-with-faucet --account $1 --url $REST_API_URL --faucet-url $FAUCET_URL
 }
@@ -1,6 +1,7 @@
 module hp_igps::igps {
 
   use std::signer;
+  use aptos_std::simple_map::{Self, SimpleMap};
   use aptos_framework::coin::Self;
   use aptos_framework::aptos_coin::AptosCoin;
   use aptos_framework::account;
@@ -32,6 +33,13 @@ module hp_igps::igps {
     set_beneficiary_events: EventHandle<SetBeneficiaryEvent>,
   }
 
+  /// Gas added to every payment for a destination, covering the cost
+  /// of delivering a message there on top of the recipient's handling
+  struct DestinationGasOverheads has key {
+    // Mapping (Domain => gas overhead)
+    overheads: SimpleMap<u32, u256>,
+  }
+
   /// Constructor
   fun init_module(account: &signer) {
     let account_address = signer::address_of(account);
@@ -41,21 +49,26 @@ module hp_igps::igps {
       gas_payment_events: account::new_event_handle<GasPaymentEvent>(account),
       set_beneficiary_events: account::new_event_handle<SetBeneficiaryEvent>(account)
     });
+    move_to<DestinationGasOverheads>(account, DestinationGasOverheads {
+      overheads: simple_map::create<u32, u256>(),
+    });
   }
 
   /// Deposits a payment for the relaying of a message
   /// to its destination chain.
+  /// The destination's gas overhead is paid for on top of `gas_amount`.
   public entry fun pay_for_gas(
     account: &signer,
     message_id: vector<u8>,
     dest_domain: u32,
     gas_amount: u256
-  ) acquires IgpState {
+  ) acquires IgpState, DestinationGasOverheads {
     let state = borrow_global_mut<IgpState>(@hp_igps);
     let account_address = signer::address_of(account);
 
     // calculate interchain gas amount
     let required_amount = (quote_gas_payment(dest_domain, gas_amount) as u64);
+    let gas_amount_with_overhead = gas_amount + get_destination_gas_overhead(dest_domain);
 
     // check account's balance if it is enough to pay interchain gas
     assert!(coin::balance<AptosCoin>(account_address) > required_amount, ERROR_INSUFFICIENT_INTERCHAIN_GAS);
@@ -70,7 +83,7 @@ module hp_igps::igps {
       events::new_gas_payment_event(
         dest_domain,
         message_id,
-        gas_amount,
+        gas_amount_with_overhead,
         required_amount,
         block::get_current_block_height(),
         transaction_context::get_transaction_hash(),
@@ -94,6 +107,27 @@ module hp_igps::igps {
     );
   }
 
+  /// Admin Function to set the gas overhead of `dest_domain`
+  public entry fun set_destination_gas_overhead(
+    account: &signer,
+    dest_domain: u32,
+    gas_overhead: u256
+  ) acquires IgpState, DestinationGasOverheads {
+    assert_owner_address(signer::address_of(account));
+    // packages published before overheads existed have no overheads yet
+    if (!exists<DestinationGasOverheads>(@hp_igps)) {
+      move_to<DestinationGasOverheads>(account, DestinationGasOverheads {
+        overheads: simple_map::create<u32, u256>(),
+      });
+    };
+    let state = borrow_global_mut<DestinationGasOverheads>(@hp_igps);
+    if (!simple_map::contains_key(&state.overheads, &dest_domain)) {
+      simple_map::add(&mut state.overheads, dest_domain, gas_overhead);
+    } else {
+      *simple_map::borrow_mut(&mut state.overheads, &dest_domain) = gas_overhead;
+    };
+  }
+
   // Assert Functions
   /// Check Beneficiary
   inline fun assert_beneficiary_address(account_address: address) acquires IgpState {
@@ -106,10 +140,12 @@ module hp_igps::igps {
   }
 
   #[view]
-  /// Quotes the amount of native tokens to pay for interchain gas.
-  public fun quote_gas_payment(dest_domain: u32, gas_amount: u256): u256 {
+  /// Quotes the amount of native tokens to pay for interchain gas,
+  /// including the destination's gas overhead.
+  public fun quote_gas_payment(dest_domain: u32, gas_amount: u256): u256 acquires DestinationGasOverheads {
     let (token_exchange_rate, gas_price) = gas_oracle::get_exchange_rate_and_gas_price(dest_domain);
-    let dest_gas_cost = gas_amount * (gas_price as u256);
+    let gas_amount_with_overhead = gas_amount + get_destination_gas_overhead(dest_domain);
+    let dest_gas_cost = gas_amount_with_overhead * (gas_price as u256);
     (dest_gas_cost * (token_exchange_rate as u256)) / TOKEN_EXCHANGE_RATE_SCALE
   }
 
@@ -119,6 +155,20 @@ module hp_igps::igps {
     gas_oracle::get_exchange_rate_and_gas_price(remote_domain)
   }
 
+  #[view]
+  /// Get the gas overhead added to every payment for `dest_domain`
+  public fun get_destination_gas_overhead(dest_domain: u32): u256 acquires DestinationGasOverheads {
+    if (!exists<DestinationGasOverheads>(@hp_igps)) {
+      return 0
+    };
+    let state = borrow_global<DestinationGasOverheads>(@hp_igps);
+    if (!simple_map::contains_key(&state.overheads, &dest_domain)) {
+      0
+    } else {
+      *simple_map::borrow(&state.overheads, &dest_domain)
+    }
+  }
+
   #[test_only]
   public fun init_for_test(account: &signer) {
     init_module(account);
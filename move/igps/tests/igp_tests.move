@@ -77,6 +77,40 @@ module hp_igps::igp_tests {
     assert!(coin::balance<AptosCoin>(@0xb0b) == bob_aptos_amt + (expected_gas_payment as u64), 1);
   }
 
+  #[test(aptos_framework = @0x1, hp_igps=@hp_igps, alice = @0xa11ce)]
+  fun destination_gas_overhead_test(aptos_framework: signer, hp_igps: signer, alice: signer) {
+    test_utils::setup(&aptos_framework, &hp_igps, vector[signer::address_of(&alice)]);
+
+    init_igps_for_test(&hp_igps);
+
+    // set exchange rate: 1 BNB = 50 APT
+    gas_oracle::set_remote_gas_data(&hp_igps, BSC_TESTNET_DOMAIN, 500_000_000_000, 1000);
+
+    // no overhead unless one is set
+    assert!(igps::get_destination_gas_overhead(BSC_TESTNET_DOMAIN) == 0, 0);
+    assert!(igps::quote_gas_payment(BSC_TESTNET_DOMAIN, 500000) == 1000 * 500000 * 50, 1);
+
+    igps::set_destination_gas_overhead(&hp_igps, BSC_TESTNET_DOMAIN, 100000);
+    assert!(igps::get_destination_gas_overhead(BSC_TESTNET_DOMAIN) == 100000, 2);
+    assert!(igps::get_destination_gas_overhead(BSC_MAINNET_DOMAIN) == 0, 3);
+    // the overhead is quoted on top of the gas amount
+    assert!(igps::quote_gas_payment(BSC_TESTNET_DOMAIN, 500000) == 1000 * 600000 * 50, 4);
+
+    // updating replaces the overhead
+    igps::set_destination_gas_overhead(&hp_igps, BSC_TESTNET_DOMAIN, 50000);
+    assert!(igps::get_destination_gas_overhead(BSC_TESTNET_DOMAIN) == 50000, 5);
+  }
+
+  // Test will fail because non-admin tries setting the destination gas overhead
+  #[test(aptos_framework = @0x1, hp_igps=@hp_igps, alice = @0xa11ce)]
+  #[expected_failure(abort_code = 1)]
+  fun non_admin_tries_setting_destination_gas_overhead(aptos_framework: signer, hp_igps: signer, alice: signer) {
+    test_utils::setup(&aptos_framework, &hp_igps, vector[signer::address_of(&alice)]);
+
+    init_igps_for_test(&hp_igps);
+    igps::set_destination_gas_overhead(&alice, BSC_TESTNET_DOMAIN, 100000);
+  }
+
   // Test will fail because non-admin tries setting beneficiary
   #[test(aptos_framework = @0x1, hp_igps=@hp_igps, alice = @0xa11ce)]
   #[expected_failure(abort_code = 1)]
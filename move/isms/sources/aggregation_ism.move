@@ -0,0 +1,133 @@
+module hp_isms::aggregation_ism {
+    use std::signer;
+    use std::vector;
+
+    //
+    // Constants
+    //
+    const MODULE_TYPE: u64 = 2;
+    // AGGREGATION
+
+    //
+    // Errors
+    //
+    const ERROR_INVALID_OWNER: u64 = 1;
+    const ERROR_ALREADY_INITIALIZED: u64 = 2;
+    const ERROR_NOT_INITIALIZED: u64 = 3;
+    const ERROR_INVALID_THRESHOLD: u64 = 33;
+
+    struct AggregationIsm has key {
+        // ISM addresses, ordered as the metadata ranges are
+        modules: vector<address>,
+        threshold: u64,
+        owner: address
+    }
+
+    /// Hosts an m-of-n aggregation ISM at the address of `account`, owned by it.
+    /// Called by the package account, it turns this ISM deployment into an
+    /// aggregation ISM. `modules` must be this package or ISMs hosted by it,
+    /// the only ones the mailbox can verify.
+    public entry fun initialize(
+        account: &signer,
+        modules: vector<address>,
+        threshold: u64
+    ) {
+        let account_address = signer::address_of(account);
+        assert!(!exists<AggregationIsm>(account_address), ERROR_ALREADY_INITIALIZED);
+        assert!(threshold > 0 && threshold <= vector::length(&modules), ERROR_INVALID_THRESHOLD);
+        move_to<AggregationIsm>(account, AggregationIsm {
+            modules,
+            threshold,
+            owner: account_address
+        });
+    }
+
+    /// Replaces the set of modules and the number of them required to verify a message
+    public entry fun set_modules_and_threshold(
+        account: &signer,
+        modules: vector<address>,
+        threshold: u64
+    ) acquires AggregationIsm {
+        set_modules_and_threshold_at(account, @hp_isms, modules, threshold);
+    }
+
+    /// Replaces the modules and threshold of the aggregation ISM hosted at `ism`
+    public entry fun set_modules_and_threshold_at(
+        account: &signer,
+        ism: address,
+        modules: vector<address>,
+        threshold: u64
+    ) acquires AggregationIsm {
+        let state = borrow_global_mut<AggregationIsm>(ism);
+        assert!(state.owner == signer::address_of(account), ERROR_INVALID_OWNER);
+        assert!(threshold > 0 && threshold <= vector::length(&modules), ERROR_INVALID_THRESHOLD);
+        state.modules = modules;
+        state.threshold = threshold;
+    }
+
+    /// Transfer ownership of aggregation_ism
+    entry fun transfer_ownership(
+        account: &signer,
+        new_owner: address
+    ) acquires AggregationIsm {
+        transfer_ownership_at(account, @hp_isms, new_owner);
+    }
+
+    /// Transfer ownership of the aggregation ISM hosted at `ism`
+    public entry fun transfer_ownership_at(
+        account: &signer,
+        ism: address,
+        new_owner: address
+    ) acquires AggregationIsm {
+        let state = borrow_global_mut<AggregationIsm>(ism);
+        assert!(state.owner == signer::address_of(account), ERROR_INVALID_OWNER);
+        state.owner = new_owner;
+    }
+
+    /// Returns true if this deployment has been initialized as an aggregation ISM
+    public fun is_initialized(): bool {
+        is_initialized_at(@hp_isms)
+    }
+
+    /// Returns true if an aggregation ISM is hosted at `ism`
+    public fun is_initialized_at(ism: address): bool {
+        exists<AggregationIsm>(ism)
+    }
+
+    #[view]
+    /// Return ISM Module Type - AGGREGATION
+    public fun get_module_type(): u64 {
+        MODULE_TYPE
+    }
+
+    #[view]
+    /// Returns the modules that may verify `message` and how many of them must succeed
+    public fun modules_and_threshold(
+        message: vector<u8>
+    ): (vector<address>, u64) acquires AggregationIsm {
+        modules_and_threshold_at(@hp_isms, message)
+    }
+
+    #[view]
+    /// Returns the modules and threshold of the aggregation ISM hosted at `ism`
+    public fun modules_and_threshold_at(
+        ism: address,
+        _message: vector<u8>
+    ): (vector<address>, u64) acquires AggregationIsm {
+        assert!(is_initialized_at(ism), ERROR_NOT_INITIALIZED);
+        let state = borrow_global<AggregationIsm>(ism);
+        (state.modules, state.threshold)
+    }
+
+    #[view]
+    /// Returns current owner
+    public fun owner(): address acquires AggregationIsm {
+        owner_at(@hp_isms)
+    }
+
+    #[view]
+    /// Returns the owner of the aggregation ISM hosted at `ism`
+    public fun owner_at(ism: address): address acquires AggregationIsm {
+        borrow_global<AggregationIsm>(ism).owner
+    }
+}
@@ -0,0 +1,157 @@
+module hp_isms::domain_routing_ism {
+    use std::signer;
+    use aptos_std::simple_map::{Self, SimpleMap};
+
+    use hp_library::msg_utils;
+
+    //
+    // Constants
+    //
+    const MODULE_TYPE: u64 = 1;
+    // ROUTING
+
+    //
+    // Errors
+    //
+    const ERROR_INVALID_OWNER: u64 = 1;
+    const ERROR_ALREADY_INITIALIZED: u64 = 2;
+    const ERROR_NOT_INITIALIZED: u64 = 3;
+    const ERROR_NO_ROUTE: u64 = 4;
+
+    struct RoutingIsm has key {
+        // Mapping (origin domain => ISM address)
+        modules: SimpleMap<u32, address>,
+        owner: address
+    }
+
+    /// Hosts a domain routing ISM at the address of `account`, owned by it.
+    /// Called by the package account, it turns this ISM deployment into a
+    /// domain routing ISM. Routing state is opt-in, so deployments that never
+    /// call this keep behaving as a multisig ISM.
+    public entry fun initialize(account: &signer) {
+        let account_address = signer::address_of(account);
+        assert!(!exists<RoutingIsm>(account_address), ERROR_ALREADY_INITIALIZED);
+        move_to<RoutingIsm>(account, RoutingIsm {
+            modules: simple_map::create<u32, address>(),
+            owner: account_address
+        });
+    }
+
+    /// Sets the ISM used to verify messages from `origin_domain`
+    public entry fun set_domain(
+        account: &signer,
+        origin_domain: u32,
+        module_address: address
+    ) acquires RoutingIsm {
+        set_domain_at(account, @hp_isms, origin_domain, module_address);
+    }
+
+    /// Sets the ISM the routing ISM hosted at `ism` uses to verify messages from `origin_domain`.
+    /// `module_address` must be this package or an ISM hosted by it, the only ones the mailbox can verify.
+    public entry fun set_domain_at(
+        account: &signer,
+        ism: address,
+        origin_domain: u32,
+        module_address: address
+    ) acquires RoutingIsm {
+        let state = borrow_global_mut<RoutingIsm>(ism);
+        assert!(state.owner == signer::address_of(account), ERROR_INVALID_OWNER);
+
+        if (!simple_map::contains_key(&state.modules, &origin_domain)) {
+            simple_map::add(&mut state.modules, origin_domain, module_address);
+        } else {
+            let module = simple_map::borrow_mut(&mut state.modules, &origin_domain);
+            *module = module_address;
+        };
+    }
+
+    /// Removes the ISM route for `origin_domain`
+    public entry fun remove_domain(
+        account: &signer,
+        origin_domain: u32
+    ) acquires RoutingIsm {
+        remove_domain_at(account, @hp_isms, origin_domain);
+    }
+
+    /// Removes the ISM route for `origin_domain` of the routing ISM hosted at `ism`
+    public entry fun remove_domain_at(
+        account: &signer,
+        ism: address,
+        origin_domain: u32
+    ) acquires RoutingIsm {
+        let state = borrow_global_mut<RoutingIsm>(ism);
+        assert!(state.owner == signer::address_of(account), ERROR_INVALID_OWNER);
+        assert!(simple_map::contains_key(&state.modules, &origin_domain), ERROR_NO_ROUTE);
+        simple_map::remove(&mut state.modules, &origin_domain);
+    }
+
+    /// Transfer ownership of domain_routing_ism
+    entry fun transfer_ownership(
+        account: &signer,
+        new_owner: address
+    ) acquires RoutingIsm {
+        transfer_ownership_at(account, @hp_isms, new_owner);
+    }
+
+    /// Transfer ownership of the routing ISM hosted at `ism`
+    public entry fun transfer_ownership_at(
+        account: &signer,
+        ism: address,
+        new_owner: address
+    ) acquires RoutingIsm {
+        let state = borrow_global_mut<RoutingIsm>(ism);
+        assert!(state.owner == signer::address_of(account), ERROR_INVALID_OWNER);
+        state.owner = new_owner;
+    }
+
+    /// Returns true if this deployment has been initialized as a routing ISM
+    public fun is_initialized(): bool {
+        is_initialized_at(@hp_isms)
+    }
+
+    /// Returns true if a routing ISM is hosted at `ism`
+    public fun is_initialized_at(ism: address): bool {
+        exists<RoutingIsm>(ism)
+    }
+
+    #[view]
+    /// Return ISM Module Type - ROUTING
+    public fun get_module_type(): u64 {
+        MODULE_TYPE
+    }
+
+    #[view]
+    /// Returns the ISM responsible for verifying `message`
+    public fun route(message: vector<u8>): address acquires RoutingIsm {
+        route_at(@hp_isms, message)
+    }
+
+    #[view]
+    /// Returns the ISM the routing ISM hosted at `ism` verifies `message` with
+    public fun route_at(ism: address, message: vector<u8>): address acquires RoutingIsm {
+        assert!(is_initialized_at(ism), ERROR_NOT_INITIALIZED);
+        let state = borrow_global<RoutingIsm>(ism);
+        let origin_domain = msg_utils::origin_domain(&message);
+        assert!(simple_map::contains_key(&state.modules, &origin_domain), ERROR_NO_ROUTE);
+        *simple_map::borrow(&state.modules, &origin_domain)
+    }
+
+    #[view]
+    /// Returns the origin domains that have a route
+    public fun domains(): vector<u32> acquires RoutingIsm {
+        let state = borrow_global<RoutingIsm>(@hp_isms);
+        simple_map::keys(&state.modules)
+    }
+
+    #[view]
+    /// Returns current owner
+    public fun owner(): address acquires RoutingIsm {
+        owner_at(@hp_isms)
+    }
+
+    #[view]
+    /// Returns the owner of the routing ISM hosted at `ism`
+    public fun owner_at(ism: address): address acquires RoutingIsm {
+        borrow_global<RoutingIsm>(ism).owner
+    }
+}
@@ -0,0 +1,124 @@
+module hp_isms::ism {
+    use std::option::{Self, Option};
+    use std::vector;
+    use aptos_std::from_bcs;
+
+    use hp_isms::multisig_ism;
+    use hp_isms::domain_routing_ism;
+    use hp_isms::aggregation_ism;
+    use hp_library::utils;
+
+    //
+    // Constants
+    //
+    // Bytes of one member of the (start, end) metadata range of an aggregation ISM module
+    const METADATA_RANGE_SIZE: u64 = 4;
+
+    //
+    // Errors
+    //
+    const ERROR_VERIFY_FAILED: u64 = 1;
+
+    #[view]
+    /// Return the module type of the ISM deployed at this package address.
+    /// Routing and aggregation deployments report their own type, everything
+    /// else is a MESSAGE_ID_MULTISIG ISM.
+    public fun get_module_type(): u64 {
+        get_module_type_at(@hp_isms)
+    }
+
+    #[view]
+    /// Return the module type of the ISM hosted at `ism`
+    public fun get_module_type_at(ism: address): u64 {
+        if (domain_routing_ism::is_initialized_at(ism)) {
+            domain_routing_ism::get_module_type()
+        } else if (aggregation_ism::is_initialized_at(ism)) {
+            aggregation_ism::get_module_type()
+        } else {
+            multisig_ism::get_module_type()
+        }
+    }
+
+    #[view]
+    /// Returns whether `metadata` verifies `message` against this ISM.
+    public fun verify(metadata: vector<u8>, message: vector<u8>): bool {
+        verify_at(@hp_isms, metadata, message)
+    }
+
+    #[view]
+    /// Returns whether `metadata` verifies `message` against the ISM hosted at `ism`.
+    /// Routing and aggregation ISMs verify with their sub-modules, which must
+    /// be hosted by this package too.
+    public fun verify_at(ism: address, metadata: vector<u8>, message: vector<u8>): bool {
+        if (domain_routing_ism::is_initialized_at(ism)) {
+            let routed_ism = domain_routing_ism::route_at(ism, message);
+            return verify_at(routed_ism, metadata, message)
+        };
+        if (aggregation_ism::is_initialized_at(ism)) {
+            return verify_aggregation(ism, &metadata, message)
+        };
+        multisig_ism::verify_at(ism, &metadata, &message)
+    }
+
+    /// Requires every module of the aggregation ISM hosted at `ism` given
+    /// metadata to verify `message`, and at least threshold of them to be given.
+    fun verify_aggregation(ism: address, metadata: &vector<u8>, message: vector<u8>): bool {
+        let (modules, threshold) = aggregation_ism::modules_and_threshold_at(ism, message);
+        let module_count = vector::length(&modules);
+        let verified = 0;
+        let i = 0;
+        while (i < module_count && verified < threshold) {
+            let module_metadata = aggregation_module_metadata(metadata, i);
+            if (option::is_some(&module_metadata)) {
+                let module = *vector::borrow(&modules, i);
+                if (!verify_at(module, option::extract(&mut module_metadata), message)) {
+                    return false
+                };
+                verified = verified + 1;
+            };
+            i = i + 1;
+        };
+        verified >= threshold
+    }
+
+    /// Returns the metadata of module `index` of an aggregation ISM, none if the relayer gave none.
+    /// Aggregation metadata starts with a big-endian (start, end) pair of u32 offsets per module,
+    /// a start of 0 meaning no metadata.
+    fun aggregation_module_metadata(metadata: &vector<u8>, index: u64): Option<vector<u8>> {
+        let range_offset = index * METADATA_RANGE_SIZE * 2;
+        let start = from_bcs::to_u32(utils::extract_from_bytes_reversed(
+            metadata,
+            range_offset,
+            range_offset + METADATA_RANGE_SIZE
+        ));
+        let end = from_bcs::to_u32(utils::extract_from_bytes_reversed(
+            metadata,
+            range_offset + METADATA_RANGE_SIZE,
+            range_offset + METADATA_RANGE_SIZE * 2
+        ));
+        if (start == 0) {
+            return option::none()
+        };
+        option::some(utils::extract_from_bytes(metadata, (start as u64), (end as u64)))
+    }
+
+    /// Aborts unless `metadata` verifies `message`.
+    /// Meant to be simulated by off-chain agents to estimate verification gas.
+    public entry fun assert_verified(
+        account: &signer,
+        metadata: vector<u8>,
+        message: vector<u8>
+    ) {
+        assert_verified_at(account, @hp_isms, metadata, message);
+    }
+
+    /// Aborts unless `metadata` verifies `message` against the ISM hosted at `ism`.
+    public entry fun assert_verified_at(
+        _account: &signer,
+        ism: address,
+        metadata: vector<u8>,
+        message: vector<u8>
+    ) {
+        assert!(verify_at(ism, metadata, message), ERROR_VERIFY_FAILED);
+    }
+}
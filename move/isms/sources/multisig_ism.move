@@ -20,6 +20,7 @@ module hp_isms::multisig_ism {
     //
     const ERROR_INVALID_OWNER: u64 = 1;
     const ERROR_THRESHOLD_NOT_MET: u64 = 2;
+    const ERROR_ALREADY_INITIALIZED: u64 = 3;
     const ERROR_INVALID_THRESHOLD: u64 = 33;
 
     struct ValidatorsAndThreshold has store {
@@ -41,6 +42,18 @@ module hp_isms::multisig_ism {
         });
     }
 
+    /// Hosts a multisig ISM at the address of `account`, owned by it.
+    /// Hosted ISMs are verified by this package, so routing and aggregation
+    /// ISMs can point at them.
+    public entry fun create(account: &signer) {
+        let account_address = signer::address_of(account);
+        assert!(!exists<ISM>(account_address), ERROR_ALREADY_INITIALIZED);
+        move_to<ISM>(account, ISM {
+            validators_per_domain: simple_map::create<u32, ValidatorsAndThreshold>(),
+            owner: account_address
+        });
+    }
+
     /// Enrolls multiple validators into a validator set.
     /// And sets threshold
     public entry fun set_validators_and_threshold(
@@ -49,7 +62,19 @@ module hp_isms::multisig_ism {
         threshold: u64,
         origin_domain: u32
     ) acquires ISM {
-        let state = borrow_global_mut<ISM>(@hp_isms);
+        set_validators_and_threshold_at(account, @hp_isms, validators, threshold, origin_domain);
+    }
+
+    /// Enrolls multiple validators into a validator set of the ISM hosted at `ism`.
+    /// And sets threshold
+    public entry fun set_validators_and_threshold_at(
+        account: &signer,
+        ism: address,
+        validators: vector<address>,
+        threshold: u64,
+        origin_domain: u32
+    ) acquires ISM {
+        let state = borrow_global_mut<ISM>(ism);
 
         // only owner can set
         assert!(state.owner == signer::address_of(account), ERROR_INVALID_OWNER);
@@ -84,7 +109,16 @@ module hp_isms::multisig_ism {
         metadata: &vector<u8>,
         message: &vector<u8>,
     ): bool acquires ISM {
-        let state = borrow_global<ISM>(@hp_isms);
+        verify_at(@hp_isms, metadata, message)
+    }
+
+    /// Verifies `message` against the validators of the ISM hosted at `ism`
+    public fun verify_at(
+        ism: address,
+        metadata: &vector<u8>,
+        message: &vector<u8>,
+    ): bool acquires ISM {
+        let state = borrow_global<ISM>(ism);
 
         let origin_mailbox = ism_metadata::origin_mailbox(metadata);
         let origin_domain = msg_utils::origin_domain(message);
@@ -151,7 +185,16 @@ module hp_isms::multisig_ism {
     public fun validators_and_threshold(
         origin_domain: u32
     ): (vector<address>, u64) acquires ISM {
-        let state = borrow_global<ISM>(@hp_isms);
+        validators_and_threshold_at(@hp_isms, origin_domain)
+    }
+
+    #[view]
+    /// Returns the validators and threshold of the ISM hosted at `ism`
+    public fun validators_and_threshold_at(
+        ism: address,
+        origin_domain: u32
+    ): (vector<address>, u64) acquires ISM {
+        let state = borrow_global<ISM>(ism);
         if (!simple_map::contains_key(&state.validators_per_domain, &origin_domain)) {
             return (vector[], 0)
         };
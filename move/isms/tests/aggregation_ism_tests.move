@@ -0,0 +1,85 @@
+#[test_only]
+module hp_isms::aggregation_ism_tests {
+  use std::signer;
+
+  use hp_isms::aggregation_ism;
+  use hp_isms::domain_routing_ism;
+  use hp_isms::ism;
+  use hp_library::test_utils;
+
+  #[test(aptos_framework=@0x1, alice=@hp_isms)]
+  fun modules_and_threshold_test(aptos_framework: signer, alice: signer) {
+    let modules = vector[@0xb5c, @0xb5d, @0xb5e];
+    aggregation_ism::initialize(&alice, modules, 2);
+
+    let (expected_modules, expected_threshold) = aggregation_ism::modules_and_threshold(b"");
+    assert!(expected_modules == modules && expected_threshold == 2, 0);
+    assert!(ism::get_module_type() == 2, 0);
+
+    aggregation_ism::set_modules_and_threshold(&alice, vector[@0xb5c], 1);
+    let (expected_modules, expected_threshold) = aggregation_ism::modules_and_threshold(b"");
+    assert!(expected_modules == vector[@0xb5c] && expected_threshold == 1, 0);
+  }
+
+  // Test will fail because threshold exceeds the number of modules
+  #[test(aptos_framework=@0x1, alice=@hp_isms)]
+  #[expected_failure(abort_code = 33)]
+  fun invalid_threshold_test(aptos_framework: signer, alice: signer) {
+    aggregation_ism::initialize(&alice, vector[@0xb5c], 2);
+  }
+
+  #[test(aptos_framework=@0x1, hp_isms=@hp_isms, alice=@0xa11ce)]
+  fun hosted_modules_and_threshold_test(aptos_framework: signer, hp_isms: signer, alice: signer) {
+    aggregation_ism::initialize(&alice, vector[@hp_isms, @0xb5c], 1);
+    let (modules, threshold) = aggregation_ism::modules_and_threshold_at(@0xa11ce, b"");
+    assert!(modules == vector[@hp_isms, @0xb5c] && threshold == 1, 0);
+    assert!(ism::get_module_type_at(@0xa11ce) == 2, 0);
+
+    aggregation_ism::set_modules_and_threshold_at(&alice, @0xa11ce, vector[@0xb5c], 1);
+    let (modules, _) = aggregation_ism::modules_and_threshold_at(@0xa11ce, b"");
+    assert!(modules == vector[@0xb5c], 0);
+  }
+
+  #[test(aptos_framework=@0x1, alice=@0xa11ce)]
+  fun transfer_hosted_ownership_test(aptos_framework: signer, alice: signer) {
+    aggregation_ism::initialize(&alice, vector[@0xb5c], 1);
+    aggregation_ism::transfer_ownership_at(&alice, @0xa11ce, @0xb0b);
+    assert!(aggregation_ism::owner_at(@0xa11ce) == @0xb0b, 0);
+  }
+
+  // Test will fail because the hosted aggregation ISM was transferred away from alice
+  #[test(aptos_framework=@0x1, alice=@0xa11ce)]
+  #[expected_failure(abort_code = 1)]
+  fun previous_owner_tries_setting_hosted_modules(aptos_framework: signer, alice: signer) {
+    aggregation_ism::initialize(&alice, vector[@0xb5c], 1);
+    aggregation_ism::transfer_ownership_at(&alice, @0xa11ce, @0xb0b);
+    aggregation_ism::set_modules_and_threshold_at(&alice, @0xa11ce, vector[@0xb5d], 1);
+  }
+
+  #[test(aptos_framework=@0x1, alice=@hp_isms)]
+  fun verify_below_threshold_test(aptos_framework: signer, alice: signer) {
+    aggregation_ism::initialize(&alice, vector[@0xb5c, @0xb5d], 1);
+    // neither module is given metadata
+    assert!(!ism::verify(x"00000000000000000000000000000000", b""), 0);
+  }
+
+  // Test will fail because the module given metadata is a routing ISM without routes
+  #[test(aptos_framework=@0x1, hp_isms=@hp_isms, alice=@0xa11ce)]
+  #[expected_failure(abort_code = 4)]
+  fun verify_sub_module_test(aptos_framework: signer, hp_isms: signer, alice: signer) {
+    domain_routing_ism::initialize(&alice);
+    aggregation_ism::initialize(&hp_isms, vector[@0xa11ce], 1);
+    // module 0 is given the single byte at offset 8
+    ism::verify(x"000000080000000900", x"0000000000000000610000000000000000000000000000000000000000000000000000000000000a11ce");
+  }
+
+  // Test will fail because non-admin tries setting modules
+  #[test(aptos_framework = @0x1, hp_isms=@hp_isms, alice = @0xa11ce)]
+  #[expected_failure(abort_code = 1)]
+  fun non_admin_tries_setting_modules(aptos_framework: signer, hp_isms: signer, alice: signer) {
+    test_utils::setup(&aptos_framework, &hp_isms, vector[signer::address_of(&alice)]);
+
+    aggregation_ism::initialize(&hp_isms, vector[@0xb5c], 1);
+    aggregation_ism::set_modules_and_threshold(&alice, vector[@0xb5d], 1);
+  }
+}
@@ -0,0 +1,116 @@
+#[test_only]
+module hp_isms::domain_routing_ism_tests {
+  use std::signer;
+
+  use hp_isms::domain_routing_ism;
+  use hp_isms::multisig_ism;
+  use hp_isms::ism;
+  use hp_library::msg_utils;
+  use hp_library::test_utils;
+
+  const BSC_TESTNET_DOMAIN: u32 = 97;
+  const APTOS_TESTNET_DOMAIN: u32 = 14402;
+
+  fun message_from(origin: u32): vector<u8> {
+    msg_utils::format_message_into_bytes(
+      3,                    // version
+      0,                    // nonce
+      origin,               // origin domain
+      @0xa11ce,             // sender
+      APTOS_TESTNET_DOMAIN, // destination domain
+      x"0000000000000000000000000000000000000000000000000000000000000b0b",
+      b"Hello World!"
+    )
+  }
+
+  #[test(aptos_framework=@0x1, alice=@hp_isms)]
+  fun route_test(aptos_framework: signer, alice: signer) {
+    domain_routing_ism::initialize(&alice);
+    domain_routing_ism::set_domain(&alice, BSC_TESTNET_DOMAIN, @0xb5c);
+
+    assert!(domain_routing_ism::route(message_from(BSC_TESTNET_DOMAIN)) == @0xb5c, 0);
+    assert!(domain_routing_ism::domains() == vector[BSC_TESTNET_DOMAIN], 0);
+
+    // override existing route
+    domain_routing_ism::set_domain(&alice, BSC_TESTNET_DOMAIN, @0xb5d);
+    assert!(domain_routing_ism::route(message_from(BSC_TESTNET_DOMAIN)) == @0xb5d, 0);
+  }
+
+  #[test(aptos_framework=@0x1, alice=@hp_isms)]
+  fun module_type_test(aptos_framework: signer, alice: signer) {
+    // before initialization the deployment is a multisig ism
+    assert!(ism::get_module_type() == 5, 0);
+    domain_routing_ism::initialize(&alice);
+    assert!(ism::get_module_type() == 1, 0);
+  }
+
+  #[test(aptos_framework=@0x1, hp_isms=@hp_isms, alice=@0xa11ce, bob=@0xb0b)]
+  fun route_to_hosted_ism_test(aptos_framework: signer, hp_isms: signer, alice: signer, bob: signer) {
+    // alice hosts a routing ISM which routes BSC messages to the multisig ISM hosted by bob
+    domain_routing_ism::initialize(&alice);
+    domain_routing_ism::set_domain_at(&alice, @0xa11ce, BSC_TESTNET_DOMAIN, @0xb0b);
+    multisig_ism::create(&bob);
+    multisig_ism::set_validators_and_threshold_at(
+      &bob,
+      @0xb0b,
+      vector[@0x598264ff31f198f6071226b2b7e9ce360163accd],
+      1,
+      BSC_TESTNET_DOMAIN
+    );
+
+    assert!(domain_routing_ism::route_at(@0xa11ce, message_from(BSC_TESTNET_DOMAIN)) == @0xb0b, 0);
+    assert!(ism::get_module_type_at(@0xa11ce) == 1, 0);
+    assert!(ism::get_module_type_at(@0xb0b) == 5, 0);
+    let (validators, threshold) = multisig_ism::validators_and_threshold_at(@0xb0b, BSC_TESTNET_DOMAIN);
+    assert!(validators == vector[@0x598264ff31f198f6071226b2b7e9ce360163accd] && threshold == 1, 0);
+
+    // the package deployment itself is unaffected
+    multisig_ism::init_for_test(&hp_isms);
+    assert!(ism::get_module_type() == 5, 0);
+  }
+
+  #[test(aptos_framework=@0x1, alice=@0xa11ce)]
+  fun transfer_hosted_ownership_test(aptos_framework: signer, alice: signer) {
+    domain_routing_ism::initialize(&alice);
+    domain_routing_ism::transfer_ownership_at(&alice, @0xa11ce, @0xb0b);
+    assert!(domain_routing_ism::owner_at(@0xa11ce) == @0xb0b, 0);
+  }
+
+  // Test will fail because the hosted routing ISM was transferred away from alice
+  #[test(aptos_framework=@0x1, alice=@0xa11ce)]
+  #[expected_failure(abort_code = 1)]
+  fun previous_owner_tries_setting_hosted_domain(aptos_framework: signer, alice: signer) {
+    domain_routing_ism::initialize(&alice);
+    domain_routing_ism::transfer_ownership_at(&alice, @0xa11ce, @0xb0b);
+    domain_routing_ism::set_domain_at(&alice, @0xa11ce, BSC_TESTNET_DOMAIN, @0xb5c);
+  }
+
+  // Test will fail because verification goes through the route, and there is none for the origin domain
+  #[test(aptos_framework=@0x1, alice=@hp_isms)]
+  #[expected_failure(abort_code = 4)]
+  fun verify_routes_test(aptos_framework: signer, alice: signer) {
+    domain_routing_ism::initialize(&alice);
+    domain_routing_ism::set_domain(&alice, BSC_TESTNET_DOMAIN, @0xb0b);
+    ism::verify(b"", message_from(APTOS_TESTNET_DOMAIN));
+  }
+
+  // Test will fail because there is no route for the origin domain
+  #[test(aptos_framework=@0x1, alice=@hp_isms)]
+  #[expected_failure(abort_code = 4)]
+  fun route_missing_domain_test(aptos_framework: signer, alice: signer) {
+    domain_routing_ism::initialize(&alice);
+    domain_routing_ism::set_domain(&alice, BSC_TESTNET_DOMAIN, @0xb5c);
+    domain_routing_ism::remove_domain(&alice, BSC_TESTNET_DOMAIN);
+    domain_routing_ism::route(message_from(BSC_TESTNET_DOMAIN));
+  }
+
+  // Test will fail because non-admin tries setting a route
+  #[test(aptos_framework = @0x1, hp_isms=@hp_isms, alice = @0xa11ce)]
+  #[expected_failure(abort_code = 1)]
+  fun non_admin_tries_setting_domain(aptos_framework: signer, hp_isms: signer, alice: signer) {
+    test_utils::setup(&aptos_framework, &hp_isms, vector[signer::address_of(&alice)]);
+
+    domain_routing_ism::initialize(&hp_isms);
+    domain_routing_ism::set_domain(&alice, BSC_TESTNET_DOMAIN, @0xb5c);
+  }
+}
@@ -9,6 +9,14 @@ module hp_library::msg_utils {
     use hp_library::utils::{ extract_from_bytes, extract_from_bytes_reversed,
         ism_checkpoint_hash, get_version};
 
+    const MAX_U64: u64 = 18446744073709551615;
+
+    //
+    // Errors
+    //
+    const ERROR_INVALID_RECIPIENT_LENGTH: u64 = 0;
+    const ERROR_AMOUNT_OVERFLOW: u64 = 1;
+
     /// Convert message ata into bytes
     public fun format_message_into_bytes(
         version: u8,
@@ -103,6 +111,70 @@ module hp_library::msg_utils {
         from_bcs::to_u64(extract_from_bytes(bytes, 0, 8))
     }
 
+    // Serialize transfer_remote() data of the fungible asset routers
+    // Uses the same layout as the EVM and Sealevel token routers:
+    // [0..32]  recipient
+    // [32..64] amount in remote decimals as a big-endian u256
+    // [64..]   metadata
+    // The native router keeps the layout of format_token_message() its deployed routes use
+    public fun format_warp_token_message(
+        recipient: vector<u8>,
+        amount: u256,
+    ): vector<u8> {
+        assert!(vector::length(&recipient) == 32, ERROR_INVALID_RECIPIENT_LENGTH);
+        let result = recipient;
+        // convert into big-endian
+        let amount_bytes = bcs::to_bytes<u256>(&amount);
+        vector::reverse(&mut amount_bytes);
+        vector::append(&mut result, amount_bytes);
+        result
+    }
+
+    public fun recipient_from_warp_token_message(bytes: &vector<u8>): address {
+        from_bcs::to_address(extract_from_bytes(bytes, 0, 32))
+    }
+
+    public fun amount_from_warp_token_message(bytes: &vector<u8>): u256 {
+        from_bcs::to_u256(extract_from_bytes_reversed(bytes, 32, 64))
+    }
+
+    public fun metadata_from_warp_token_message(bytes: &vector<u8>): vector<u8> {
+        extract_from_bytes(bytes, 64, 0)
+    }
+
+    // Convert a local amount into the decimals of the remote token
+    // Dust below the remote precision is truncated
+    public fun local_to_remote_amount(amount: u64, decimals: u8, remote_decimals: u8): u256 {
+        convert_decimals((amount as u256), decimals, remote_decimals)
+    }
+
+    // Convert an amount in the decimals of the remote token into a local amount
+    public fun remote_to_local_amount(amount: u256, remote_decimals: u8, decimals: u8): u64 {
+        let amount = convert_decimals(amount, remote_decimals, decimals);
+        // coins and fungible assets on aptos are u64, larger amounts can't be credited
+        assert!(amount <= (MAX_U64 as u256), ERROR_AMOUNT_OVERFLOW);
+        (amount as u64)
+    }
+
+    fun convert_decimals(amount: u256, from_decimals: u8, to_decimals: u8): u256 {
+        if (from_decimals > to_decimals) {
+            amount / pow10(from_decimals - to_decimals)
+        } else if (from_decimals < to_decimals) {
+            amount * pow10(to_decimals - from_decimals)
+        } else {
+            amount
+        }
+    }
+
+    fun pow10(exponent: u8): u256 {
+        let result = 1;
+        while (exponent > 0) {
+            result = result * 10;
+            exponent = exponent - 1;
+        };
+        result
+    }
+
     #[test_only]
     public fun format_message_and_digest(
         root: vector<u8>,
@@ -138,4 +210,37 @@ module hp_library::msg_utils {
         );
         (message_bytes, digest_bytes_to_sign)
     }
+
+    #[test]
+    fun token_message_test() {
+        // deployed native routes carry the amount as a little-endian u64
+        let token_message = format_token_message(12);
+        assert!(token_message == x"0c00000000000000", 0);
+        assert!(amount_from_token_message(&token_message) == 12, 0);
+
+        let recipient = x"0000000000000000000000000000000000000000000000000000000000000b0b";
+        let token_message = format_warp_token_message(recipient, 12);
+        assert!(vector::length(&token_message) == 64, 0);
+        assert!(*vector::borrow(&token_message, 63) == 12, 0);
+        assert!(recipient_from_warp_token_message(&token_message) == @0xb0b, 0);
+        assert!(amount_from_warp_token_message(&token_message) == 12, 0);
+        assert!(metadata_from_warp_token_message(&token_message) == vector::empty(), 0);
+    }
+
+    #[test]
+    fun convert_decimals_test() {
+        // 8 decimals coins to 18 decimals EVM tokens and back
+        assert!(local_to_remote_amount(12, 8, 18) == 120000000000, 0);
+        assert!(remote_to_local_amount(120000000000, 18, 8) == 12, 0);
+        // dust below the local precision is dropped
+        assert!(remote_to_local_amount(120000000001, 18, 8) == 12, 0);
+        assert!(local_to_remote_amount(1234, 8, 6) == 12, 0);
+        assert!(local_to_remote_amount(12, 8, 8) == 12, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun remote_amount_overflow_test() {
+        remote_to_local_amount((MAX_U64 as u256) + 1, 8, 8);
+    }
 }
\ No newline at end of file
@@ -13,7 +13,7 @@ module hp_mailbox::mailbox {
   use hp_library::utils;
   use hp_library::h256::{Self, H256};
   use hp_library::merkle_tree::{Self, MerkleTree};
-  use hp_isms::multisig_ism;
+  use hp_isms::ism;
   use hp_router::router::{Self, RouterCap};
   use hp_igps::igps;
 
@@ -132,6 +132,7 @@ module hp_mailbox::mailbox {
     let sender_addr = msg_utils::sender(&message);
     router::assert_router_should_be_enrolled<T>(src_domain, sender_addr);
     inbox_process(
+      router::type_address<T>(),
       message,
       metadata
     );
@@ -142,6 +143,7 @@ module hp_mailbox::mailbox {
   /// `message` via the recipient's ISM using the provided `metadata`.
   ///! `message` should be in a specific format
   fun inbox_process(
+    recipient: address,
     message: vector<u8>,
     metadata: vector<u8>
   ) acquires MailBoxState {
@@ -156,7 +158,10 @@ module hp_mailbox::mailbox {
     // mark it as delivered
     simple_map::add(&mut state.delivered, id, true);
     
-    assert!(multisig_ism::verify(&metadata, &message), ERROR_VERIFY_FAILED);
+    // verify with the ISM the recipient declared, if any
+    let verifying_ism = router::recipient_ism(recipient);
+    if (verifying_ism == @0x0) verifying_ism = get_default_ism();
+    assert!(ism::verify_at(verifying_ism, metadata, message), ERROR_VERIFY_FAILED);
 
     // emit process event
     event::emit_event<ProcessEvent>(
@@ -248,6 +253,12 @@ module hp_mailbox::mailbox {
     router::fetch_module_name(recipient)
   }
 
+  #[view]
+  /// Returns the ISM declared by recipient package, or @0x0 if it uses the default ISM
+  public fun recipient_ism(recipient: address): address {
+    router::recipient_ism(recipient)
+  }
+
   #[view]
   /// Calculates and returns tree's current root
   public fun outbox_get_root(): vector<u8> acquires MailBoxState {
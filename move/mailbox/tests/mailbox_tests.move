@@ -2,12 +2,15 @@
 module hp_mailbox::mailbox_tests {
   use std::vector;
   use std::signer;
+  use std::bcs;
   use std::features;
   use std::string::{Self, String};
   use aptos_framework::block;
   use aptos_framework::account;
 
   use hp_mailbox::mailbox;
+  use hp_isms::domain_routing_ism;
+  use hp_library::msg_utils;
   use hp_library::test_utils;
   use hp_router::router::{Self, RouterCap};
 
@@ -66,4 +69,37 @@ module hp_mailbox::mailbox_tests {
     // check if mailbox count increased
     assert!(mailbox::outbox_get_count() == 2, 0);
   }
-}
\ No newline at end of file
+
+  // Test will fail because the message is verified with the recipient ISM, a routing ISM without routes
+  #[test(aptos_framework=@0x1, hp_router=@hp_router, hp_mailbox=@hp_mailbox, alice=@0xa11ce)]
+  #[expected_failure(abort_code = 4)]
+  fun handle_message_with_recipient_ism_test(aptos_framework: signer, hp_router: signer, hp_mailbox: signer, alice: signer) acquires RouterCapWrapper {
+    test_utils::setup(&aptos_framework, &hp_mailbox, vector[@hp_mailbox, @0xa11ce]);
+
+    mailbox::init_for_test(&hp_mailbox);
+    mailbox::initialize(&hp_mailbox, APTOS_TESTNET_DOMAIN);
+    router::init_for_test(&hp_router);
+
+    let router_cap = router::init<TestRouter>(&hp_mailbox);
+    move_to<RouterCapWrapper<TestRouter>>(&hp_mailbox, RouterCapWrapper { router_cap });
+    let bsc_testnet_router = bcs::to_bytes(&@0xb5c);
+    router::enroll_remote_router<TestRouter>(&hp_mailbox, BSC_TESTNET_DOMAIN, bsc_testnet_router);
+
+    // alice hosts the ISM of the recipient
+    domain_routing_ism::initialize(&alice);
+    router::set_recipient_ism<TestRouter>(&hp_mailbox, @0xa11ce);
+    assert!(mailbox::recipient_ism(@hp_mailbox) == @0xa11ce, 0);
+
+    let message = msg_utils::format_message_into_bytes(
+      0,                    // version
+      0,                    // nonce
+      BSC_TESTNET_DOMAIN,   // origin domain
+      @0xb5c,               // sender
+      APTOS_TESTNET_DOMAIN, // destination domain
+      bcs::to_bytes(&@hp_mailbox),
+      b"Hello World!"
+    );
+    let cap_wrapper = borrow_global<RouterCapWrapper<TestRouter>>(@hp_mailbox);
+    mailbox::handle_message<TestRouter>(message, vector[], &cap_wrapper.router_cap);
+  }
+}
@@ -20,6 +20,7 @@ module hp_router::router {
   const ERROR_ROUTER_ALREADY_INITED: u64 = 6;
   const ERROR_DUPLICATED_TYPEINFO: u64 = 7;
   const ERROR_DUPLICATED_PACKAGE: u64 = 8;
+  const ERROR_RECIPIENT_ISMS_NOT_INITIALIZED: u64 = 9;
   
   //
   // Constants
@@ -47,6 +48,12 @@ module hp_router::router {
     enroll_router_events: EventHandle<EnrollRemoteRouterEvent>
   }
 
+  struct RecipientIsmRegistry has key {
+    // map (package_addy => ism address)
+    // packages without an entry are verified by the mailbox default ISM
+    isms: SimpleMap<address, address>,
+  }
+
   // Router capability
   // used as a type constrain with a remote router
   // since a remote router is only identified by its address, move
@@ -55,6 +62,11 @@ module hp_router::router {
   struct RouterCap<phantom T> has store {}
 
   fun init_module(account: &signer) {
+    init_router_registry(account);
+    initialize_recipient_isms(account);
+  }
+
+  fun init_router_registry(account: &signer) {
     move_to<RouterRegistry>(account, RouterRegistry {
       // map (package_addy => module_name)
       package_map: simple_map::create<address, vector<u8>>(),
@@ -64,6 +76,20 @@ module hp_router::router {
     });
   }
 
+  /**
+   * @notice Creates the recipient ISM registry.
+   * Routers published before recipient ISMs existed did not create it in `init_module`
+   * and must call this once after upgrading. Does nothing if the registry already exists.
+   */
+  public entry fun initialize_recipient_isms(account: &signer) {
+    assert!(signer::address_of(account) == @hp_router, ERROR_INVALID_OWNER);
+    if (!exists<RecipientIsmRegistry>(@hp_router)) {
+      move_to<RecipientIsmRegistry>(account, RecipientIsmRegistry {
+        isms: simple_map::create<address, address>(),
+      });
+    };
+  }
+
   public fun init<T>(account: &signer): RouterCap<T> acquires RouterRegistry {
     let account_address = signer::address_of(account);
 
@@ -161,6 +187,43 @@ module hp_router::router {
     );
   }
 
+  /**
+   * @notice Sets the ISM the mailbox verifies messages to the recipient package of type T with.
+   * The ISM must be the mailbox default ISM package or an ISM hosted by it.
+   */
+  public entry fun set_recipient_ism<T>(
+    account: &signer,
+    ism: address
+  ) acquires RouterRegistry, RecipientIsmRegistry {
+    let account_address = signer::address_of(account);
+    assert_owner_address<T>(account_address);
+    assert!(exists<RecipientIsmRegistry>(@hp_router), ERROR_RECIPIENT_ISMS_NOT_INITIALIZED);
+
+    let registry = borrow_global_mut<RecipientIsmRegistry>(@hp_router);
+    let package_addr = type_address<T>();
+    if (!simple_map::contains_key(&registry.isms, &package_addr)) {
+      simple_map::add(&mut registry.isms, package_addr, ism);
+    } else {
+      let recipient_ism = simple_map::borrow_mut(&mut registry.isms, &package_addr);
+      *recipient_ism = ism;
+    };
+  }
+
+  /**
+   * @notice Clears the recipient ISM of type T, falling back to the mailbox default ISM
+   */
+  public entry fun remove_recipient_ism<T>(account: &signer) acquires RouterRegistry, RecipientIsmRegistry {
+    let account_address = signer::address_of(account);
+    assert_owner_address<T>(account_address);
+    assert!(exists<RecipientIsmRegistry>(@hp_router), ERROR_RECIPIENT_ISMS_NOT_INITIALIZED);
+
+    let registry = borrow_global_mut<RecipientIsmRegistry>(@hp_router);
+    let package_addr = type_address<T>();
+    if (simple_map::contains_key(&registry.isms, &package_addr)) {
+      simple_map::remove(&mut registry.isms, &package_addr);
+    };
+  }
+
   /// Check and return remote router address
   public fun must_have_remote_router<T>(domain: u32): vector<u8> acquires RouterRegistry {
     let registry = borrow_global<RouterRegistry>(@hp_router);
@@ -251,12 +314,31 @@ module hp_router::router {
     }
   }
 
+  #[view]
+  /// Returns the ISM declared by `package_addr`, or @0x0 if it declares none
+  public fun recipient_ism(package_addr: address): address acquires RecipientIsmRegistry {
+    if (!exists<RecipientIsmRegistry>(@hp_router)) {
+      return @0x0
+    };
+    let registry = borrow_global<RecipientIsmRegistry>(@hp_router);
+    if (!simple_map::contains_key(&registry.isms, &package_addr)) {
+      @0x0
+    } else {
+      *simple_map::borrow(&registry.isms, &package_addr)
+    }
+  }
 
   #[test_only]
   public fun init_for_test(account: &signer) {
     init_module(account);
   }
 
+  #[test_only]
+  /// Initializes the router as it was published before recipient ISMs existed
+  public fun init_without_recipient_isms_for_test(account: &signer) {
+    init_router_registry(account);
+  }
+
   #[test_only]
   public fun get_remote_router_for_test<T>(domain: u32): vector<u8> acquires RouterRegistry {
     must_have_remote_router<T>(domain)
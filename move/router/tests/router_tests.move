@@ -136,4 +136,80 @@ module hp_router::router_tests {
     aptos_std::debug::print<vector<u8>>(&module_name);
     assert!(module_name == b"router_tests", 0);
   }
-}
\ No newline at end of file
+
+  #[test(aptos_framework=@0x1, hp_router=@hp_router)]
+  fun recipient_ism_test(aptos_framework: signer, hp_router: signer) {
+    test_utils::setup(&aptos_framework, &hp_router, vector[]);
+
+    router::init_for_test(&hp_router);
+    // init router
+    let router_cap = router::init<TestRouter>(&hp_router);
+    move_to<RouterCapWrapper<TestRouter>>(&hp_router, RouterCapWrapper { router_cap });
+    let package_addy = router::type_address<TestRouter>();
+
+    // recipient without its own ISM falls back to default
+    assert!(router::recipient_ism(package_addy) == @0x0, 0);
+
+    // recipient declares its own ISM
+    router::set_recipient_ism<TestRouter>(&hp_router, @0xb5c);
+    assert!(router::recipient_ism(package_addy) == @0xb5c, 0);
+
+    // and clears it again
+    router::remove_recipient_ism<TestRouter>(&hp_router);
+    assert!(router::recipient_ism(package_addy) == @0x0, 0);
+  }
+
+  // Test will fail because non-owner tries setting the recipient ISM
+  #[test(aptos_framework=@0x1, hp_router=@hp_router, alice=@0xa11ce)]
+  #[expected_failure(abort_code = 1)]
+  fun non_owner_sets_recipient_ism(aptos_framework: signer, hp_router: signer, alice: signer) {
+    test_utils::setup(&aptos_framework, &hp_router, vector[]);
+
+    router::init_for_test(&hp_router);
+    let router_cap = router::init<TestRouter>(&hp_router);
+    move_to<RouterCapWrapper<TestRouter>>(&hp_router, RouterCapWrapper { router_cap });
+
+    router::set_recipient_ism<TestRouter>(&alice, @0xb5c);
+  }
+
+  // Test will fail because an upgraded router sets a recipient ISM before creating the registry
+  #[test(aptos_framework=@0x1, hp_router=@hp_router)]
+  #[expected_failure(abort_code = 9)]
+  fun upgraded_router_sets_recipient_ism(aptos_framework: signer, hp_router: signer) {
+    test_utils::setup(&aptos_framework, &hp_router, vector[]);
+
+    router::init_without_recipient_isms_for_test(&hp_router);
+    let router_cap = router::init<TestRouter>(&hp_router);
+    move_to<RouterCapWrapper<TestRouter>>(&hp_router, RouterCapWrapper { router_cap });
+
+    router::set_recipient_ism<TestRouter>(&hp_router, @0xb5c);
+  }
+
+  #[test(aptos_framework=@0x1, hp_router=@hp_router)]
+  fun upgraded_router_recipient_ism_test(aptos_framework: signer, hp_router: signer) {
+    test_utils::setup(&aptos_framework, &hp_router, vector[]);
+
+    router::init_without_recipient_isms_for_test(&hp_router);
+    let router_cap = router::init<TestRouter>(&hp_router);
+    move_to<RouterCapWrapper<TestRouter>>(&hp_router, RouterCapWrapper { router_cap });
+    let package_addy = router::type_address<TestRouter>();
+    assert!(router::recipient_ism(package_addy) == @0x0, 0);
+
+    // migrate, twice to check it is idempotent
+    router::initialize_recipient_isms(&hp_router);
+    router::initialize_recipient_isms(&hp_router);
+
+    router::set_recipient_ism<TestRouter>(&hp_router, @0xb5c);
+    assert!(router::recipient_ism(package_addy) == @0xb5c, 0);
+  }
+
+  // Test will fail because only the router package can create the registry
+  #[test(aptos_framework=@0x1, hp_router=@hp_router, alice=@0xa11ce)]
+  #[expected_failure(abort_code = 1)]
+  fun non_router_initializes_recipient_isms(aptos_framework: signer, hp_router: signer, alice: signer) {
+    test_utils::setup(&aptos_framework, &hp_router, vector[]);
+
+    router::init_without_recipient_isms_for_test(&hp_router);
+    router::initialize_recipient_isms(&alice);
+  }
+}
@@ -6,4 +6,6 @@
 ./function.sh test_router
 ./function.sh test_examples
 ./function.sh test_token
+./function.sh test_token_collateral
+./function.sh test_token_synthetic
 ./function.sh test_library
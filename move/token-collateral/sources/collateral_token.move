@@ -0,0 +1,161 @@
+/// Collateral hyperlane token
+
+module hp_token_collateral::collateral_token {
+    use aptos_framework::account;
+    use aptos_framework::event::{Self, EventHandle};
+    use aptos_framework::fungible_asset::{Self, Metadata};
+    use aptos_framework::object::Object;
+    use aptos_framework::primary_fungible_store;
+    use hp_library::msg_utils;
+    use hp_mailbox::mailbox;
+    use hp_router::router;
+    use std::signer;
+
+    const DEFAULT_GAS_AMOUNT: u256 = 1_000_000_000;
+
+    // Errors
+    const E_INSUFFICIENT_BALANCE: u64 = 1;
+    const E_ALREADY_INITIALIZED: u64 = 2;
+
+    // Collateral token router
+    // Used to send an existing fungible asset to another network
+    // Transferred tokens are locked in an escrow account on aptos
+    // and released when the synthetic equivalent is sent back from the other chain
+    // Coins can be bridged through the fungible asset paired with them
+    struct CollateralToken {}
+
+    struct State has key {
+        cap: router::RouterCap<CollateralToken>,
+        metadata: Object<Metadata>,
+        decimals: u8,
+        remote_decimals: u8,
+        escrow: address,
+        signer_cap: account::SignerCapability,
+        sent_transfer_remote_events: EventHandle<SentTransferRemote>,
+        received_transfer_remote_events: EventHandle<ReceivedTransferRemote>,
+    }
+
+    struct SentTransferRemote has store, drop {
+        destination: u32,
+        recipient: vector<u8>,
+        amount: u64
+    }
+
+    struct ReceivedTransferRemote has store, drop {
+        origin: u32,
+        recipient: address,
+        amount: u64
+    }
+
+    /// Register the router and the fungible asset it holds as collateral
+    /// Amounts are sent to other chains in `remote_decimals`
+    /// Must be called once by the package account after publishing
+    public entry fun initialize(
+        account: &signer,
+        metadata: Object<Metadata>,
+        remote_decimals: u8,
+    ) {
+        assert!(!exists<State>(signer::address_of(account)), E_ALREADY_INITIALIZED);
+
+        // escrow account holding the locked collateral
+        let (resource_account, signer_cap) = account::create_resource_account(account, b"collateral");
+
+        let cap = router::init<CollateralToken>(account);
+        move_to<State>(account, State {
+            cap,
+            metadata,
+            decimals: fungible_asset::decimals(metadata),
+            remote_decimals,
+            escrow: signer::address_of(&resource_account),
+            signer_cap,
+            sent_transfer_remote_events: account::new_event_handle<SentTransferRemote>(account),
+            received_transfer_remote_events: account::new_event_handle<ReceivedTransferRemote>(account),
+        });
+    }
+
+    // Lock collateral and send its synthetic equivalent to the other chain
+    public entry fun transfer_remote(
+        account: &signer,
+        dest_domain: u32,
+        recipient: vector<u8>,
+        amount: u64,
+    ) acquires State {
+        let state = borrow_global_mut<State>(@hp_token_collateral);
+
+        let account_address = signer::address_of(account);
+
+        // Check if the account has enough balance
+        let balance = primary_fungible_store::balance(account_address, state.metadata);
+        assert!(balance >= amount, E_INSUFFICIENT_BALANCE);
+        let remote_amount = msg_utils::local_to_remote_amount(amount, state.decimals, state.remote_decimals);
+        let message_body = msg_utils::format_warp_token_message(recipient, remote_amount);
+
+        // lock amount in escrow
+        primary_fungible_store::transfer(account, state.metadata, state.escrow, amount);
+
+        mailbox::dispatch_with_gas<CollateralToken>(
+            account,
+            dest_domain,
+            message_body,
+            DEFAULT_GAS_AMOUNT,
+            &state.cap
+        );
+
+        event::emit_event<SentTransferRemote>(
+            &mut state.sent_transfer_remote_events,
+            SentTransferRemote { destination: dest_domain, recipient, amount }
+        );
+    }
+
+    /// Release collateral for tokens sent back from other chains
+    public entry fun handle_message(
+        message: vector<u8>,
+        metadata: vector<u8>
+    ) acquires State {
+        let state = borrow_global_mut<State>(@hp_token_collateral);
+
+        mailbox::handle_message<CollateralToken>(
+            message,
+            metadata,
+            &state.cap
+        );
+
+        let token_message = msg_utils::body(&message);
+        let recipient = msg_utils::recipient_from_warp_token_message(&token_message);
+        let amount = msg_utils::remote_to_local_amount(
+            msg_utils::amount_from_warp_token_message(&token_message),
+            state.remote_decimals,
+            state.decimals
+        );
+
+        // Check if the escrow has enough balance
+        let balance = primary_fungible_store::balance(state.escrow, state.metadata);
+        assert!(balance >= amount, E_INSUFFICIENT_BALANCE);
+
+        let escrow_signer = account::create_signer_with_capability(&state.signer_cap);
+        primary_fungible_store::transfer(&escrow_signer, state.metadata, recipient, amount);
+
+        let origin_domain = msg_utils::origin_domain(&message);
+        event::emit_event<ReceivedTransferRemote>(
+            &mut state.received_transfer_remote_events,
+            ReceivedTransferRemote { origin: origin_domain, recipient, amount }
+        );
+    }
+
+    #[view]
+    /// Get the fungible asset held as collateral
+    public fun get_collateral_metadata(): Object<Metadata> acquires State {
+        borrow_global<State>(@hp_token_collateral).metadata
+    }
+
+    #[view]
+    /// Get escrow, e.g. address containing locked collateral
+    public fun get_escrow(): address acquires State {
+        borrow_global<State>(@hp_token_collateral).escrow
+    }
+
+    #[test_only]
+    public fun get_default_gas_amount(): u256 {
+        DEFAULT_GAS_AMOUNT
+    }
+}
@@ -0,0 +1,152 @@
+#[test_only]
+module hp_token_collateral::collateral_tests {
+
+    use aptos_framework::account;
+    use aptos_framework::block;
+    use aptos_framework::fungible_asset::{Self, Metadata};
+    use aptos_framework::object::{Self, Object};
+    use aptos_framework::primary_fungible_store;
+    use hp_igps::test_utils::set_igps_for_test;
+    use hp_isms::multisig_ism;
+    use hp_library::ism_metadata;
+    use hp_library::msg_utils;
+    use hp_library::test_utils;
+    use hp_library::utils;
+    use hp_mailbox::mailbox;
+    use hp_router::router;
+    use hp_token_collateral::collateral_token::{Self, CollateralToken};
+    use std::bcs;
+    use std::features;
+    use std::option;
+    use std::signer;
+    use std::string;
+
+    const APTOS_TESTNET_DOMAIN: u32 = 14402;
+    const REMOTE_DOMAIN: u32 = 14411;
+    const REMOTE_ROUTER: address = @0xbeef;
+
+    // Lock collateral to send it to a remote synthetic router and release part of it back
+    // 1. alice calls transfer_remote() to lock her tokens in escrow
+    // 2. it acts as a verifier and signs a message sent back by the remote router
+    // 3. it calls handle_message() to release tokens from escrow to bob
+    #[test(
+        aptos_framework= @0x1,
+        hp_router= @hp_router,
+        hp_mailbox= @hp_mailbox,
+        hp_igps= @hp_igps,
+        hp_isms= @hp_isms,
+        hp_token_collateral= @hp_token_collateral,
+        alice= @0xa11ce,
+        bob = @0xb0b,
+    )]
+    fun dispatch_handle_test(aptos_framework: signer, hp_router: signer,
+                             hp_mailbox: signer, hp_igps: signer, hp_isms: signer,
+                             hp_token_collateral: signer, alice: signer, bob: signer) {
+        test_utils::setup(
+            &aptos_framework,
+            &hp_token_collateral,
+            vector[@hp_mailbox, @hp_igps, @hp_isms, @0xa11ce, @0xb0b]
+        );
+
+        // enable auid feature because mailbox needs to call `get_transaction_hash()`
+        let feature = features::get_auids();
+        features::change_feature_flags_for_next_epoch(&aptos_framework, vector[feature], vector[]);
+
+        // block must be initilized because mailbox access block resource
+        account::create_account_for_test(@aptos_framework);
+        block::initialize_for_test(&aptos_framework, 1000 /* epoch_interval */);
+
+        mailbox::init_for_test(&hp_mailbox);
+        mailbox::initialize(&hp_mailbox, APTOS_TESTNET_DOMAIN);
+        router::init_for_test(&hp_router);
+
+        let alice_address = signer::address_of(&alice);
+        let bob_address = signer::address_of(&bob);
+        let metadata = create_test_asset(&alice, 100);
+
+        collateral_token::initialize(&hp_token_collateral, metadata, 8);
+        let escrow = collateral_token::get_escrow();
+
+        router::enroll_remote_router<CollateralToken>(
+            &hp_token_collateral,
+            REMOTE_DOMAIN,
+            bcs::to_bytes(&REMOTE_ROUTER)
+        );
+
+        set_igps_for_test(&hp_igps, REMOTE_DOMAIN,
+            collateral_token::get_default_gas_amount(),
+            10, // gas price
+            10_000_000_000 // token exchange rate
+        );
+
+        // lock
+        let amount: u64 = 12;
+        collateral_token::transfer_remote(&alice, REMOTE_DOMAIN, bcs::to_bytes<address>(&bob_address), amount);
+
+        assert!(primary_fungible_store::balance(alice_address, metadata) == 100 - amount, 0);
+        assert!(primary_fungible_store::balance(escrow, metadata) == amount, 0);
+        assert!(mailbox::outbox_get_count() == 1, 0);
+
+        // LN1_ISMS_ADDRESS validator, see native_tests for the key
+        let isms1_eth_address = @0x050D907812D2D2de09Ba8D6cE414d6fee84C29Cb;
+        multisig_ism::init_for_test(&hp_isms);
+        multisig_ism::set_validators_and_threshold(
+            &hp_isms,
+            vector[isms1_eth_address],
+            1, // threshold
+            REMOTE_DOMAIN // origin_domain
+        );
+
+        // message sent back by the remote router to this one
+        let message_bytes = msg_utils::format_message_into_bytes(
+            utils::get_version(),
+            0, // nonce
+            REMOTE_DOMAIN,
+            REMOTE_ROUTER,
+            APTOS_TESTNET_DOMAIN,
+            bcs::to_bytes<address>(&signer::address_of(&hp_token_collateral)),
+            msg_utils::format_warp_token_message(bcs::to_bytes<address>(&bob_address), 5)
+        );
+        let root = x"0000000000000000000000000000000000000000000000000000000000000000";
+        let digest_bytes_to_sign = utils::ism_checkpoint_hash(
+            signer::address_of(&hp_mailbox),
+            REMOTE_DOMAIN,
+            root,
+            0,
+            msg_utils::id(&message_bytes)
+        );
+
+        // if this fails, sign the new digest with sign_msg.js and replace the signature below
+        assert!(&digest_bytes_to_sign == &x"ade60bce33ba729c87a5d63e5283eeb23e8d3d1b8386af6bcdf452fa72d53b95", 0);
+        let digest_bytes_signature = x"219d820e9293feaaab62dbf28fee363b85f24ebc47e6988b8dccfd76f7880c6a69fd54cfb2eaccc7298b26c06fb06f9e78053433892aa66add957c5bbf61dfc81c";
+        let metadata_bytes = ism_metadata::format_signature_into_bytes(
+            signer::address_of(&hp_mailbox),
+            root,
+            0,
+            digest_bytes_signature
+        );
+
+        // release
+        collateral_token::handle_message(message_bytes, metadata_bytes);
+
+        assert!(primary_fungible_store::balance(bob_address, metadata) == 5, 0);
+        assert!(primary_fungible_store::balance(escrow, metadata) == amount - 5, 0);
+    }
+
+    // Create a fungible asset and mint `amount` of it to `owner`
+    fun create_test_asset(owner: &signer, amount: u64): Object<Metadata> {
+        let constructor_ref = object::create_named_object(owner, b"TEST");
+        primary_fungible_store::create_primary_store_enabled_fungible_asset(
+            &constructor_ref,
+            option::none(),
+            string::utf8(b"Test Token"),
+            string::utf8(b"TEST"),
+            8,
+            string::utf8(b""),
+            string::utf8(b""),
+        );
+        let mint_ref = fungible_asset::generate_mint_ref(&constructor_ref);
+        primary_fungible_store::mint(&mint_ref, signer::address_of(owner), amount);
+        object::object_from_constructor_ref<Metadata>(&constructor_ref)
+    }
+}
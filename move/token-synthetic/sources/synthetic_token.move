@@ -0,0 +1,159 @@
+/// Synthetic hyperlane token
+
+module hp_token_synthetic::synthetic_token {
+    use aptos_framework::account;
+    use aptos_framework::event::{Self, EventHandle};
+    use aptos_framework::fungible_asset::{Self, BurnRef, Metadata, MintRef};
+    use aptos_framework::object::{Self, Object};
+    use aptos_framework::primary_fungible_store;
+    use hp_library::msg_utils;
+    use hp_mailbox::mailbox;
+    use hp_router::router;
+    use std::option;
+    use std::signer;
+    use std::string::{Self, String};
+
+    const DEFAULT_GAS_AMOUNT: u256 = 1_000_000_000;
+
+    // Errors
+    const E_INSUFFICIENT_BALANCE: u64 = 1;
+    const E_ALREADY_INITIALIZED: u64 = 2;
+
+    // Synthetic token router
+    // Represents a token whose collateral lives on another chain
+    // Tokens are minted when received and burnt when sent back
+    struct SyntheticToken {}
+
+    struct State has key {
+        cap: router::RouterCap<SyntheticToken>,
+        metadata: Object<Metadata>,
+        decimals: u8,
+        remote_decimals: u8,
+        mint_ref: MintRef,
+        burn_ref: BurnRef,
+        sent_transfer_remote_events: EventHandle<SentTransferRemote>,
+        received_transfer_remote_events: EventHandle<ReceivedTransferRemote>,
+    }
+
+    struct SentTransferRemote has store, drop {
+        destination: u32,
+        recipient: vector<u8>,
+        amount: u64
+    }
+
+    struct ReceivedTransferRemote has store, drop {
+        origin: u32,
+        recipient: address,
+        amount: u64
+    }
+
+    /// Register the router and create the synthetic fungible asset
+    /// Amounts are sent to other chains in `remote_decimals`
+    /// Must be called once by the package account after publishing
+    public entry fun initialize(
+        account: &signer,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        remote_decimals: u8,
+    ) {
+        assert!(!exists<State>(signer::address_of(account)), E_ALREADY_INITIALIZED);
+
+        let constructor_ref = object::create_named_object(account, *string::bytes(&symbol));
+        primary_fungible_store::create_primary_store_enabled_fungible_asset(
+            &constructor_ref,
+            option::none(), // unlimited supply
+            name,
+            symbol,
+            decimals,
+            string::utf8(b""), // icon uri
+            string::utf8(b""), // project uri
+        );
+
+        let cap = router::init<SyntheticToken>(account);
+        move_to<State>(account, State {
+            cap,
+            metadata: object::object_from_constructor_ref<Metadata>(&constructor_ref),
+            decimals,
+            remote_decimals,
+            mint_ref: fungible_asset::generate_mint_ref(&constructor_ref),
+            burn_ref: fungible_asset::generate_burn_ref(&constructor_ref),
+            sent_transfer_remote_events: account::new_event_handle<SentTransferRemote>(account),
+            received_transfer_remote_events: account::new_event_handle<ReceivedTransferRemote>(account),
+        });
+    }
+
+    // Burn synthetic tokens and release the collateral on the other chain
+    public entry fun transfer_remote(
+        account: &signer,
+        dest_domain: u32,
+        recipient: vector<u8>,
+        amount: u64,
+    ) acquires State {
+        let state = borrow_global_mut<State>(@hp_token_synthetic);
+
+        let account_address = signer::address_of(account);
+
+        // Check if the account has enough balance
+        let balance = primary_fungible_store::balance(account_address, state.metadata);
+        assert!(balance >= amount, E_INSUFFICIENT_BALANCE);
+        let remote_amount = msg_utils::local_to_remote_amount(amount, state.decimals, state.remote_decimals);
+        let message_body = msg_utils::format_warp_token_message(recipient, remote_amount);
+
+        primary_fungible_store::burn(&state.burn_ref, account_address, amount);
+
+        mailbox::dispatch_with_gas<SyntheticToken>(
+            account,
+            dest_domain,
+            message_body,
+            DEFAULT_GAS_AMOUNT,
+            &state.cap
+        );
+
+        event::emit_event<SentTransferRemote>(
+            &mut state.sent_transfer_remote_events,
+            SentTransferRemote { destination: dest_domain, recipient, amount }
+        );
+    }
+
+    /// Mint synthetic tokens for collateral locked on other chains
+    public entry fun handle_message(
+        message: vector<u8>,
+        metadata: vector<u8>
+    ) acquires State {
+        let state = borrow_global_mut<State>(@hp_token_synthetic);
+
+        mailbox::handle_message<SyntheticToken>(
+            message,
+            metadata,
+            &state.cap
+        );
+
+        let token_message = msg_utils::body(&message);
+        let recipient = msg_utils::recipient_from_warp_token_message(&token_message);
+        let amount = msg_utils::remote_to_local_amount(
+            msg_utils::amount_from_warp_token_message(&token_message),
+            state.remote_decimals,
+            state.decimals
+        );
+
+        primary_fungible_store::mint(&state.mint_ref, recipient, amount);
+
+        let origin_domain = msg_utils::origin_domain(&message);
+        event::emit_event<ReceivedTransferRemote>(
+            &mut state.received_transfer_remote_events,
+            ReceivedTransferRemote { origin: origin_domain, recipient, amount }
+        );
+    }
+
+    #[view]
+    /// Get the synthetic fungible asset minted by this router
+    public fun get_metadata(): Object<Metadata> acquires State {
+        borrow_global<State>(@hp_token_synthetic).metadata
+    }
+
+    #[test_only]
+    public fun get_default_gas_amount(): u256 {
+        DEFAULT_GAS_AMOUNT
+    }
+}
@@ -0,0 +1,131 @@
+#[test_only]
+module hp_token_synthetic::synthetic_tests {
+
+    use aptos_framework::account;
+    use aptos_framework::block;
+    use aptos_framework::primary_fungible_store;
+    use hp_igps::test_utils::set_igps_for_test;
+    use hp_isms::multisig_ism;
+    use hp_library::ism_metadata;
+    use hp_library::msg_utils;
+    use hp_library::test_utils;
+    use hp_library::utils;
+    use hp_mailbox::mailbox;
+    use hp_router::router;
+    use hp_token_synthetic::synthetic_token::{Self, SyntheticToken};
+    use std::bcs;
+    use std::features;
+    use std::signer;
+    use std::string;
+
+    const APTOS_TESTNET_DOMAIN: u32 = 14402;
+    const REMOTE_DOMAIN: u32 = 14411;
+    const REMOTE_ROUTER: address = @0xbeef;
+
+    // Receive synthetic tokens from a remote collateral router and send part of them back
+    // 1. it acts as a verifier and signs a message sent by the remote router
+    // 2. it calls handle_message() to mint synthetic tokens to bob
+    // 3. bob calls transfer_remote() to burn them and release collateral on the other chain
+    #[test(
+        aptos_framework= @0x1,
+        hp_router= @hp_router,
+        hp_mailbox= @hp_mailbox,
+        hp_igps= @hp_igps,
+        hp_isms= @hp_isms,
+        hp_token_synthetic= @hp_token_synthetic,
+        bob = @0xb0b,
+    )]
+    fun handle_dispatch_test(aptos_framework: signer, hp_router: signer,
+                             hp_mailbox: signer, hp_igps: signer, hp_isms: signer,
+                             hp_token_synthetic: signer, bob: signer) {
+        test_utils::setup(
+            &aptos_framework,
+            &hp_token_synthetic,
+            vector[@hp_mailbox, @hp_igps, @hp_isms, @0xb0b]
+        );
+
+        // enable auid feature because mailbox needs to call `get_transaction_hash()`
+        let feature = features::get_auids();
+        features::change_feature_flags_for_next_epoch(&aptos_framework, vector[feature], vector[]);
+
+        // block must be initilized because mailbox access block resource
+        account::create_account_for_test(@aptos_framework);
+        block::initialize_for_test(&aptos_framework, 1000 /* epoch_interval */);
+
+        mailbox::init_for_test(&hp_mailbox);
+        mailbox::initialize(&hp_mailbox, APTOS_TESTNET_DOMAIN);
+        router::init_for_test(&hp_router);
+
+        synthetic_token::initialize(
+            &hp_token_synthetic,
+            string::utf8(b"Wrapped Ether"),
+            string::utf8(b"WETH"),
+            8,
+            8
+        );
+        let metadata = synthetic_token::get_metadata();
+
+        router::enroll_remote_router<SyntheticToken>(
+            &hp_token_synthetic,
+            REMOTE_DOMAIN,
+            bcs::to_bytes(&REMOTE_ROUTER)
+        );
+
+        // LN1_ISMS_ADDRESS validator, see native_tests for the key
+        let isms1_eth_address = @0x050D907812D2D2de09Ba8D6cE414d6fee84C29Cb;
+        multisig_ism::init_for_test(&hp_isms);
+        multisig_ism::set_validators_and_threshold(
+            &hp_isms,
+            vector[isms1_eth_address],
+            1, // threshold
+            REMOTE_DOMAIN // origin_domain
+        );
+
+        let bob_address = signer::address_of(&bob);
+        let amount: u64 = 12;
+
+        // message sent by the remote router to this one
+        let message_bytes = msg_utils::format_message_into_bytes(
+            utils::get_version(),
+            0, // nonce
+            REMOTE_DOMAIN,
+            REMOTE_ROUTER,
+            APTOS_TESTNET_DOMAIN,
+            bcs::to_bytes<address>(&signer::address_of(&hp_token_synthetic)),
+            msg_utils::format_warp_token_message(bcs::to_bytes<address>(&bob_address), (amount as u256))
+        );
+        let root = x"0000000000000000000000000000000000000000000000000000000000000000";
+        let digest_bytes_to_sign = utils::ism_checkpoint_hash(
+            signer::address_of(&hp_mailbox),
+            REMOTE_DOMAIN,
+            root,
+            0,
+            msg_utils::id(&message_bytes)
+        );
+
+        // if this fails, sign the new digest with sign_msg.js and replace the signature below
+        assert!(&digest_bytes_to_sign == &x"30cc75d63fb5743123938d367c2e82884e55271749e9242154ca3308ad314a3c", 0);
+        let digest_bytes_signature = x"551cc5f1f3b46b3e8f73b885504199922891e4e7792cf7eb09ceba631f59380641b2f34658e7781985c4262773a1fbf00d45cd1619e1ebcda70cfe23f18021aa1b";
+        let metadata_bytes = ism_metadata::format_signature_into_bytes(
+            signer::address_of(&hp_mailbox),
+            root,
+            0,
+            digest_bytes_signature
+        );
+
+        // mint
+        synthetic_token::handle_message(message_bytes, metadata_bytes);
+        assert!(primary_fungible_store::balance(bob_address, metadata) == amount, 0);
+
+        // burn part of it and send it back
+        set_igps_for_test(&hp_igps, REMOTE_DOMAIN,
+            synthetic_token::get_default_gas_amount(),
+            10, // gas price
+            10_000_000_000 // token exchange rate
+        );
+        synthetic_token::transfer_remote(&bob, REMOTE_DOMAIN, bcs::to_bytes<address>(&bob_address), 5);
+
+        assert!(primary_fungible_store::balance(bob_address, metadata) == amount - 5, 0);
+        assert!(mailbox::outbox_get_count() == 1, 0);
+    }
+}
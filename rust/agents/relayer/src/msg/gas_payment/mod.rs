@@ -1,11 +1,11 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_base::db::HyperlaneRocksDB;
 use hyperlane_core::{
     FixedPointNumber, GasPaymentKey, HyperlaneMessage, InterchainGasExpenditure,
-    InterchainGasPayment, TxCostEstimate, TxOutcome, U256,
+    InterchainGasPaymaster, InterchainGasPayment, TxCostEstimate, TxOutcome, U256,
 };
 use tracing::{debug, error, trace};
 
@@ -55,9 +55,12 @@ pub struct GasPaymentEnforcer {
 impl GasPaymentEnforcer {
     /// Note that `policy_configs` should not be empty. In the settings,
     /// a default of vec![GasPaymentEnforcementConf::default()] is used.
+    /// `origin_igp` lets the on-chain fee quoting policy check payments
+    /// against the origin IGP's quotes.
     pub fn new(
         policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
         db: HyperlaneRocksDB,
+        origin_igp: Option<Arc<dyn InterchainGasPaymaster>>,
     ) -> Self {
         let policies = policy_configs
             .into_iter()
@@ -70,7 +73,11 @@ impl GasPaymentEnforcer {
                     GasPaymentEnforcementPolicy::OnChainFeeQuoting {
                         gas_fraction_numerator: n,
                         gas_fraction_denominator: d,
-                    } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(n, d)),
+                    } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(
+                        n,
+                        d,
+                        origin_igp.clone(),
+                    )),
                 };
                 (p, cfg.matching_list)
             })
@@ -208,6 +215,7 @@ mod test {
                     matching_list: Default::default(),
                 }],
                 hyperlane_db,
+                None,
             );
 
             // Ensure that message without any payment is considered as not meeting the
@@ -240,6 +248,7 @@ mod test {
                     matching_list,
                 }],
                 hyperlane_db,
+                None,
             );
 
             assert!(matches!(
@@ -276,6 +285,7 @@ mod test {
                     matching_list: MatchingList::default(),
                 }],
                 hyperlane_db.clone(),
+                None,
             );
 
             let wrong_destination_payment = InterchainGasPayment {
@@ -337,6 +347,7 @@ mod test {
                     matching_list: MatchingList::default(),
                 }],
                 hyperlane_db.clone(),
+                None,
             );
 
             let initial_payment = InterchainGasPayment {
@@ -402,6 +413,7 @@ mod test {
                     },
                 ],
                 hyperlane_db,
+                None,
             );
 
             let sender: H256 = H160::from_str(sender_address).unwrap().into();
@@ -1,9 +1,13 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use eyre::Result;
 
 use hyperlane_core::{
-    HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment, TxCostEstimate, U256,
+    HyperlaneMessage, InterchainGasExpenditure, InterchainGasPaymaster, InterchainGasPayment,
+    TxCostEstimate, U256,
 };
+use tracing::debug;
 
 use crate::msg::gas_payment::GasPaymentPolicy;
 
@@ -15,14 +19,64 @@ pub struct GasPaymentPolicyOnChainFeeQuoting {
     /// Denominator value to modify the estimated gas by. The estimated gas
     /// value is divided by this value.
     fractional_denominator: u64,
+    /// IGP of the origin chain. If it quotes payments on chain, payments
+    /// must also be worth its current quote for the estimated gas.
+    origin_igp: Option<Arc<dyn InterchainGasPaymaster>>,
 }
 
 impl GasPaymentPolicyOnChainFeeQuoting {
-    pub fn new(fractional_numerator: u64, fractional_denominator: u64) -> Self {
+    pub fn new(
+        fractional_numerator: u64,
+        fractional_denominator: u64,
+        origin_igp: Option<Arc<dyn InterchainGasPaymaster>>,
+    ) -> Self {
         Self {
             fractional_numerator,
             fractional_denominator,
+            origin_igp,
+        }
+    }
+
+    /// The gas the origin IGP adds to the gas amount of every payment for
+    /// the message's destination, zero without an IGP that adds any
+    async fn gas_overhead(&self, message: &HyperlaneMessage) -> Result<U256> {
+        let Some(igp) = &self.origin_igp else {
+            return Ok(U256::zero());
+        };
+        Ok(igp
+            .destination_gas_overhead(message.destination)
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Whether `payment` is worth at least the origin IGP's current quote for
+    /// `gas_amount`, which includes the destination's gas overhead. Always
+    /// true without an IGP that quotes payments on chain.
+    async fn payment_covers_quote(
+        &self,
+        message: &HyperlaneMessage,
+        payment: &InterchainGasPayment,
+        gas_amount: U256,
+    ) -> Result<bool> {
+        let Some(igp) = &self.origin_igp else {
+            return Ok(true);
+        };
+        let Some(quote) = igp
+            .quote_gas_payment(message.destination, gas_amount)
+            .await?
+        else {
+            return Ok(true);
+        };
+        if payment.payment < quote {
+            debug!(
+                ?payment,
+                ?quote,
+                ?gas_amount,
+                "Payment is less than the current on-chain quote"
+            );
+            return Ok(false);
         }
+        Ok(true)
     }
 }
 
@@ -32,6 +86,7 @@ impl Default for GasPaymentPolicyOnChainFeeQuoting {
         Self {
             fractional_numerator: 1,
             fractional_denominator: 2,
+            origin_igp: None,
         }
     }
 }
@@ -40,7 +95,7 @@ impl Default for GasPaymentPolicyOnChainFeeQuoting {
 impl GasPaymentPolicy for GasPaymentPolicyOnChainFeeQuoting {
     async fn message_meets_gas_payment_requirement(
         &self,
-        _message: &HyperlaneMessage,
+        message: &HyperlaneMessage,
         current_payment: &InterchainGasPayment,
         current_expenditure: &InterchainGasExpenditure,
         tx_cost_estimate: &TxCostEstimate,
@@ -48,22 +103,28 @@ impl GasPaymentPolicy for GasPaymentPolicyOnChainFeeQuoting {
         let fractional_gas_estimate = (tx_cost_estimate.enforceable_gas_limit()
             * self.fractional_numerator)
             / self.fractional_denominator;
+        // recorded gas amounts include the IGP's overhead, which isn't gas
+        // available to process the message
         let gas_amount = current_payment
             .gas_amount
+            .saturating_sub(self.gas_overhead(message).await?)
             .saturating_sub(current_expenditure.gas_used);
         // We might want to migrate later to a solution which is a little more
         // sophisticated. See https://github.com/hyperlane-xyz/hyperlane-monorepo/pull/1658#discussion_r1093243358
-        if gas_amount >= fractional_gas_estimate {
-            Ok(Some(tx_cost_estimate.gas_limit.max(gas_amount)))
-        } else {
-            Ok(None)
+        if gas_amount < fractional_gas_estimate
+            || !self
+                .payment_covers_quote(message, current_payment, fractional_gas_estimate)
+                .await?
+        {
+            return Ok(None);
         }
+        Ok(Some(tx_cost_estimate.gas_limit.max(gas_amount)))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use hyperlane_core::H256;
+    use hyperlane_core::{ChainResult, HyperlaneContract, H256};
     use once_cell::sync::Lazy;
 
     use super::*;
@@ -239,4 +300,108 @@ mod test {
             Some(tx_cost_estimate.gas_limit),
         );
     }
+
+    /// An IGP quoting 2 tokens per gas plus an overhead of 100 gas
+    #[derive(Debug)]
+    struct QuotingIgp;
+
+    impl HyperlaneContract for QuotingIgp {
+        fn address(&self) -> H256 {
+            H256::zero()
+        }
+    }
+
+    #[async_trait]
+    impl InterchainGasPaymaster for QuotingIgp {
+        async fn quote_gas_payment(
+            &self,
+            _destination: u32,
+            gas_amount: U256,
+        ) -> ChainResult<Option<U256>> {
+            Ok(Some((gas_amount + 100) * 2))
+        }
+
+        async fn destination_gas_overhead(&self, _destination: u32) -> ChainResult<Option<U256>> {
+            Ok(Some(U256::from(100)))
+        }
+    }
+
+    fn quoting_policy() -> GasPaymentPolicyOnChainFeeQuoting {
+        GasPaymentPolicyOnChainFeeQuoting::new(1, 2, Some(Arc::new(QuotingIgp)))
+    }
+
+    /// The quote for half of COST_ESTIMATE's gas limit, including the overhead
+    const QUOTE: U256 = U256([2200, 0, 0, 0]);
+
+    #[tokio::test]
+    async fn test_payment_matching_on_chain_quote() {
+        let policy = quoting_policy();
+        let message = HyperlaneMessage::default();
+
+        // The recorded gas amount includes the overhead
+        let payment = InterchainGasPayment {
+            payment: QUOTE,
+            ..current_payment(MIN * 2 + 100)
+        };
+        assert_eq!(
+            policy
+                .message_meets_gas_payment_requirement(
+                    &message,
+                    &payment,
+                    &current_expenditure(0),
+                    &COST_ESTIMATE,
+                )
+                .await
+                .unwrap(),
+            Some(MIN * 2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_below_on_chain_quote() {
+        let policy = quoting_policy();
+        let message = HyperlaneMessage::default();
+
+        // Enough gas is declared, but the payment is worth less than its quote
+        let payment = InterchainGasPayment {
+            payment: QUOTE - 1,
+            ..current_payment(MIN * 2 + 100)
+        };
+        assert_eq!(
+            policy
+                .message_meets_gas_payment_requirement(
+                    &message,
+                    &payment,
+                    &current_expenditure(0),
+                    &COST_ESTIMATE,
+                )
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_gas_amount_excludes_overhead() {
+        let policy = quoting_policy();
+        let message = HyperlaneMessage::default();
+
+        // Without the overhead the declared gas amount is below the minimum
+        let payment = InterchainGasPayment {
+            payment: QUOTE * 2,
+            ..current_payment(MIN + 99)
+        };
+        assert_eq!(
+            policy
+                .message_meets_gas_payment_requirement(
+                    &message,
+                    &payment,
+                    &current_expenditure(0),
+                    &COST_ESTIMATE,
+                )
+                .await
+                .unwrap(),
+            None
+        );
+    }
 }
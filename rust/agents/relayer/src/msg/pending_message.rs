@@ -268,6 +268,9 @@ impl PendingOperation for PendingMessage {
             .await
         {
             Ok(metadata) => metadata,
+            Err(err @ ChainCommunicationError::TransactionWouldRevert(_)) => {
+                return self.on_reprepare(Some(err), ReprepareReason::WouldRevert);
+            }
             Err(err) => {
                 return self.on_reprepare(Some(err), ReprepareReason::ErrorEstimatingGas);
             }
@@ -634,3 +637,106 @@ impl MessageSubmissionMetrics {
             .set(std::cmp::max(self.last_known_nonce.get(), msg.nonce as i64));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hyperlane_aptos::{AptosMailbox, ConnectionConf, ExternalSubmission};
+    use hyperlane_aptos_test_utils::MockAptosNode;
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::ContractLocator;
+    use serde_json::json;
+
+    use super::*;
+    use crate::msg::processor::test::{
+        dummy_domain, dummy_metadata_builder, dummy_submission_metrics,
+    };
+
+    #[tokio::test]
+    async fn test_external_submission_emits_message_once() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            let path = std::env::temp_dir().join(format!(
+                "relayer-process-payloads-{}.jsonl",
+                std::process::id()
+            ));
+            let _ = std::fs::remove_file(&path);
+
+            // An Aptos mailbox handing process payloads to an external submitter,
+            // which never lands the transaction
+            let node = MockAptosNode::spawn();
+            node.state()
+                .set_view("0xbeef::mailbox::delivered", vec![json!(false)]);
+            node.state().set_view(
+                "0xbeef::mailbox::recipient_module_name",
+                // hex encoded `hello_world`
+                vec![json!("0x68656c6c6f5f776f726c64")],
+            );
+            let conf = ConnectionConf {
+                urls: vec![node.url()],
+                transaction_overrides: Default::default(),
+                external_submission: Some(ExternalSubmission::File(path.clone())),
+                client_metrics: Default::default(),
+                indexer_backend: Default::default(),
+            };
+            let mailbox = AptosMailbox::new(
+                &conf,
+                ContractLocator {
+                    domain: &destination_domain,
+                    address: H256::from_low_u64_be(0xbeef),
+                },
+                None,
+            )
+            .unwrap();
+            let ctx = Arc::new(MessageContext {
+                destination_mailbox: Arc::new(mailbox),
+                origin_db: db.clone(),
+                metadata_builder: Arc::new(dummy_metadata_builder(
+                    &origin_domain,
+                    &destination_domain,
+                    &db,
+                )),
+                origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new(
+                    [],
+                    db.clone(),
+                    None,
+                )),
+                transaction_gas_limit: Default::default(),
+                metrics: dummy_submission_metrics(),
+            });
+            let message = HyperlaneMessage {
+                origin: origin_domain.id(),
+                destination: destination_domain.id(),
+                recipient: H256::from_low_u64_be(0xb0b),
+                ..Default::default()
+            };
+            let mut pending_message = PendingMessage::new(
+                message,
+                ctx,
+                PendingOperationStatus::FirstPrepareAttempt,
+                None,
+            );
+
+            // The message is submitted again every time it is found undelivered
+            for _ in 0..3 {
+                // stands in for `prepare`, which builds the ISM metadata
+                pending_message.submission_data = Some(Box::new(MessageSubmissionData {
+                    metadata: vec![],
+                    gas_limit: U256::from(1000),
+                }));
+                pending_message.submit().await;
+                pending_message.next_attempt_after = None;
+                assert!(matches!(
+                    pending_message.confirm().await,
+                    PendingOperationResult::Reprepare(ReprepareReason::RevertedOrReorged)
+                ));
+            }
+
+            let contents = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(contents.lines().count(), 1);
+            std::fs::remove_file(&path).unwrap();
+        })
+        .await;
+    }
+}
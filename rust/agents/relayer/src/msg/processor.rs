@@ -380,7 +380,7 @@ impl MessageProcessorMetrics {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use std::time::Instant;
 
     use crate::{
@@ -422,7 +422,7 @@ mod test {
         }
     }
 
-    fn dummy_submission_metrics() -> MessageSubmissionMetrics {
+    pub(crate) fn dummy_submission_metrics() -> MessageSubmissionMetrics {
         MessageSubmissionMetrics {
             last_known_nonce: IntGauge::new("last_known_nonce_gauge", "help string").unwrap(),
             messages_processed: IntCounter::new("message_processed_gauge", "help string").unwrap(),
@@ -447,7 +447,7 @@ mod test {
         }
     }
 
-    fn dummy_metadata_builder(
+    pub(crate) fn dummy_metadata_builder(
         origin_domain: &HyperlaneDomain,
         destination_domain: &HyperlaneDomain,
         db: &HyperlaneRocksDB,
@@ -485,7 +485,7 @@ mod test {
             destination_mailbox: Arc::new(MockMailboxContract::default()),
             origin_db: db.clone(),
             metadata_builder: Arc::new(base_metadata_builder),
-            origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new([], db.clone())),
+            origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new([], db.clone(), None)),
             transaction_gas_limit: Default::default(),
             metrics: dummy_submission_metrics(),
         });
@@ -527,7 +527,7 @@ mod test {
         }
     }
 
-    fn dummy_domain(domain_id: u32, name: &str) -> HyperlaneDomain {
+    pub(crate) fn dummy_domain(domain_id: u32, name: &str) -> HyperlaneDomain {
         let test_domain = HyperlaneDomain::new_test_domain(name);
         HyperlaneDomain::Unknown {
             domain_id,
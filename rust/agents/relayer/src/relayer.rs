@@ -42,7 +42,7 @@ use crate::{
         processor::{MessageProcessor, MessageProcessorMetrics},
     },
     server::{self as relayer_server, MessageRetryRequest},
-    settings::{matching_list::MatchingList, RelayerSettings},
+    settings::{matching_list::MatchingList, GasPaymentEnforcementPolicy, RelayerSettings},
 };
 use crate::{
     merkle_tree::processor::{MerkleTreeProcessor, MerkleTreeProcessorMetrics},
@@ -211,6 +211,20 @@ impl BaseAgent for Relayer {
 
         info!(gas_enforcement_policies=?settings.gas_payment_enforcement, "Gas enforcement configuration");
 
+        // only the on-chain fee quoting policy reads the origin IGPs
+        let origin_igps = if settings.gas_payment_enforcement.iter().any(|cfg| {
+            matches!(
+                cfg.policy,
+                GasPaymentEnforcementPolicy::OnChainFeeQuoting { .. }
+            )
+        }) {
+            settings
+                .build_interchain_gas_paymasters(settings.origin_chains.iter(), &core_metrics)
+                .await?
+        } else {
+            HashMap::new()
+        };
+
         // need one of these per origin chain due to the database scoping even though
         // the config itself is the same
         let gas_payment_enforcers: HashMap<_, _> = settings
@@ -222,6 +236,7 @@ impl BaseAgent for Relayer {
                     Arc::new(GasPaymentEnforcer::new(
                         settings.gas_payment_enforcement.clone(),
                         dbs.get(domain).unwrap().clone(),
+                        origin_igps.get(domain).cloned(),
                     )),
                 )
             })
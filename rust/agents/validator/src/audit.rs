@@ -0,0 +1,648 @@
+//! Audit of the checkpoints a validator already published.
+//!
+//! The checkpoint submitter only signs checkpoints that aren't in the
+//! checkpoint syncer yet, so anything published by a previous run (e.g. before
+//! a DB wipe, or by a buggy version) is never looked at again. The audit walks
+//! the published checkpoints, recomputes each of them from the merkle tree
+//! insertions indexed in the local DB and reports those that don't match.
+//! Indices audited without a fault are recorded in the DB, so a restart only
+//! audits the checkpoints published since.
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use hyperlane_base::{db::HyperlaneRocksDB, CheckpointSyncer, CoreMetrics};
+use hyperlane_core::{
+    accumulator::incremental::IncrementalMerkle, rpc_clients::call_and_retry_indefinitely,
+    Checkpoint, CheckpointWithMessageId, HyperlaneDomain, SignedCheckpointWithMessageId, H160,
+    H256,
+};
+use prometheus::IntGauge;
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+use tracing::{debug, error, info};
+
+/// What the validator does with the checkpoints it published before
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckpointAuditMode {
+    /// Don't audit published checkpoints
+    #[default]
+    Off,
+    /// Audit published checkpoints in the background and report faults
+    Report,
+    /// Audit published checkpoints before signing new ones, and don't sign
+    /// any if a fault is found
+    Enforce,
+}
+
+/// Why a published checkpoint failed the audit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditFaultKind {
+    /// Signed by the validator, but not the checkpoint of the local merkle
+    /// tree at its index. Relayers may have seen it, so the validator
+    /// equivocated.
+    Incorrect,
+    /// Not signed by the validator's key, e.g. by the key it used before a
+    /// rotation. Only reported, it doesn't block signing.
+    ForeignSigner,
+    /// At an index the merkle tree hook hasn't reached yet
+    BeyondTree,
+}
+
+impl AuditFaultKind {
+    /// Whether the validator shouldn't sign on top of such a checkpoint
+    pub fn blocks_signing(&self) -> bool {
+        !matches!(self, AuditFaultKind::ForeignSigner)
+    }
+}
+
+/// A published checkpoint that failed the audit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFault {
+    /// Why the checkpoint failed the audit
+    pub kind: AuditFaultKind,
+    /// The checkpoint in the checkpoint syncer
+    pub published: CheckpointWithMessageId,
+    /// The checkpoint recomputed from the local merkle tree, if the tree
+    /// reaches its index
+    pub expected: Option<CheckpointWithMessageId>,
+}
+
+/// Progress of the audit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditStatus {
+    /// The audit is disabled or hasn't started
+    #[default]
+    NotRun,
+    /// Published checkpoints are being walked
+    Running,
+    /// All published checkpoints were audited and none is faulty
+    Passed,
+    /// At least one published checkpoint is faulty
+    Failed,
+}
+
+/// Result of auditing the published checkpoints, served by the validator
+/// HTTP server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    /// Progress of the audit
+    pub status: AuditStatus,
+    /// Latest index in the checkpoint syncer when the audit started
+    pub latest_published_index: Option<u32>,
+    /// Highest index audited so far
+    pub audited_index: Option<u32>,
+    /// Number of indices without a published checkpoint
+    pub missing: u32,
+    /// Published checkpoints that failed the audit
+    pub faults: Vec<AuditFault>,
+}
+
+impl AuditReport {
+    /// Whether a fault was found that the validator shouldn't sign on top of
+    pub fn blocks_signing(&self) -> bool {
+        self.faults.iter().any(|fault| fault.kind.blocks_signing())
+    }
+}
+
+/// The audit report, shared between the auditor and the HTTP server
+pub type SharedAuditReport = Arc<RwLock<AuditReport>>;
+
+/// Audits a published checkpoint against the one recomputed from the local
+/// merkle tree. `expected` is `None` if the tree doesn't reach its index.
+pub fn audit_checkpoint(
+    published: &SignedCheckpointWithMessageId,
+    expected: Option<&CheckpointWithMessageId>,
+    validator: H160,
+) -> Option<AuditFaultKind> {
+    if published.recover().ok() != Some(validator) {
+        return Some(AuditFaultKind::ForeignSigner);
+    }
+    match expected {
+        None => Some(AuditFaultKind::BeyondTree),
+        Some(expected) if *expected != published.value => Some(AuditFaultKind::Incorrect),
+        Some(_) => None,
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct CheckpointAuditor {
+    validator: H160,
+    merkle_tree_hook_address: H256,
+    mailbox_domain: u32,
+    checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+    message_db: HyperlaneRocksDB,
+    metrics: CheckpointAuditMetrics,
+    report: SharedAuditReport,
+}
+
+impl CheckpointAuditor {
+    pub(crate) fn new(
+        validator: H160,
+        merkle_tree_hook_address: H256,
+        mailbox_domain: u32,
+        checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+        message_db: HyperlaneRocksDB,
+        metrics: CheckpointAuditMetrics,
+        report: SharedAuditReport,
+    ) -> Self {
+        Self {
+            validator,
+            merkle_tree_hook_address,
+            mailbox_domain,
+            checkpoint_syncer,
+            message_db,
+            metrics,
+            report,
+        }
+    }
+
+    fn checkpoint(&self, tree: &IncrementalMerkle) -> Checkpoint {
+        Checkpoint {
+            root: tree.root(),
+            index: tree.index(),
+            merkle_tree_hook_address: self.merkle_tree_hook_address,
+            mailbox_domain: self.mailbox_domain,
+        }
+    }
+
+    /// Audits the published checkpoints up to the latest index of the
+    /// checkpoint syncer, from the first index not audited by a previous run.
+    /// `tip` is the latest checkpoint of the merkle tree hook; insertions up
+    /// to it are waited for in the local DB.
+    pub(crate) async fn audit(&self, tip: &Checkpoint) -> AuditReport {
+        let previously_audited_index = self
+            .message_db
+            .retrieve_highest_audited_checkpoint_index()
+            .unwrap_or_else(|err| panic!("Error fetching the highest audited index: {err}"));
+        self.update_report(|report| {
+            *report = AuditReport {
+                status: AuditStatus::Running,
+                audited_index: previously_audited_index,
+                ..Default::default()
+            }
+        });
+        if let Some(index) = previously_audited_index {
+            self.metrics.audited_index.set(index as i64);
+        }
+
+        let latest_published_index = call_and_retry_indefinitely(|| {
+            let checkpoint_syncer = self.checkpoint_syncer.clone();
+            Box::pin(async move { Ok(checkpoint_syncer.latest_index().await?) })
+        })
+        .await;
+        self.update_report(|report| report.latest_published_index = latest_published_index);
+        info!(
+            ?latest_published_index,
+            ?previously_audited_index,
+            tip_index = tip.index,
+            "Auditing published checkpoints"
+        );
+
+        if let Some(latest_published_index) = latest_published_index {
+            let mut tree = IncrementalMerkle::default();
+            let start = previously_audited_index.map_or(0, |index| index + 1);
+            // Checkpoints audited before only need their insertion in the tree
+            for index in 0..start {
+                tree.ingest(self.wait_for_insertion(index).await);
+            }
+            let mut blocked = false;
+            for index in start..=latest_published_index {
+                let fault = self.audit_index(&mut tree, tip, index).await;
+                blocked |= fault.is_some_and(|kind| kind.blocks_signing());
+                // Checkpoints past the tip may still be published over
+                if !blocked && index <= tip.index {
+                    self.message_db
+                        .store_highest_audited_checkpoint_index(index)
+                        .unwrap_or_else(|err| {
+                            panic!("Error storing the highest audited index {index}: {err}")
+                        });
+                }
+            }
+        }
+
+        self.update_report(|report| {
+            report.status = if report.faults.is_empty() {
+                AuditStatus::Passed
+            } else {
+                AuditStatus::Failed
+            };
+        });
+        let report = self
+            .report
+            .read()
+            .expect("audit report lock poisoned")
+            .clone();
+        info!(
+            status = ?report.status,
+            audited_index = ?report.audited_index,
+            missing = report.missing,
+            faults = report.faults.len(),
+            "Finished auditing published checkpoints"
+        );
+        report
+    }
+
+    /// Audits the published checkpoint at `index`, ingesting its insertion
+    /// into `tree` if the merkle tree hook reached it. Returns why the
+    /// checkpoint failed the audit, if it did.
+    async fn audit_index(
+        &self,
+        tree: &mut IncrementalMerkle,
+        tip: &Checkpoint,
+        index: u32,
+    ) -> Option<AuditFaultKind> {
+        let expected = if index <= tip.index {
+            let message_id = self.wait_for_insertion(index).await;
+            tree.ingest(message_id);
+            Some(CheckpointWithMessageId {
+                checkpoint: self.checkpoint(tree),
+                message_id,
+            })
+        } else {
+            None
+        };
+
+        let published = call_and_retry_indefinitely(|| {
+            let checkpoint_syncer = self.checkpoint_syncer.clone();
+            Box::pin(async move { Ok(checkpoint_syncer.fetch_checkpoint(index).await?) })
+        })
+        .await;
+
+        let fault = match published {
+            None => {
+                debug!(index, "No published checkpoint at index");
+                self.update_report(|report| report.missing += 1);
+                None
+            }
+            Some(published) => {
+                let fault = audit_checkpoint(&published, expected.as_ref(), self.validator);
+                if let Some(kind) = fault {
+                    error!(
+                        index,
+                        ?kind,
+                        published = ?published.value,
+                        ?expected,
+                        "Published checkpoint failed the audit"
+                    );
+                    self.update_report(|report| {
+                        report.faults.push(AuditFault {
+                            kind,
+                            published: published.value,
+                            expected,
+                        });
+                        self.metrics.faults.set(report.faults.len() as i64);
+                    });
+                }
+                fault
+            }
+        };
+
+        self.update_report(|report| report.audited_index = Some(index));
+        self.metrics.audited_index.set(index as i64);
+        fault
+    }
+
+    /// Message id of the merkle tree insertion at `index`, waiting for it to
+    /// be indexed
+    async fn wait_for_insertion(&self, index: u32) -> H256 {
+        loop {
+            if let Some(insertion) = self
+                .message_db
+                .retrieve_merkle_tree_insertion_by_leaf_index(&index)
+                .unwrap_or_else(|err| {
+                    panic!("Error fetching merkle tree insertion for leaf index {index}: {err}")
+                })
+            {
+                return insertion.message_id();
+            }
+            sleep(Duration::from_millis(100)).await
+        }
+    }
+
+    fn update_report(&self, update: impl FnOnce(&mut AuditReport)) {
+        update(&mut self.report.write().expect("audit report lock poisoned"));
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct CheckpointAuditMetrics {
+    audited_index: IntGauge,
+    faults: IntGauge,
+}
+
+impl CheckpointAuditMetrics {
+    pub fn new(metrics: &CoreMetrics, mailbox_chain: &HyperlaneDomain) -> eyre::Result<Self> {
+        let chain_name = mailbox_chain.name();
+        Ok(Self {
+            audited_index: metrics
+                .new_int_gauge(
+                    "validator_checkpoint_audit_index",
+                    "Highest index of the published checkpoints audited against the local merkle tree",
+                    &["origin"],
+                )?
+                .with_label_values(&[chain_name]),
+            faults: metrics
+                .new_int_gauge(
+                    "validator_checkpoint_audit_faults",
+                    "Number of published checkpoints that failed the audit",
+                    &["origin"],
+                )?
+                .with_label_values(&[chain_name]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::signers::LocalWallet;
+    use hyperlane_base::{db::test_utils::run_test_db, LocalStorage};
+    use hyperlane_core::{HyperlaneSigner, HyperlaneSignerExt, MerkleTreeInsertion};
+    use hyperlane_ethereum::Signers;
+    use prometheus::Registry;
+
+    use super::*;
+
+    const MERKLE_TREE_HOOK: H256 = H256::repeat_byte(1);
+    const DOMAIN: u32 = 31337;
+
+    fn signer(key: &str) -> Signers {
+        key.parse::<LocalWallet>().unwrap().into()
+    }
+
+    fn validator() -> Signers {
+        signer("0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+    }
+
+    /// Checkpoints of a tree of `count` messages, with a DB holding their
+    /// insertions
+    fn checkpoints(db: &HyperlaneRocksDB, count: u32) -> Vec<CheckpointWithMessageId> {
+        let mut tree = IncrementalMerkle::default();
+        (0..count)
+            .map(|index| {
+                let message_id = H256::from_low_u64_be(index as u64 + 100);
+                db.process_tree_insertion(&MerkleTreeInsertion::new(index, message_id), 1)
+                    .unwrap();
+                tree.ingest(message_id);
+                CheckpointWithMessageId {
+                    checkpoint: Checkpoint {
+                        root: tree.root(),
+                        index,
+                        merkle_tree_hook_address: MERKLE_TREE_HOOK,
+                        mailbox_domain: DOMAIN,
+                    },
+                    message_id,
+                }
+            })
+            .collect()
+    }
+
+    async fn publish(syncer: &LocalStorage, signer: &Signers, checkpoint: CheckpointWithMessageId) {
+        let signed = signer.sign(checkpoint).await.unwrap();
+        syncer.write_checkpoint(&signed).await.unwrap();
+        syncer.update_latest_index(checkpoint.index).await.unwrap();
+    }
+
+    fn auditor(
+        db: &HyperlaneRocksDB,
+        syncer: LocalStorage,
+        core_metrics: &CoreMetrics,
+    ) -> CheckpointAuditor {
+        CheckpointAuditor::new(
+            validator().eth_address(),
+            MERKLE_TREE_HOOK,
+            DOMAIN,
+            Arc::new(syncer),
+            db.clone(),
+            CheckpointAuditMetrics::new(core_metrics, &HyperlaneDomain::new_test_domain("test"))
+                .unwrap(),
+            SharedAuditReport::default(),
+        )
+    }
+
+    fn core_metrics() -> CoreMetrics {
+        CoreMetrics::new("dummy_validator", 37582, Registry::new()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_audit_checkpoint() {
+        let checkpoint = CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                root: H256::repeat_byte(2),
+                index: 0,
+                merkle_tree_hook_address: MERKLE_TREE_HOOK,
+                mailbox_domain: DOMAIN,
+            },
+            message_id: H256::repeat_byte(3),
+        };
+        let mut other = checkpoint;
+        other.checkpoint.root = H256::repeat_byte(4);
+        let validator_address = validator().eth_address();
+        let signed = validator().sign(checkpoint).await.unwrap();
+
+        assert_eq!(
+            audit_checkpoint(&signed, Some(&checkpoint), validator_address),
+            None
+        );
+        assert_eq!(
+            audit_checkpoint(&signed, Some(&other), validator_address),
+            Some(AuditFaultKind::Incorrect)
+        );
+        assert_eq!(
+            audit_checkpoint(&signed, None, validator_address),
+            Some(AuditFaultKind::BeyondTree)
+        );
+
+        let foreign = signer("0x0123456789012345678901234567890123456789012345678901234567890123")
+            .sign(checkpoint)
+            .await
+            .unwrap();
+        assert_eq!(
+            audit_checkpoint(&foreign, Some(&checkpoint), validator_address),
+            Some(AuditFaultKind::ForeignSigner)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_audit_passes_on_correct_checkpoints() {
+        run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test"), db);
+            let checkpoints = checkpoints(&db, 3);
+            let dir = tempfile::tempdir().unwrap();
+            let syncer = LocalStorage::new(dir.path().to_path_buf(), None).unwrap();
+            for checkpoint in [checkpoints[0], checkpoints[2]] {
+                publish(&syncer, &validator(), checkpoint).await;
+            }
+
+            let core_metrics = core_metrics();
+            let report = auditor(&db, syncer, &core_metrics)
+                .audit(&checkpoints[2].checkpoint)
+                .await;
+
+            assert_eq!(
+                report,
+                AuditReport {
+                    status: AuditStatus::Passed,
+                    latest_published_index: Some(2),
+                    audited_index: Some(2),
+                    missing: 1,
+                    faults: vec![],
+                }
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_audit_passes_without_published_checkpoints() {
+        run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test"), db);
+            let checkpoints = checkpoints(&db, 1);
+            let dir = tempfile::tempdir().unwrap();
+            let syncer = LocalStorage::new(dir.path().to_path_buf(), None).unwrap();
+
+            let core_metrics = core_metrics();
+            let report = auditor(&db, syncer, &core_metrics)
+                .audit(&checkpoints[0].checkpoint)
+                .await;
+
+            assert_eq!(report.status, AuditStatus::Passed);
+            assert_eq!(report.audited_index, None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_audit_reports_faulty_checkpoints() {
+        run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test"), db);
+            let checkpoints = checkpoints(&db, 2);
+            let dir = tempfile::tempdir().unwrap();
+            let syncer = LocalStorage::new(dir.path().to_path_buf(), None).unwrap();
+
+            // A correct checkpoint, one signed over a different tree at index 1,
+            // and one past the tip of the merkle tree hook
+            let mut incorrect = checkpoints[1];
+            incorrect.checkpoint.root = H256::repeat_byte(0xee);
+            let mut beyond_tree = checkpoints[1];
+            beyond_tree.checkpoint.index = 2;
+            for checkpoint in [checkpoints[0], incorrect, beyond_tree] {
+                publish(&syncer, &validator(), checkpoint).await;
+            }
+
+            let core_metrics = core_metrics();
+            let auditor = auditor(&db, syncer, &core_metrics);
+            let report = auditor.audit(&checkpoints[1].checkpoint).await;
+
+            assert_eq!(report.status, AuditStatus::Failed);
+            assert_eq!(report.audited_index, Some(2));
+            assert_eq!(report.missing, 0);
+            assert_eq!(
+                report.faults,
+                vec![
+                    AuditFault {
+                        kind: AuditFaultKind::Incorrect,
+                        published: incorrect,
+                        expected: Some(checkpoints[1]),
+                    },
+                    AuditFault {
+                        kind: AuditFaultKind::BeyondTree,
+                        published: beyond_tree,
+                        expected: None,
+                    },
+                ]
+            );
+            assert_eq!(*auditor.report.read().unwrap(), report);
+            assert_eq!(auditor.metrics.faults.get(), 2);
+            assert_eq!(auditor.metrics.audited_index.get(), 2);
+            assert!(report.blocks_signing());
+            // The faulty checkpoints are audited again on restart
+            assert_eq!(
+                db.retrieve_highest_audited_checkpoint_index().unwrap(),
+                Some(0)
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_audit_resumes_from_audited_index() {
+        run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test"), db);
+            let checkpoints = checkpoints(&db, 4);
+            let dir = tempfile::tempdir().unwrap();
+            let syncer = LocalStorage::new(dir.path().to_path_buf(), None).unwrap();
+            for checkpoint in [checkpoints[0], checkpoints[2]] {
+                publish(&syncer, &validator(), checkpoint).await;
+            }
+
+            let core_metrics = core_metrics();
+            let report = auditor(&db, syncer.clone(), &core_metrics)
+                .audit(&checkpoints[2].checkpoint)
+                .await;
+            assert_eq!(report.missing, 1);
+            assert_eq!(
+                db.retrieve_highest_audited_checkpoint_index().unwrap(),
+                Some(2)
+            );
+
+            // Index 1 is still missing, but only index 3 is audited again
+            publish(&syncer, &validator(), checkpoints[3]).await;
+            let report = auditor(&db, syncer, &core_metrics)
+                .audit(&checkpoints[3].checkpoint)
+                .await;
+            assert_eq!(
+                report,
+                AuditReport {
+                    status: AuditStatus::Passed,
+                    latest_published_index: Some(3),
+                    audited_index: Some(3),
+                    missing: 0,
+                    faults: vec![],
+                }
+            );
+            assert_eq!(
+                db.retrieve_highest_audited_checkpoint_index().unwrap(),
+                Some(3)
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_audit_only_reports_foreign_signers() {
+        run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test"), db);
+            let checkpoints = checkpoints(&db, 2);
+            let dir = tempfile::tempdir().unwrap();
+            let syncer = LocalStorage::new(dir.path().to_path_buf(), None).unwrap();
+            // Signed by the key used before a rotation
+            let rotated =
+                signer("0x0123456789012345678901234567890123456789012345678901234567890123");
+            publish(&syncer, &rotated, checkpoints[0]).await;
+            publish(&syncer, &validator(), checkpoints[1]).await;
+
+            let core_metrics = core_metrics();
+            let report = auditor(&db, syncer, &core_metrics)
+                .audit(&checkpoints[1].checkpoint)
+                .await;
+
+            assert_eq!(report.status, AuditStatus::Failed);
+            assert_eq!(report.faults.len(), 1);
+            assert_eq!(report.faults[0].kind, AuditFaultKind::ForeignSigner);
+            assert!(!report.blocks_signing());
+            assert_eq!(
+                db.retrieve_highest_audited_checkpoint_index().unwrap(),
+                Some(1)
+            );
+        })
+        .await;
+    }
+}
@@ -9,8 +9,10 @@ use hyperlane_base::agent_main;
 
 use crate::validator::Validator;
 
+mod audit;
 mod server;
 mod settings;
+mod signing_history;
 mod submit;
 mod validator;
 
@@ -0,0 +1,96 @@
+//! Serves the audit of the checkpoints the validator already published.
+//!
+//! Base URL /checkpoint_audit
+//! Routes
+//! - / - Audit report
+//!   eg. response {"status":"passed","latestPublishedIndex":41,"auditedIndex":41,"missing":0,"faults":[]}
+//!   with status 200, or 503 if a published checkpoint failed the audit
+
+use axum::{
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, Router},
+    Json,
+};
+use derive_new::new;
+
+use crate::audit::{AuditStatus, SharedAuditReport};
+
+const CHECKPOINT_AUDIT_API_BASE: &str = "/checkpoint_audit";
+
+#[derive(new)]
+pub struct CheckpointAuditApi {
+    report: SharedAuditReport,
+}
+
+impl CheckpointAuditApi {
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (CHECKPOINT_AUDIT_API_BASE, self.router())
+    }
+
+    pub fn router(&self) -> Router {
+        let report = self.report.clone();
+        Router::new().route("/", get(move || Self::report_handler(report.clone())))
+    }
+
+    /// Method to return the audit report
+    pub async fn report_handler(report: SharedAuditReport) -> impl IntoResponse {
+        let report = report.read().expect("audit report lock poisoned").clone();
+        let status = match report.status {
+            AuditStatus::Failed => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::OK,
+        };
+        (status, Json(report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        audit::{AuditFault, AuditFaultKind, AuditReport},
+        signing_history::test::checkpoint,
+    };
+
+    #[tokio::test]
+    async fn test_checkpoint_audit_api() {
+        let report = SharedAuditReport::default();
+        let app = CheckpointAuditApi::new(report.clone()).router();
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        let client = reqwest::Client::new();
+
+        let res = client
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), StatusCode::OK);
+        let json: AuditReport = res.json().await.expect("Failed to parse json");
+        assert_eq!(json, AuditReport::default());
+
+        let checkpoint = checkpoint(0, 2);
+        let failed = AuditReport {
+            status: AuditStatus::Failed,
+            latest_published_index: Some(0),
+            audited_index: Some(0),
+            missing: 0,
+            faults: vec![AuditFault {
+                kind: AuditFaultKind::ForeignSigner,
+                published: checkpoint,
+                expected: Some(checkpoint),
+            }],
+        };
+        *report.write().unwrap() = failed.clone();
+        let res = client
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let json: AuditReport = res.json().await.expect("Failed to parse json");
+        assert_eq!(json, failed);
+    }
+}
@@ -1,19 +1,33 @@
+pub mod checkpoint_audit;
 pub mod eigen_node;
+pub mod signing_history;
 use std::{sync::Arc, vec};
 
 use axum::Router;
+pub use checkpoint_audit::CheckpointAuditApi;
 pub use eigen_node::EigenNodeApi;
+pub use signing_history::SigningHistoryApi;
 
 use hyperlane_base::CoreMetrics;
 use hyperlane_core::HyperlaneDomain;
 
+use crate::{audit::SharedAuditReport, signing_history::SigningHistory};
+
 /// Returns a vector of validator-specific endpoint routes to be served.
 /// Can be extended with additional routes and feature flags to enable/disable individually.
 pub fn routes(
     origin_chain: HyperlaneDomain,
     metrics: Arc<CoreMetrics>,
+    audit_report: SharedAuditReport,
+    signing_history: SigningHistory,
 ) -> Vec<(&'static str, Router)> {
     let eigen_node_api = EigenNodeApi::new(origin_chain, metrics);
+    let checkpoint_audit_api = CheckpointAuditApi::new(audit_report);
+    let signing_history_api = SigningHistoryApi::new(signing_history);
 
-    vec![eigen_node_api.get_route()]
+    vec![
+        eigen_node_api.get_route(),
+        checkpoint_audit_api.get_route(),
+        signing_history_api.get_route(),
+    ]
 }
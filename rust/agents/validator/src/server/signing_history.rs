@@ -0,0 +1,84 @@
+//! Serves the history of the checkpoints the validator signed, to be imported
+//! by another validator with the `signingHistoryImport` setting.
+//!
+//! Base URL /signing_history
+//! Routes
+//! - / - Signing history
+//!   eg. response {"formatVersion":1,"validator":"0x...","originDomain":1,"signedCheckpoints":[{"index":0,"digest":"0x..."}]}
+
+use axum::{
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, Router},
+    Json,
+};
+use derive_new::new;
+
+use crate::signing_history::SigningHistory;
+
+const SIGNING_HISTORY_API_BASE: &str = "/signing_history";
+
+#[derive(new)]
+pub struct SigningHistoryApi {
+    signing_history: SigningHistory,
+}
+
+impl SigningHistoryApi {
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (SIGNING_HISTORY_API_BASE, self.router())
+    }
+
+    pub fn router(&self) -> Router {
+        let signing_history = self.signing_history.clone();
+        Router::new().route(
+            "/",
+            get(move || Self::export_handler(signing_history.clone())),
+        )
+    }
+
+    /// Method to return the exported signing history
+    pub async fn export_handler(signing_history: SigningHistory) -> impl IntoResponse {
+        match signing_history.export() {
+            Ok(export) => Json(export).into_response(),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperlane_base::db::{test_utils::run_test_db, HyperlaneRocksDB};
+    use hyperlane_core::{HyperlaneDomain, H160};
+
+    use super::*;
+    use crate::signing_history::{test::checkpoint, SigningHistoryExport};
+
+    #[tokio::test]
+    async fn test_signing_history_api() {
+        run_test_db(|db| async move {
+            let signing_history = SigningHistory::new(
+                HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain("test"), db),
+                H160::repeat_byte(0xaa),
+            );
+            signing_history.record(&checkpoint(0, 2)).unwrap();
+
+            let app = SigningHistoryApi::new(signing_history.clone()).router();
+            let server =
+                axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+            let addr = server.local_addr();
+            tokio::spawn(server);
+
+            let res = reqwest::Client::new()
+                .get(format!("http://{}/", addr))
+                .send()
+                .await
+                .expect("Failed to send request");
+            assert_eq!(res.status(), StatusCode::OK);
+            let json: SigningHistoryExport =
+                serde_json::from_str(&res.text().await.unwrap()).expect("Failed to parse json");
+            assert_eq!(json, signing_history.export().unwrap());
+            assert_eq!(json.signed_checkpoints.len(), 1);
+        })
+        .await;
+    }
+}
@@ -19,6 +19,8 @@ use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, HyperlaneDomain
 use serde::Deserialize;
 use serde_json::Value;
 
+use crate::audit::CheckpointAuditMode;
+
 /// Settings for `Validator`
 #[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
 pub struct ValidatorSettings {
@@ -40,6 +42,12 @@ pub struct ValidatorSettings {
     pub reorg_period: u64,
     /// How frequently to check for new checkpoints
     pub interval: Duration,
+    /// Whether to audit the checkpoints published before against the local
+    /// merkle tree
+    pub checkpoint_audit: CheckpointAuditMode,
+    /// Signing history exported by another validator with the same key, to
+    /// import before signing checkpoints
+    pub signing_history_import: Option<PathBuf>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -114,6 +122,18 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             .map(Duration::from_secs)
             .unwrap_or(Duration::from_secs(5));
 
+        let checkpoint_audit = p
+            .chain(&mut err)
+            .get_opt_key("checkpointAudit")
+            .parse_value("Expected checkpoint audit mode `off`, `report` or `enforce`")
+            .unwrap_or_default();
+
+        let signing_history_import = p
+            .chain(&mut err)
+            .get_opt_key("signingHistoryImport")
+            .parse_from_str("Expected signing history file path")
+            .end();
+
         cfg_unwrap_all!(cwp, err: [origin_chain_name]);
 
         let reorg_period = p
@@ -129,7 +149,10 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
 
         let mut base: Settings = base;
         // If the origin chain is an EVM chain, then we can use the validator as the signer if needed.
-        if origin_chain.domain_protocol() == HyperlaneDomainProtocol::Ethereum {
+        // Remote signers can't sign transactions.
+        if origin_chain.domain_protocol() == HyperlaneDomainProtocol::Ethereum
+            && !matches!(validator, SignerConf::Web3Signer { .. })
+        {
             if let Some(origin) = base.chains.get_mut(origin_chain.name()) {
                 origin.signer.get_or_insert_with(|| validator.clone());
             }
@@ -143,6 +166,8 @@ impl FromRawConf<RawValidatorSettings> for ValidatorSettings {
             checkpoint_syncer,
             reorg_period,
             interval,
+            checkpoint_audit,
+            signing_history_import,
         })
     }
 }
@@ -188,6 +213,15 @@ fn parse_checkpoint_syncer(syncer: ValueParser) -> ConfigResult<CheckpointSyncer
                 folder,
             })
         }
+        Some("http") => {
+            let url = syncer
+                .chain(&mut err)
+                .get_key("url")
+                .parse_from_str("Expected checkpoint syncer url")
+                .end();
+            cfg_unwrap_all!(&syncer.cwp, err: [url]);
+            err.into_result(CheckpointSyncerConf::Http { url })
+        }
         Some(_) => {
             Err(eyre!("Unknown checkpoint syncer type")).into_config_result(|| &syncer.cwp + "type")
         }
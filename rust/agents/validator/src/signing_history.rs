@@ -0,0 +1,420 @@
+//! History of the checkpoints the validator signed.
+//!
+//! The signing hash of every checkpoint is recorded in the validator DB before
+//! it is signed, and a checkpoint whose hash differs from the one recorded at
+//! its index is never signed. The history can be exported from a running
+//! validator and imported into a new one, so that it stays protected when
+//! moved to other hardware.
+
+use std::{fs, path::Path};
+
+use eyre::Context;
+use hyperlane_base::db::{DbError, HyperlaneRocksDB};
+use hyperlane_core::{Checkpoint, CheckpointWithMessageId, Signable, H160, H256};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Version of the export format, bumped on incompatible changes
+pub const SIGNING_HISTORY_FORMAT_VERSION: u32 = 1;
+
+/// A checkpoint signed by the validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedCheckpointDigest {
+    /// Index of the checkpoint
+    pub index: u32,
+    /// Signing hash of the checkpoint
+    pub digest: H256,
+}
+
+/// Signing history of a validator, as exported and imported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningHistoryExport {
+    /// Version of the export format
+    pub format_version: u32,
+    /// Address of the validator
+    pub validator: H160,
+    /// Domain the checkpoints were signed for
+    pub origin_domain: u32,
+    /// The signed checkpoints, by increasing index
+    pub signed_checkpoints: Vec<SignedCheckpointDigest>,
+}
+
+/// Error types for the signing history
+#[derive(Debug, thiserror::Error)]
+pub enum SigningHistoryError {
+    /// A different checkpoint was signed at the index
+    #[error(
+        "Checkpoint {digest:?} at index {index} conflicts with checkpoint {signed:?} signed before"
+    )]
+    Conflict {
+        /// Index of the checkpoint
+        index: u32,
+        /// Signing hash of the conflicting checkpoint
+        digest: H256,
+        /// Signing hash of the checkpoint signed before
+        signed: H256,
+    },
+    /// The imported history is of another validator
+    #[error(
+        "Signing history of validator {history:?} can't be imported by validator {validator:?}"
+    )]
+    WrongValidator {
+        /// Validator of the imported history
+        history: H160,
+        /// This validator
+        validator: H160,
+    },
+    /// The imported history is of another origin domain
+    #[error("Signing history of domain {history} can't be imported for domain {domain}")]
+    WrongDomain {
+        /// Domain of the imported history
+        history: u32,
+        /// Domain of this validator
+        domain: u32,
+    },
+    /// The imported history has an unknown format
+    #[error("Unsupported signing history format version {0}")]
+    UnsupportedVersion(u32),
+    /// Reading or writing the DB failed
+    #[error(transparent)]
+    Db(#[from] DbError),
+}
+
+/// The checkpoints a validator signed, stored in its DB
+#[derive(Debug, Clone)]
+pub struct SigningHistory {
+    db: HyperlaneRocksDB,
+    validator: H160,
+}
+
+impl SigningHistory {
+    /// Signing history of `validator` in `db`
+    pub fn new(db: HyperlaneRocksDB, validator: H160) -> Self {
+        Self { db, validator }
+    }
+
+    /// Records a checkpoint about to be signed. Fails without recording it if
+    /// a different checkpoint was signed at its index.
+    pub fn record(&self, checkpoint: &CheckpointWithMessageId) -> Result<(), SigningHistoryError> {
+        let digest = checkpoint.signing_hash();
+        if self.check_digest(checkpoint.index, digest)? {
+            self.db
+                .store_signed_checkpoint_digest(checkpoint.index, &digest)?;
+        }
+        Ok(())
+    }
+
+    /// Whether `digest` can be signed at `index` without having been signed
+    /// before
+    fn check_digest(&self, index: u32, digest: H256) -> Result<bool, SigningHistoryError> {
+        match self.db.retrieve_signed_checkpoint_digest(index)? {
+            Some(signed) if signed != digest => Err(SigningHistoryError::Conflict {
+                index,
+                digest,
+                signed,
+            }),
+            Some(_) => Ok(false),
+            None => Ok(true),
+        }
+    }
+
+    /// All checkpoints signed by the validator
+    pub fn export(&self) -> Result<SigningHistoryExport, DbError> {
+        let mut signed_checkpoints = vec![];
+        if let Some(highest) = self.db.retrieve_highest_signed_checkpoint_index()? {
+            for index in 0..=highest {
+                if let Some(digest) = self.db.retrieve_signed_checkpoint_digest(index)? {
+                    signed_checkpoints.push(SignedCheckpointDigest { index, digest });
+                }
+            }
+        }
+        Ok(SigningHistoryExport {
+            format_version: SIGNING_HISTORY_FORMAT_VERSION,
+            validator: self.validator,
+            origin_domain: self.db.domain().id(),
+            signed_checkpoints,
+        })
+    }
+
+    /// Adds the checkpoints of an exported history. Nothing is imported if
+    /// the history is of another validator or domain, or if any of its
+    /// checkpoints conflicts with one signed before. Returns the number of
+    /// checkpoints that weren't in the history yet.
+    pub fn import(&self, history: &SigningHistoryExport) -> Result<usize, SigningHistoryError> {
+        if history.format_version != SIGNING_HISTORY_FORMAT_VERSION {
+            return Err(SigningHistoryError::UnsupportedVersion(
+                history.format_version,
+            ));
+        }
+        if history.validator != self.validator {
+            return Err(SigningHistoryError::WrongValidator {
+                history: history.validator,
+                validator: self.validator,
+            });
+        }
+        let domain = self.db.domain().id();
+        if history.origin_domain != domain {
+            return Err(SigningHistoryError::WrongDomain {
+                history: history.origin_domain,
+                domain,
+            });
+        }
+
+        let mut new_checkpoints = vec![];
+        for signed in &history.signed_checkpoints {
+            if self.check_digest(signed.index, signed.digest)? {
+                new_checkpoints.push(signed);
+            }
+        }
+        for signed in &new_checkpoints {
+            self.db
+                .store_signed_checkpoint_digest(signed.index, &signed.digest)?;
+        }
+        Ok(new_checkpoints.len())
+    }
+
+    /// Adds the roots signed before the signing hashes of checkpoints were
+    /// recorded, as the signing hashes of their checkpoints. Runs once per DB.
+    /// Returns the number of checkpoints that weren't in the history yet.
+    pub fn import_legacy_roots(
+        &self,
+        merkle_tree_hook_address: H256,
+    ) -> Result<usize, SigningHistoryError> {
+        if self.db.retrieve_signed_checkpoint_roots_imported()? {
+            return Ok(0);
+        }
+
+        // A root was only signed once the message at its index was inserted
+        let mut imported = 0;
+        let mut index = 0;
+        while let Some(insertion) = self
+            .db
+            .retrieve_merkle_tree_insertion_by_leaf_index(&index)?
+        {
+            if let Some(root) = self.db.retrieve_legacy_signed_checkpoint_root(index)? {
+                let checkpoint = CheckpointWithMessageId {
+                    checkpoint: Checkpoint {
+                        merkle_tree_hook_address,
+                        mailbox_domain: self.db.domain().id(),
+                        root,
+                        index,
+                    },
+                    message_id: insertion.message_id(),
+                };
+                let digest = checkpoint.signing_hash();
+                if self.check_digest(index, digest)? {
+                    self.db.store_signed_checkpoint_digest(index, &digest)?;
+                    imported += 1;
+                }
+            }
+            index += 1;
+        }
+        self.db.store_signed_checkpoint_roots_imported()?;
+        if imported > 0 {
+            info!(imported, "Imported legacy signed checkpoint roots");
+        }
+        Ok(imported)
+    }
+
+    /// Imports the history exported to the JSON file at `path`
+    pub fn import_file(&self, path: &Path) -> eyre::Result<()> {
+        let history: SigningHistoryExport = serde_json::from_slice(
+            &fs::read(path).with_context(|| format!("Reading signing history {path:?}"))?,
+        )
+        .with_context(|| format!("Parsing signing history {path:?}"))?;
+        let imported = self.import(&history)?;
+        info!(
+            ?path,
+            imported,
+            total = history.signed_checkpoints.len(),
+            "Imported signing history"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use hyperlane_base::db::{test_utils::run_test_db, DB};
+    use hyperlane_core::{HyperlaneDomain, MerkleTreeInsertion};
+
+    use super::*;
+
+    const VALIDATOR: H160 = H160::repeat_byte(0xaa);
+
+    pub(crate) fn checkpoint(index: u32, root: u8) -> CheckpointWithMessageId {
+        CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(1),
+                mailbox_domain: 31337,
+                root: H256::repeat_byte(root),
+                index,
+            },
+            message_id: H256::repeat_byte(3),
+        }
+    }
+
+    fn history(db: DB, name: &str) -> SigningHistory {
+        SigningHistory::new(
+            HyperlaneRocksDB::new(&HyperlaneDomain::new_test_domain(name), db),
+            VALIDATOR,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refuses_conflicting_checkpoints() {
+        run_test_db(|db| async move {
+            let history = history(db, "test");
+            history.record(&checkpoint(0, 1)).unwrap();
+            history.record(&checkpoint(0, 1)).unwrap();
+            history.record(&checkpoint(2, 1)).unwrap();
+
+            let err = history.record(&checkpoint(0, 2)).unwrap_err();
+            assert!(matches!(
+                err,
+                SigningHistoryError::Conflict { index: 0, digest, signed }
+                    if digest == checkpoint(0, 2).signing_hash()
+                        && signed == checkpoint(0, 1).signing_hash()
+            ));
+
+            let export = history.export().unwrap();
+            assert_eq!(
+                export.signed_checkpoints,
+                vec![
+                    SignedCheckpointDigest {
+                        index: 0,
+                        digest: checkpoint(0, 1).signing_hash(),
+                    },
+                    SignedCheckpointDigest {
+                        index: 2,
+                        digest: checkpoint(2, 1).signing_hash(),
+                    },
+                ]
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_imports_legacy_roots() {
+        run_test_db(|db| async move {
+            let history = history(db, "test");
+            for index in 0..3 {
+                history
+                    .db
+                    .store_merkle_tree_insertion_by_leaf_index(
+                        &index,
+                        &MerkleTreeInsertion::new(index, H256::repeat_byte(3)),
+                    )
+                    .unwrap();
+            }
+            for index in [0, 2] {
+                history
+                    .db
+                    .store_keyed_encodable(
+                        "signed_checkpoint_root_by_index_",
+                        &index,
+                        &H256::repeat_byte(1),
+                    )
+                    .unwrap();
+            }
+
+            assert_eq!(
+                history.import_legacy_roots(H256::repeat_byte(1)).unwrap(),
+                2
+            );
+            assert_eq!(
+                history.export().unwrap().signed_checkpoints,
+                vec![
+                    SignedCheckpointDigest {
+                        index: 0,
+                        digest: checkpoint(0, 1).signing_hash(),
+                    },
+                    SignedCheckpointDigest {
+                        index: 2,
+                        digest: checkpoint(2, 1).signing_hash(),
+                    },
+                ]
+            );
+            assert!(matches!(
+                history.record(&checkpoint(2, 2)),
+                Err(SigningHistoryError::Conflict { index: 2, .. })
+            ));
+            // The roots are only imported once
+            assert_eq!(
+                history.import_legacy_roots(H256::repeat_byte(1)).unwrap(),
+                0
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import() {
+        run_test_db(|old_db| async move {
+            let old = history(old_db, "test");
+            old.record(&checkpoint(0, 1)).unwrap();
+            old.record(&checkpoint(1, 1)).unwrap();
+            let export = old.export().unwrap();
+
+            run_test_db(|new_db| async move {
+                let new = history(new_db, "test");
+                new.record(&checkpoint(1, 1)).unwrap();
+                assert_eq!(new.import(&export).unwrap(), 1);
+                assert_eq!(new.export().unwrap(), export);
+                assert!(matches!(
+                    new.record(&checkpoint(0, 2)),
+                    Err(SigningHistoryError::Conflict { index: 0, .. })
+                ));
+            })
+            .await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_import_refuses_conflicting_histories() {
+        run_test_db(|db| async move {
+            let history = history(db, "test");
+            history.record(&checkpoint(1, 2)).unwrap();
+            let mut export = SigningHistoryExport {
+                format_version: SIGNING_HISTORY_FORMAT_VERSION,
+                validator: VALIDATOR,
+                origin_domain: HyperlaneDomain::new_test_domain("test").id(),
+                signed_checkpoints: vec![
+                    SignedCheckpointDigest {
+                        index: 0,
+                        digest: checkpoint(0, 1).signing_hash(),
+                    },
+                    SignedCheckpointDigest {
+                        index: 1,
+                        digest: checkpoint(1, 1).signing_hash(),
+                    },
+                ],
+            };
+
+            assert!(matches!(
+                history.import(&export),
+                Err(SigningHistoryError::Conflict { index: 1, .. })
+            ));
+            // Nothing was imported
+            assert_eq!(history.export().unwrap().signed_checkpoints.len(), 1);
+
+            export.signed_checkpoints.pop();
+            export.validator = H160::repeat_byte(0xbb);
+            assert!(matches!(
+                history.import(&export),
+                Err(SigningHistoryError::WrongValidator { .. })
+            ));
+            export.validator = VALIDATOR;
+            export.origin_domain += 1;
+            assert!(matches!(
+                history.import(&export),
+                Err(SigningHistoryError::WrongDomain { .. })
+            ));
+        })
+        .await;
+    }
+}
@@ -16,6 +16,8 @@ use hyperlane_core::{
 };
 use hyperlane_ethereum::SingletonSignerHandle;
 
+use crate::signing_history::{SigningHistory, SigningHistoryError};
+
 #[derive(Clone)]
 pub(crate) struct ValidatorSubmitter {
     interval: Duration,
@@ -24,6 +26,7 @@ pub(crate) struct ValidatorSubmitter {
     merkle_tree_hook: Arc<dyn MerkleTreeHook>,
     checkpoint_syncer: Arc<dyn CheckpointSyncer>,
     message_db: HyperlaneRocksDB,
+    signing_history: SigningHistory,
     metrics: ValidatorSubmitterMetrics,
 }
 
@@ -35,6 +38,7 @@ impl ValidatorSubmitter {
         signer: SingletonSignerHandle,
         checkpoint_syncer: Arc<dyn CheckpointSyncer>,
         message_db: HyperlaneRocksDB,
+        signing_history: SigningHistory,
         metrics: ValidatorSubmitterMetrics,
     ) -> Self {
         Self {
@@ -44,6 +48,7 @@ impl ValidatorSubmitter {
             signer,
             checkpoint_syncer,
             message_db,
+            signing_history,
             metrics,
         }
     }
@@ -240,6 +245,18 @@ impl ValidatorSubmitter {
             debug!(index = checkpoint.index, "Checkpoint already submitted");
             return Ok(());
         }
+        // Record the checkpoint before signing it, so that a different one is
+        // never signed at its index even if this one never reaches the
+        // checkpoint syncer. Like a root mismatch with the correctness
+        // checkpoint, a conflict means something went wrong and we bail loudly.
+        match self.signing_history.record(&checkpoint) {
+            Ok(()) => {}
+            Err(SigningHistoryError::Db(err)) => return Err(err.into()),
+            Err(err) => {
+                error!(?checkpoint, ?err, "Refusing to sign checkpoint");
+                panic!("Refusing to sign checkpoint: {err}");
+            }
+        }
         let signed_checkpoint = self.signer.sign(checkpoint).await?;
         self.checkpoint_syncer
             .write_checkpoint(&signed_checkpoint)
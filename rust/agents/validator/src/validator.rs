@@ -18,14 +18,16 @@ use hyperlane_base::{
 };
 
 use hyperlane_core::{
-    Announcement, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneSigner,
-    HyperlaneSignerExt, Mailbox, MerkleTreeHook, MerkleTreeInsertion, TxOutcome, ValidatorAnnounce,
-    H256, U256,
+    rpc_clients::call_and_retry_indefinitely, Announcement, ChainResult, Checkpoint,
+    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneSigner, HyperlaneSignerExt,
+    Mailbox, MerkleTreeHook, MerkleTreeInsertion, TxOutcome, ValidatorAnnounce, H256, U256,
 };
 use hyperlane_ethereum::{SingletonSigner, SingletonSignerHandle};
 
 use crate::{
+    audit::{CheckpointAuditMetrics, CheckpointAuditMode, CheckpointAuditor, SharedAuditReport},
     settings::ValidatorSettings,
+    signing_history::SigningHistory,
     submit::{ValidatorSubmitter, ValidatorSubmitterMetrics},
 };
 
@@ -47,6 +49,9 @@ pub struct Validator {
     reorg_period: u64,
     interval: Duration,
     checkpoint_syncer: Arc<dyn CheckpointSyncer>,
+    checkpoint_audit: CheckpointAuditMode,
+    audit_report: SharedAuditReport,
+    signing_history: SigningHistory,
     core_metrics: Arc<CoreMetrics>,
     agent_metrics: AgentMetrics,
     chain_metrics: ChainMetrics,
@@ -76,6 +81,11 @@ impl BaseAgent for Validator {
         // Intentionally using hyperlane_ethereum for the validator's signer
         let (signer_instance, signer) = SingletonSigner::new(settings.validator.build().await?);
 
+        let signing_history = SigningHistory::new(msg_db.clone(), signer.eth_address());
+        if let Some(path) = &settings.signing_history_import {
+            signing_history.import_file(path)?;
+        }
+
         let core = settings.build_hyperlane_core(metrics.clone());
         let checkpoint_syncer = settings.checkpoint_syncer.build(None).await?.into();
 
@@ -86,6 +96,7 @@ impl BaseAgent for Validator {
         let merkle_tree_hook = settings
             .build_merkle_tree_hook(&settings.origin_chain, &metrics)
             .await?;
+        signing_history.import_legacy_roots(merkle_tree_hook.address())?;
 
         let validator_announce = settings
             .build_validator_announce(&settings.origin_chain, &metrics)
@@ -122,6 +133,9 @@ impl BaseAgent for Validator {
             reorg_period: settings.reorg_period,
             interval: settings.interval,
             checkpoint_syncer,
+            checkpoint_audit: settings.checkpoint_audit,
+            audit_report: SharedAuditReport::default(),
+            signing_history,
             agent_metrics,
             chain_metrics,
             core_metrics: metrics,
@@ -134,8 +148,12 @@ impl BaseAgent for Validator {
         let mut tasks = vec![];
 
         // run server
-        let custom_routes =
-            validator_server::routes(self.origin_chain.clone(), self.core.metrics.clone());
+        let custom_routes = validator_server::routes(
+            self.origin_chain.clone(),
+            self.core.metrics.clone(),
+            self.audit_report.clone(),
+            self.signing_history.clone(),
+        );
         let server = self
             .core
             .settings
@@ -192,6 +210,21 @@ impl BaseAgent for Validator {
                 }
                 Ok(_) => {
                     tasks.push(self.run_merkle_tree_hook_sync().await);
+                    match self.checkpoint_audit {
+                        CheckpointAuditMode::Off => {}
+                        CheckpointAuditMode::Report => {
+                            tasks.push(self.run_checkpoint_audit().await);
+                        }
+                        CheckpointAuditMode::Enforce => {
+                            // Don't sign anything on top of faulty checkpoints, but
+                            // keep serving the audit report
+                            let (auditor, tip) = self.checkpoint_auditor().await;
+                            if auditor.audit(&tip).await.blocks_signing() {
+                                error!("Published checkpoints failed the audit, not submitting checkpoints");
+                                break;
+                            }
+                        }
+                    }
                     for checkpoint_sync_task in self.run_checkpoint_submitters().await {
                         tasks.push(checkpoint_sync_task);
                     }
@@ -226,6 +259,36 @@ impl Validator {
         .instrument(info_span!("MerkleTreeHookSyncer"))
     }
 
+    /// The auditor of the published checkpoints and the checkpoint of the
+    /// merkle tree hook to audit them against
+    async fn checkpoint_auditor(&self) -> (CheckpointAuditor, Checkpoint) {
+        let auditor = CheckpointAuditor::new(
+            self.signer.eth_address(),
+            self.merkle_tree_hook.address(),
+            self.merkle_tree_hook.domain().id(),
+            self.checkpoint_syncer.clone(),
+            self.db.clone(),
+            CheckpointAuditMetrics::new(&self.core.metrics, &self.origin_chain)
+                .expect("failed to register checkpoint audit metrics"),
+            self.audit_report.clone(),
+        );
+        let reorg_period = NonZeroU64::new(self.reorg_period);
+        let tip = call_and_retry_indefinitely(|| {
+            let merkle_tree_hook = self.merkle_tree_hook.clone();
+            Box::pin(async move { merkle_tree_hook.latest_checkpoint(reorg_period).await })
+        })
+        .await;
+        (auditor, tip)
+    }
+
+    async fn run_checkpoint_audit(&self) -> Instrumented<JoinHandle<()>> {
+        let (auditor, tip) = self.checkpoint_auditor().await;
+        tokio::spawn(async move {
+            auditor.audit(&tip).await;
+        })
+        .instrument(info_span!("CheckpointAuditor"))
+    }
+
     async fn run_checkpoint_submitters(&self) -> Vec<Instrumented<JoinHandle<()>>> {
         let submitter = ValidatorSubmitter::new(
             self.interval,
@@ -234,6 +297,7 @@ impl Validator {
             self.signer.clone(),
             self.checkpoint_syncer.clone(),
             self.db.clone(),
+            self.signing_history.clone(),
             ValidatorSubmitterMetrics::new(&self.core.metrics, &self.origin_chain),
         );
 
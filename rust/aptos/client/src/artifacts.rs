@@ -0,0 +1,69 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use hyperlane_core::H256;
+
+use std::{fs::File, io::Write, path::Path};
+
+/// An address as the hex and base58 strings Sealevel artifacts use
+#[derive(Serialize, Deserialize)]
+pub(crate) struct HexAndBase58ProgramIdArtifact {
+    hex: String,
+    base58: String,
+}
+
+impl From<H256> for HexAndBase58ProgramIdArtifact {
+    fn from(val: H256) -> Self {
+        HexAndBase58ProgramIdArtifact {
+            hex: format!("0x{}", hex::encode(val)),
+            base58: bs58::encode(val.as_bytes()).into_string(),
+        }
+    }
+}
+
+impl From<&HexAndBase58ProgramIdArtifact> for H256 {
+    fn from(val: &HexAndBase58ProgramIdArtifact) -> Self {
+        val.hex.parse().unwrap()
+    }
+}
+
+/// For serializing and deserializing an AccountAddress as an Aptos hex literal
+pub(crate) mod serde_account_address {
+    use aptos_sdk::types::account_address::AccountAddress;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(address: &AccountAddress, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(&address.to_hex_literal())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<AccountAddress, D::Error> {
+        let address = String::deserialize(de)?;
+        AccountAddress::from_hex_literal(&address).map_err(serde::de::Error::custom)
+    }
+}
+
+pub(crate) fn write_json<T>(path: &Path, program_id: T)
+where
+    T: Serialize,
+{
+    let json = serde_json::to_string_pretty(&program_id).unwrap();
+    println!("Writing to file {} contents:\n{}", path.display(), json);
+
+    let mut file = File::create(path).expect("Failed to create file");
+    file.write_all(json.as_bytes())
+        .expect("Failed write JSON to file");
+}
+
+pub(crate) fn read_json<T>(path: &Path) -> T
+where
+    T: DeserializeOwned,
+{
+    try_read_json(path).expect("Failed to read JSON from file")
+}
+
+pub(crate) fn try_read_json<T>(path: &Path) -> std::io::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let file = File::open(path)?;
+    Ok(serde_json::from_reader(file)?)
+}
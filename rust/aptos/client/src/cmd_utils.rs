@@ -0,0 +1,169 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use aptos_sdk::types::account_address::AccountAddress;
+
+use hyperlane_aptos::{make_aptos_payload, signers::AptosSigner};
+
+use crate::Context;
+
+/// Resource every account holding published Move packages has
+const PACKAGE_REGISTRY: &str = "0x1::code::PackageRegistry";
+
+pub(crate) async fn package_published(ctx: &Context, address: AccountAddress) -> bool {
+    ctx.client
+        .get_account_resource(address, PACKAGE_REGISTRY)
+        .await
+        .map(|resource| resource.into_inner().is_some())
+        // accounts that don't exist yet have no packages
+        .unwrap_or(false)
+}
+
+/// Publishes the Move package in `package_dir` from the account of `key_path`,
+/// unless that account already holds published packages
+pub(crate) async fn publish_package_idempotent(
+    ctx: &Context,
+    address: AccountAddress,
+    key_path: &Path,
+    package_dir: &Path,
+    named_addresses: &[(&str, AccountAddress)],
+    funding_amount: u64,
+) -> bool {
+    if package_published(ctx, address).await {
+        println!("Package {} already published", address.to_hex_literal());
+        return false;
+    }
+
+    fund_account(ctx, address, funding_amount).await;
+    publish_package(&ctx.url, key_path, package_dir, named_addresses);
+    true
+}
+
+pub(crate) async fn fund_account(ctx: &Context, address: AccountAddress, amount: u64) {
+    let payload = make_aptos_payload(
+        AccountAddress::ONE,
+        "aptos_account",
+        "transfer",
+        vec![],
+        vec![
+            bcs::to_bytes(&address).unwrap(),
+            bcs::to_bytes(&amount).unwrap(),
+        ],
+    );
+    ctx.send_with_payer(payload).await;
+    println!("Funded {} with {} octas", address.to_hex_literal(), amount);
+}
+
+pub(crate) fn publish_package(
+    url: &str,
+    key_path: &Path,
+    package_dir: &Path,
+    named_addresses: &[(&str, AccountAddress)],
+) {
+    let named_addresses = named_addresses
+        .iter()
+        .map(|(name, address)| format!("{}={}", name, address.to_hex_literal()))
+        .collect::<Vec<_>>()
+        .join(",");
+    build_cmd(
+        &[
+            "aptos",
+            "move",
+            "publish",
+            "--assume-yes",
+            "--url",
+            url,
+            "--package-dir",
+            package_dir.to_str().unwrap(),
+            "--private-key-file",
+            key_path.to_str().unwrap(),
+            "--named-addresses",
+            &named_addresses,
+        ],
+        None,
+        None,
+    );
+}
+
+pub(crate) fn create_new_directory(parent_dir: &Path, name: &str) -> PathBuf {
+    let path = parent_dir.join(name);
+    std::fs::create_dir_all(path.clone())
+        .unwrap_or_else(|_| panic!("Failed to create directory {}", path.display()));
+    path
+}
+
+/// Reads a key file holding a hex encoded Ed25519 private key,
+/// the format `aptos move publish --private-key-file` expects
+pub(crate) fn read_key_file(path: &Path) -> AptosSigner {
+    let key = std::fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("Failed to read key file {}", path.display()));
+    AptosSigner::from_encoded_key(key.trim()).expect("Invalid private key")
+}
+
+/// Writes a new hex encoded private key to `key_name` in `key_dir`, in the
+/// format of [`read_key_file`]. Key files aren't JSON, so take a `.key` name.
+pub(crate) fn create_and_write_key(
+    key_dir: &Path,
+    key_name: &str,
+    use_existing_key: bool,
+) -> (AptosSigner, PathBuf) {
+    let path = key_dir.join(key_name);
+
+    if use_existing_key && path.exists() {
+        println!("Using existing key at path {}", path.display());
+        return (read_key_file(&path), path);
+    }
+
+    let key = format!("0x{}", hex::encode(rand::random::<[u8; 32]>()));
+    let signer = AptosSigner::from_encoded_key(&key).unwrap();
+
+    let mut file = create_key_file(&path);
+    file.write_all(key.as_bytes())
+        .expect("Failed to write key to file");
+    println!(
+        "Wrote key {} to {}",
+        signer.account_address().to_hex_literal(),
+        path.display()
+    );
+
+    (signer, path)
+}
+
+/// Creates or truncates a file only its owner can read, to hold a private key
+fn create_key_file(path: &Path) -> File {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let file = options.open(path).expect("Failed to create key file");
+    // The mode only applies to new files
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))
+        .expect("Failed to restrict key file permissions");
+    file
+}
+
+fn build_cmd(cmd: &[&str], wd: Option<&str>, env: Option<&HashMap<&str, &str>>) {
+    assert!(!cmd.is_empty(), "Must specify a command!");
+    let mut c = Command::new(cmd[0]);
+    c.args(&cmd[1..]);
+    c.stdout(Stdio::inherit());
+    c.stderr(Stdio::inherit());
+    if let Some(wd) = wd {
+        c.current_dir(wd);
+    }
+    if let Some(env) = env {
+        c.envs(env);
+    }
+    let status = c.status().expect("Failed to run command");
+    assert!(
+        status.success(),
+        "Command returned non-zero exit code: {}",
+        cmd.join(" ")
+    );
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use aptos_sdk::{
+    move_types::language_storage::TypeTag,
+    rest_client::aptos_api_types::MoveType,
+    types::{account_address::AccountAddress, transaction::TransactionPayload},
+};
+
+use hyperlane_aptos::{
+    send_aptos_transaction, send_view_request, signers::AptosSigner, tx_outcome_from_transaction,
+    AptosClient, AptosNonceManager, ConnectionConf, TransactionOverrides,
+};
+use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain, TxOutcome};
+
+pub(crate) struct Context {
+    pub client: AptosClient,
+    pub url: String,
+    payer: Option<AptosSigner>,
+}
+
+impl Context {
+    pub(crate) fn new(url: String, payer: Option<AptosSigner>) -> Self {
+        let conf = Self::connection_conf_for_url(&url);
+        Self {
+            client: AptosClient::new(
+                &conf,
+                &HyperlaneDomain::Known(KnownHyperlaneDomain::AptosLocalnet1),
+            ),
+            url,
+            payer,
+        }
+    }
+
+    /// A context for another chain, paid for by the same payer
+    pub(crate) fn for_url(&self, url: String) -> Self {
+        Self::new(url, self.payer.clone())
+    }
+
+    pub(crate) fn payer(&self) -> &AptosSigner {
+        self.payer
+            .as_ref()
+            .expect("A payer key is required, pass --private-key-file or --profile")
+    }
+
+    pub(crate) fn connection_conf(&self) -> ConnectionConf {
+        Self::connection_conf_for_url(&self.url)
+    }
+
+    fn connection_conf_for_url(url: &str) -> ConnectionConf {
+        ConnectionConf {
+            urls: vec![url.parse().expect("Invalid url")],
+            transaction_overrides: Default::default(),
+            external_submission: None,
+            client_metrics: Default::default(),
+            indexer_backend: Default::default(),
+        }
+    }
+
+    pub(crate) fn nonce_manager(&self, signer: &AptosSigner) -> Arc<AptosNonceManager> {
+        AptosNonceManager::for_signer(&self.client, signer)
+    }
+
+    /// Submits `payload` from the payer and waits for it to be committed
+    pub(crate) async fn send_with_payer(&self, payload: TransactionPayload) -> TxOutcome {
+        self.send(self.payer(), payload).await
+    }
+
+    /// Submits `payload` from `signer` and waits for it to be committed
+    pub(crate) async fn send(
+        &self,
+        signer: &AptosSigner,
+        payload: TransactionPayload,
+    ) -> TxOutcome {
+        let transaction = send_aptos_transaction(
+            &self.client,
+            &self.nonce_manager(signer),
+            payload,
+            &TransactionOverrides::default(),
+        )
+        .await
+        .unwrap_or_else(|err| panic!("Transaction failed: {err}"));
+        let outcome = tx_outcome_from_transaction(&transaction).unwrap();
+        println!("Transaction {:?} committed", outcome.transaction_id);
+        outcome
+    }
+
+    pub(crate) async fn view(
+        &self,
+        package: AccountAddress,
+        module_name: &str,
+        function_name: &str,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<serde_json::Value>,
+    ) -> Vec<serde_json::Value> {
+        send_view_request(
+            &self.client,
+            package.to_hex_literal(),
+            module_name.to_string(),
+            function_name.to_string(),
+            type_arguments.iter().map(MoveType::from).collect(),
+            arguments,
+        )
+        .await
+        .unwrap_or_else(|err| panic!("View {module_name}::{function_name} failed: {err}"))
+    }
+}
@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+
+use aptos_sdk::types::account_address::AccountAddress;
+
+use std::collections::HashMap;
+use std::{fs::File, path::Path};
+
+use crate::{
+    artifacts::{read_json, write_json},
+    cmd_utils::{create_and_write_key, create_new_directory, publish_package_idempotent},
+    Context, CoreCmd, CoreDeploy, CoreSubCmd,
+};
+use hyperlane_aptos::{make_aptos_payload, signers::AptosSigner};
+
+/// 1 APT, enough to publish any of the packages
+pub(crate) const DEFAULT_PACKAGE_FUNDING_AMOUNT: u64 = 100_000_000;
+
+/// Scale of the token exchange rates of the IGP gas oracle
+const TOKEN_EXCHANGE_RATE_SCALE: u128 = 10_000_000_000;
+
+/// Core packages in publishing order, with the named address each is published at
+const CORE_PACKAGES: [(&str, &str); 6] = [
+    ("library", "hp_library"),
+    ("isms", "hp_isms"),
+    ("igps", "hp_igps"),
+    ("router", "hp_router"),
+    ("mailbox", "hp_mailbox"),
+    ("validator-announce", "hp_validator"),
+];
+
+/// Gas oracle data of a remote domain
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GasOracleConfig {
+    domain: u32,
+    token_exchange_rate: u128,
+    gas_price: u128,
+}
+
+pub(crate) async fn process_core_cmd(ctx: Context, cmd: CoreCmd) {
+    match cmd.cmd {
+        CoreSubCmd::Deploy(core) => {
+            let environments_dir =
+                create_new_directory(&core.env_args.environments_dir, &core.env_args.environment);
+            let chain_dir = create_new_directory(&environments_dir, &core.chain);
+            let core_dir = create_new_directory(&chain_dir, "core");
+            let key_dir = create_new_directory(&core_dir, "keys");
+
+            let keys = CORE_PACKAGES
+                .iter()
+                .map(|(package, named_address)| {
+                    let (signer, key_path) = create_and_write_key(
+                        &key_dir,
+                        &format!("{package}.key"),
+                        core.use_existing_keys,
+                    );
+                    (*named_address, (signer, key_path))
+                })
+                .collect::<HashMap<_, _>>();
+            let named_addresses = CORE_PACKAGES
+                .iter()
+                .map(|(_, named_address)| (*named_address, keys[named_address].0.account_address()))
+                .collect::<Vec<_>>();
+
+            let mut newly_published = HashMap::new();
+            for (package, named_address) in CORE_PACKAGES {
+                let (signer, key_path) = &keys[named_address];
+                let published = publish_package_idempotent(
+                    &ctx,
+                    signer.account_address(),
+                    key_path,
+                    &core.move_dir.join(package),
+                    &named_addresses,
+                    core.funding_amount,
+                )
+                .await;
+                println!(
+                    "Package {} at {}",
+                    package,
+                    signer.account_address().to_hex_literal()
+                );
+                newly_published.insert(named_address, published);
+            }
+
+            let mailbox = &keys["hp_mailbox"].0;
+            if newly_published["hp_mailbox"] {
+                init_mailbox(&ctx, mailbox, core.local_domain).await;
+            }
+            if newly_published["hp_validator"] {
+                init_validator_announce(
+                    &ctx,
+                    &keys["hp_validator"].0,
+                    mailbox.account_address(),
+                    core.local_domain,
+                )
+                .await;
+            }
+            set_gas_oracle_configs(&ctx, &keys["hp_igps"].0, &core).await;
+
+            let igps = keys["hp_igps"].0.account_address();
+            let package_addresses = CorePackageAddresses {
+                mailbox: mailbox.account_address(),
+                validator_announce: keys["hp_validator"].0.account_address(),
+                multisig_ism_message_id: keys["hp_isms"].0.account_address(),
+                igp_program_id: igps,
+                overhead_igp_account: igps,
+                igp_account: igps,
+                router: keys["hp_router"].0.account_address(),
+                library: keys["hp_library"].0.account_address(),
+            };
+            write_package_addresses(&core_dir, package_addresses);
+        }
+    }
+}
+
+pub(crate) async fn init_mailbox(ctx: &Context, mailbox: &AptosSigner, local_domain: u32) {
+    let payload = make_aptos_payload(
+        mailbox.account_address(),
+        "mailbox",
+        "initialize",
+        vec![],
+        vec![bcs::to_bytes(&local_domain).unwrap()],
+    );
+    ctx.send(mailbox, payload).await;
+
+    println!("Initialized Mailbox with local domain {local_domain}");
+}
+
+pub(crate) async fn init_validator_announce(
+    ctx: &Context,
+    validator_announce: &AptosSigner,
+    mailbox: AccountAddress,
+    local_domain: u32,
+) {
+    let payload = make_aptos_payload(
+        validator_announce.account_address(),
+        "validator_announce",
+        "initialize",
+        vec![],
+        vec![
+            bcs::to_bytes(&mailbox).unwrap(),
+            bcs::to_bytes(&local_domain).unwrap(),
+        ],
+    );
+    ctx.send(validator_announce, payload).await;
+
+    println!("Initialized ValidatorAnnounce");
+}
+
+async fn set_gas_oracle_configs(ctx: &Context, igps: &AptosSigner, core: &CoreDeploy) {
+    let mut gas_oracle_configs = core
+        .gas_oracle_config_file
+        .as_deref()
+        .map(|p| {
+            let file = File::open(p).expect("Failed to open oracle config file");
+            serde_json::from_reader::<_, Vec<GasOracleConfig>>(file)
+                .expect("Failed to parse oracle config file")
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| c.domain != core.local_domain)
+        .map(|c| (c.domain, c))
+        .collect::<HashMap<_, _>>();
+    for &remote in &core.remote_domains {
+        gas_oracle_configs
+            .entry(remote)
+            .or_insert_with(|| GasOracleConfig {
+                domain: remote,
+                token_exchange_rate: TOKEN_EXCHANGE_RATE_SCALE,
+                gas_price: 1,
+            });
+    }
+
+    if gas_oracle_configs.is_empty() {
+        println!("Skipping settings gas oracle config");
+        return;
+    }
+
+    let configs = gas_oracle_configs.into_values().collect::<Vec<_>>();
+    let domains = configs.iter().map(|c| c.domain).collect::<Vec<_>>();
+    let token_exchange_rates = configs
+        .iter()
+        .map(|c| c.token_exchange_rate)
+        .collect::<Vec<_>>();
+    let gas_prices = configs.iter().map(|c| c.gas_price).collect::<Vec<_>>();
+    let payload = make_aptos_payload(
+        igps.account_address(),
+        "gas_oracle",
+        "set_remote_gas_data_list",
+        vec![],
+        vec![
+            bcs::to_bytes(&domains).unwrap(),
+            bcs::to_bytes(&token_exchange_rates).unwrap(),
+            bcs::to_bytes(&gas_prices).unwrap(),
+        ],
+    );
+    ctx.send(igps, payload).await;
+
+    println!("Set gas oracle for remote domains {domains:?}");
+}
+
+/// Addresses of the core packages, written to `program-ids.json` with the
+/// keys of the Sealevel core program IDs so tooling can read either.
+/// Addresses are Aptos hex literals rather than base58 public keys.
+/// Aptos IGPs keep their state, overheads included, at the IGP package
+/// address, which the IGP account entries therefore hold as well.
+/// `router` and `library` are Aptos only, to publish the packages depending
+/// on the core packages.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CorePackageAddresses {
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub mailbox: AccountAddress,
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub validator_announce: AccountAddress,
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub multisig_ism_message_id: AccountAddress,
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub igp_program_id: AccountAddress,
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub overhead_igp_account: AccountAddress,
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub igp_account: AccountAddress,
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub router: AccountAddress,
+    #[serde(with = "crate::artifacts::serde_account_address")]
+    pub library: AccountAddress,
+}
+
+impl CorePackageAddresses {
+    /// Named addresses to publish packages depending on the core packages with
+    pub(crate) fn named_addresses(&self) -> Vec<(&'static str, AccountAddress)> {
+        vec![
+            ("hp_library", self.library),
+            ("hp_isms", self.multisig_ism_message_id),
+            ("hp_igps", self.igp_program_id),
+            ("hp_router", self.router),
+            ("hp_mailbox", self.mailbox),
+            ("hp_validator", self.validator_announce),
+        ]
+    }
+}
+
+fn write_package_addresses(core_dir: &Path, package_addresses: CorePackageAddresses) {
+    write_json(&core_dir.join("program-ids.json"), package_addresses);
+}
+
+pub(crate) fn read_core_package_addresses(
+    environments_dir: &Path,
+    environment: &str,
+    chain: &str,
+) -> CorePackageAddresses {
+    let path = environments_dir
+        .join(environment)
+        .join(chain)
+        .join("core")
+        .join("program-ids.json");
+    read_json(&path)
+}
@@ -0,0 +1,141 @@
+use hyperlane_aptos::make_aptos_payload;
+
+use crate::{Context, GetSetCmd, IgpCmd, IgpSubCmd};
+
+pub(crate) async fn process_igp_cmd(ctx: Context, cmd: IgpCmd) {
+    match cmd.cmd {
+        IgpSubCmd::Query(query) => {
+            let gas_data = ctx
+                .view(
+                    query.package,
+                    "igps",
+                    "get_exchange_rate_and_gas_price",
+                    vec![],
+                    vec![serde_json::json!(query.destination_domain)],
+                )
+                .await;
+            // u256 arguments are passed as strings
+            let quote = ctx
+                .view(
+                    query.package,
+                    "igps",
+                    "quote_gas_payment",
+                    vec![],
+                    vec![
+                        serde_json::json!(query.destination_domain),
+                        serde_json::json!(query.gas_amount.to_string()),
+                    ],
+                )
+                .await;
+            println!(
+                "Token exchange rate: {}, gas price: {}",
+                gas_data[0], gas_data[1]
+            );
+            println!(
+                "Quote for {} gas to domain {}: {} octas",
+                query.gas_amount, query.destination_domain, quote[0]
+            );
+        }
+        IgpSubCmd::PayForGas(payment) => {
+            let payload = make_aptos_payload(
+                payment.package,
+                "igps",
+                "pay_for_gas",
+                vec![],
+                vec![
+                    bcs::to_bytes(&payment.message_id.as_bytes().to_vec()).unwrap(),
+                    bcs::to_bytes(&payment.destination_domain).unwrap(),
+                    u256_bcs_bytes(payment.gas),
+                ],
+            );
+            ctx.send_with_payer(payload).await;
+        }
+        IgpSubCmd::SetBeneficiary(args) => {
+            let payload = make_aptos_payload(
+                args.package,
+                "igps",
+                "set_beneficiary",
+                vec![],
+                vec![bcs::to_bytes(&args.new_beneficiary).unwrap()],
+            );
+            ctx.send_with_payer(payload).await;
+            println!(
+                "Set beneficiary to {}",
+                args.new_beneficiary.to_hex_literal()
+            );
+        }
+        IgpSubCmd::GasOracleConfig(args) => match args.cmd {
+            GetSetCmd::Get(_) => {
+                let gas_data = ctx
+                    .view(
+                        args.package,
+                        "gas_oracle",
+                        "get_exchange_rate_and_gas_price",
+                        vec![],
+                        vec![serde_json::json!(args.remote_domain)],
+                    )
+                    .await;
+                println!(
+                    "Gas oracle for domain {}: token exchange rate {}, gas price {}",
+                    args.remote_domain, gas_data[0], gas_data[1]
+                );
+            }
+            GetSetCmd::Set(set) => {
+                let payload = make_aptos_payload(
+                    args.package,
+                    "gas_oracle",
+                    "set_remote_gas_data",
+                    vec![],
+                    vec![
+                        bcs::to_bytes(&args.remote_domain).unwrap(),
+                        bcs::to_bytes(&set.token_exchange_rate).unwrap(),
+                        bcs::to_bytes(&set.gas_price).unwrap(),
+                    ],
+                );
+                ctx.send_with_payer(payload).await;
+                println!("Set gas oracle for domain {}", args.remote_domain);
+            }
+        },
+        IgpSubCmd::DestinationGasOverhead(args) => match args.cmd {
+            GetSetCmd::Get(_) => {
+                let overhead = ctx
+                    .view(
+                        args.package,
+                        "igps",
+                        "get_destination_gas_overhead",
+                        vec![],
+                        vec![serde_json::json!(args.remote_domain)],
+                    )
+                    .await;
+                println!(
+                    "Gas overhead for domain {}: {}",
+                    args.remote_domain, overhead[0]
+                );
+            }
+            GetSetCmd::Set(set) => {
+                let payload = make_aptos_payload(
+                    args.package,
+                    "igps",
+                    "set_destination_gas_overhead",
+                    vec![],
+                    vec![
+                        bcs::to_bytes(&args.remote_domain).unwrap(),
+                        u256_bcs_bytes(set.gas_overhead),
+                    ],
+                );
+                ctx.send_with_payer(payload).await;
+                println!(
+                    "Set gas overhead for domain {} to {}",
+                    args.remote_domain, set.gas_overhead
+                );
+            }
+        },
+    }
+}
+
+/// BCS encoding of a Move `u256`, 32 little-endian bytes
+fn u256_bcs_bytes(value: u64) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&value.to_le_bytes());
+    bytes.to_vec()
+}
@@ -0,0 +1,76 @@
+use hyperlane_aptos::make_aptos_payload;
+
+use crate::{init_mailbox, Context, MailboxCmd, MailboxSubCmd};
+
+pub(crate) async fn process_mailbox_cmd(ctx: Context, cmd: MailboxCmd) {
+    match cmd.cmd {
+        MailboxSubCmd::Init(init) => {
+            // the package account owns the mailbox, so the payer has to be it
+            assert_eq!(
+                ctx.payer().account_address(),
+                init.package,
+                "The mailbox must be initialized by its package account"
+            );
+            init_mailbox(&ctx, ctx.payer(), init.local_domain).await;
+        }
+        MailboxSubCmd::Query(query) => {
+            let local_domain = ctx
+                .view(query.package, "mailbox", "local_domain", vec![], vec![])
+                .await;
+            let count = ctx
+                .view(query.package, "mailbox", "outbox_get_count", vec![], vec![])
+                .await;
+            let root = ctx
+                .view(query.package, "mailbox", "outbox_get_root", vec![], vec![])
+                .await;
+            let default_ism = ctx
+                .view(query.package, "mailbox", "get_default_ism", vec![], vec![])
+                .await;
+            let owner = ctx
+                .view(query.package, "mailbox", "owner", vec![], vec![])
+                .await;
+            println!("Local domain: {}", local_domain[0]);
+            println!("Outbox count: {}", count[0]);
+            println!("Outbox root: {}", root[0]);
+            println!("Default ISM: {}", default_ism[0]);
+            println!("Owner: {}", owner[0]);
+        }
+        MailboxSubCmd::Send(outbox) => {
+            let function_name = if outbox.with_gas {
+                "send_message_with_gas"
+            } else {
+                "send_message"
+            };
+            let payload = make_aptos_payload(
+                outbox.sender,
+                "hello_world",
+                function_name,
+                vec![],
+                vec![
+                    bcs::to_bytes(&outbox.destination).unwrap(),
+                    bcs::to_bytes(&outbox.message.into_bytes()).unwrap(),
+                ],
+            );
+            ctx.send_with_payer(payload).await;
+        }
+        MailboxSubCmd::Delivered(delivered) => {
+            let response = ctx
+                .view(
+                    delivered.package,
+                    "mailbox",
+                    "delivered",
+                    vec![],
+                    vec![serde_json::json!(format!(
+                        "0x{}",
+                        hex::encode(delivered.message_id)
+                    ))],
+                )
+                .await;
+            if response[0].as_bool().unwrap_or_default() {
+                println!("Message delivered");
+            } else {
+                println!("Message not delivered");
+            }
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! Administration client for the Hyperlane Aptos Move packages.
+
+#![deny(unsafe_code)]
+
+use std::path::PathBuf;
+
+use aptos_sdk::types::account_address::AccountAddress;
+use clap::{Args, Parser, Subcommand};
+
+use hyperlane_aptos::signers::AptosSigner;
+use hyperlane_core::H256;
+
+mod artifacts;
+mod cmd_utils;
+mod context;
+mod r#core;
+mod igp;
+mod mailbox;
+mod multisig_ism;
+mod validator_announce;
+mod warp_route;
+
+use crate::igp::process_igp_cmd;
+use crate::mailbox::process_mailbox_cmd;
+use crate::multisig_ism::process_multisig_ism_cmd;
+use crate::validator_announce::process_validator_announce_cmd;
+use crate::warp_route::process_warp_route_cmd;
+pub(crate) use crate::{context::*, core::*};
+
+const DEFAULT_URL: &str = "http://0.0.0.0:8080/v1";
+const DEFAULT_CLI_CONFIG: &str = ".aptos/config.yaml";
+
+const APTOS_LOCALNET1_DOMAIN: u32 = 14411;
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    cmd: HyperlaneAptosCmd,
+    #[arg(long, short, default_value = DEFAULT_URL)]
+    url: String,
+    /// File holding the hex encoded private key of the payer
+    #[arg(long, short = 'k')]
+    private_key_file: Option<PathBuf>,
+    /// Profile of the Aptos CLI config to take the payer key from
+    #[arg(long, short)]
+    profile: Option<String>,
+    #[arg(long, short = 'C', default_value = DEFAULT_CLI_CONFIG)]
+    config: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum HyperlaneAptosCmd {
+    Core(CoreCmd),
+    Mailbox(MailboxCmd),
+    Igp(IgpCmd),
+    ValidatorAnnounce(ValidatorAnnounceCmd),
+    MultisigIsm(MultisigIsmCmd),
+    WarpRoute(WarpRouteCmd),
+}
+
+#[derive(Args)]
+struct EnvironmentArgs {
+    #[arg(long)]
+    environment: String,
+    #[arg(long)]
+    environments_dir: PathBuf,
+}
+
+#[derive(Args)]
+pub(crate) struct CoreCmd {
+    #[command(subcommand)]
+    cmd: CoreSubCmd,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum CoreSubCmd {
+    Deploy(CoreDeploy),
+}
+
+#[derive(Args)]
+pub(crate) struct CoreDeploy {
+    #[arg(long)]
+    local_domain: u32,
+    #[command(flatten)]
+    env_args: EnvironmentArgs,
+    #[arg(long)]
+    gas_oracle_config_file: Option<PathBuf>,
+    #[arg(long)]
+    chain: String,
+    #[arg(long)]
+    use_existing_keys: bool,
+    #[arg(long, num_args = 1.., value_delimiter = ',')]
+    remote_domains: Vec<u32>,
+    /// Directory holding the Move packages, e.g. `move/` of this repository
+    #[arg(long)]
+    move_dir: PathBuf,
+    /// Octas transferred from the payer to each package account before publishing
+    #[arg(long, default_value_t = DEFAULT_PACKAGE_FUNDING_AMOUNT)]
+    funding_amount: u64,
+}
+
+#[derive(Args)]
+pub(crate) struct MailboxCmd {
+    #[command(subcommand)]
+    cmd: MailboxSubCmd,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum MailboxSubCmd {
+    Init(MailboxInit),
+    Query(MailboxQuery),
+    Send(MailboxSend),
+    Delivered(MailboxDelivered),
+}
+
+#[derive(Args)]
+pub(crate) struct MailboxInit {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long, short, default_value_t = APTOS_LOCALNET1_DOMAIN)]
+    local_domain: u32,
+}
+
+#[derive(Args)]
+pub(crate) struct MailboxQuery {
+    #[arg(long)]
+    package: AccountAddress,
+}
+
+/// Dispatches a message through a `hello_world` package, as the Aptos
+/// mailbox only dispatches on behalf of registered routers
+#[derive(Args)]
+pub(crate) struct MailboxSend {
+    /// Address of the `hello_world` package sending the message
+    #[arg(long)]
+    sender: AccountAddress,
+    #[arg(long, short)]
+    destination: u32,
+    #[arg(long, short, default_value = "Hello, World!")]
+    message: String,
+    /// Pay for the message's gas on the way
+    #[arg(long)]
+    with_gas: bool,
+}
+
+#[derive(Args)]
+pub(crate) struct MailboxDelivered {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long, short)]
+    message_id: H256,
+}
+
+#[derive(Args)]
+pub(crate) struct IgpCmd {
+    #[command(subcommand)]
+    cmd: IgpSubCmd,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum IgpSubCmd {
+    Query(IgpQueryArgs),
+    PayForGas(PayForGasArgs),
+    SetBeneficiary(SetBeneficiaryArgs),
+    GasOracleConfig(GasOracleConfigArgs),
+    DestinationGasOverhead(DestinationGasOverheadArgs),
+}
+
+#[derive(Args)]
+pub(crate) struct IgpQueryArgs {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long)]
+    destination_domain: u32,
+    #[arg(long, default_value_t = 100_000)]
+    gas_amount: u64,
+}
+
+#[derive(Args)]
+pub(crate) struct PayForGasArgs {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long)]
+    message_id: H256,
+    #[arg(long)]
+    destination_domain: u32,
+    #[arg(long)]
+    gas: u64,
+}
+
+#[derive(Args)]
+pub(crate) struct SetBeneficiaryArgs {
+    #[arg(long)]
+    package: AccountAddress,
+    new_beneficiary: AccountAddress,
+}
+
+#[derive(Args)]
+pub(crate) struct GasOracleConfigArgs {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long)]
+    remote_domain: u32,
+    #[command(subcommand)]
+    cmd: GetSetCmd<GetGasOracleArgs, SetGasOracleArgs>,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum GetSetCmd<G: Args, S: Args> {
+    Get(G),
+    Set(S),
+}
+
+#[derive(Args)]
+pub(crate) struct SetGasOracleArgs {
+    #[arg(long)]
+    token_exchange_rate: u128,
+    #[arg(long)]
+    gas_price: u128,
+}
+
+#[derive(Args)]
+pub(crate) struct GetGasOracleArgs;
+
+#[derive(Args)]
+pub(crate) struct DestinationGasOverheadArgs {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long)]
+    remote_domain: u32,
+    #[command(subcommand)]
+    cmd: GetSetCmd<GetGasOracleArgs, SetDestinationGasOverheadArgs>,
+}
+
+#[derive(Args)]
+pub(crate) struct SetDestinationGasOverheadArgs {
+    #[arg(long)]
+    gas_overhead: u64,
+}
+
+#[derive(Args)]
+pub(crate) struct ValidatorAnnounceCmd {
+    #[command(subcommand)]
+    cmd: ValidatorAnnounceSubCmd,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum ValidatorAnnounceSubCmd {
+    Init(ValidatorAnnounceInit),
+    Query(ValidatorAnnounceQuery),
+}
+
+#[derive(Args)]
+pub(crate) struct ValidatorAnnounceInit {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long)]
+    mailbox: AccountAddress,
+    #[arg(long, short, default_value_t = APTOS_LOCALNET1_DOMAIN)]
+    local_domain: u32,
+}
+
+#[derive(Args)]
+pub(crate) struct ValidatorAnnounceQuery {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long, num_args = 1.., value_delimiter = ',')]
+    validators: Vec<H256>,
+}
+
+#[derive(Args)]
+pub(crate) struct MultisigIsmCmd {
+    #[command(subcommand)]
+    cmd: MultisigIsmSubCmd,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum MultisigIsmSubCmd {
+    SetValidatorsAndThreshold(SetValidatorsAndThreshold),
+    Query(MultisigIsmQuery),
+}
+
+#[derive(Args)]
+pub(crate) struct SetValidatorsAndThreshold {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long)]
+    domain: u32,
+    /// Ethereum addresses of the validators
+    #[arg(long, num_args = 1.., value_delimiter = ',')]
+    validators: Vec<hyperlane_core::H160>,
+    #[arg(long)]
+    threshold: u64,
+}
+
+#[derive(Args)]
+pub(crate) struct MultisigIsmQuery {
+    #[arg(long)]
+    package: AccountAddress,
+    #[arg(long)]
+    domain: u32,
+}
+
+#[derive(Args)]
+pub(crate) struct WarpRouteCmd {
+    #[command(subcommand)]
+    cmd: WarpRouteSubCmd,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum WarpRouteSubCmd {
+    Deploy(WarpRouteDeploy),
+    TransferRemote(WarpRouteTransferRemote),
+    Query(WarpRouteQuery),
+}
+
+#[derive(Args)]
+pub(crate) struct WarpRouteDeploy {
+    #[command(flatten)]
+    env_args: EnvironmentArgs,
+    #[arg(long)]
+    move_dir: PathBuf,
+    #[arg(long)]
+    warp_route_name: String,
+    #[arg(long)]
+    token_config_file: PathBuf,
+    #[arg(long)]
+    chain_config_file: PathBuf,
+    #[arg(long)]
+    use_existing_keys: bool,
+    #[arg(long, default_value_t = DEFAULT_PACKAGE_FUNDING_AMOUNT)]
+    funding_amount: u64,
+}
+
+#[derive(Args)]
+pub(crate) struct WarpRouteTransferRemote {
+    #[command(flatten)]
+    router: WarpRouteArgs,
+    #[arg(long)]
+    destination_domain: u32,
+    #[arg(long)]
+    recipient: H256,
+    #[arg(long)]
+    amount: u64,
+}
+
+#[derive(Args)]
+pub(crate) struct WarpRouteQuery {
+    #[command(flatten)]
+    router: WarpRouteArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct WarpRouteArgs {
+    /// Address of the token router package
+    #[arg(long)]
+    package: AccountAddress,
+    /// Address of the `hp_router` package
+    #[arg(long)]
+    router: AccountAddress,
+    #[arg(long, value_enum)]
+    token_type: TokenType,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub(crate) enum TokenType {
+    Native,
+    Synthetic,
+    Collateral,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let payer = match (&cli.private_key_file, &cli.profile) {
+        (Some(path), _) => Some(cmd_utils::read_key_file(path)),
+        (None, Some(profile)) => Some(
+            AptosSigner::from_cli_profile(&cli.config, profile)
+                .expect("Failed to read payer key from Aptos CLI config"),
+        ),
+        (None, None) => None,
+    };
+    let ctx = Context::new(cli.url, payer);
+
+    match cli.cmd {
+        HyperlaneAptosCmd::Core(cmd) => process_core_cmd(ctx, cmd).await,
+        HyperlaneAptosCmd::Mailbox(cmd) => process_mailbox_cmd(ctx, cmd).await,
+        HyperlaneAptosCmd::Igp(cmd) => process_igp_cmd(ctx, cmd).await,
+        HyperlaneAptosCmd::ValidatorAnnounce(cmd) => process_validator_announce_cmd(ctx, cmd).await,
+        HyperlaneAptosCmd::MultisigIsm(cmd) => process_multisig_ism_cmd(ctx, cmd).await,
+        HyperlaneAptosCmd::WarpRoute(cmd) => process_warp_route_cmd(ctx, cmd).await,
+    }
+}
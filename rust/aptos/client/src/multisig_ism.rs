@@ -0,0 +1,50 @@
+use aptos_sdk::types::account_address::AccountAddress;
+
+use hyperlane_aptos::make_aptos_payload;
+use hyperlane_core::H256;
+
+use crate::{Context, MultisigIsmCmd, MultisigIsmSubCmd};
+
+pub(crate) async fn process_multisig_ism_cmd(ctx: Context, cmd: MultisigIsmCmd) {
+    match cmd.cmd {
+        MultisigIsmSubCmd::SetValidatorsAndThreshold(set) => {
+            // validators are stored as their ethereum addresses left-padded to 32 bytes
+            let validators = set
+                .validators
+                .iter()
+                .map(|validator| AccountAddress::new(H256::from(*validator).0))
+                .collect::<Vec<_>>();
+            let payload = make_aptos_payload(
+                set.package,
+                "multisig_ism",
+                "set_validators_and_threshold",
+                vec![],
+                vec![
+                    bcs::to_bytes(&validators).unwrap(),
+                    bcs::to_bytes(&set.threshold).unwrap(),
+                    bcs::to_bytes(&set.domain).unwrap(),
+                ],
+            );
+            ctx.send_with_payer(payload).await;
+            println!(
+                "Set {} validators with threshold {} for domain {}",
+                validators.len(),
+                set.threshold,
+                set.domain
+            );
+        }
+        MultisigIsmSubCmd::Query(query) => {
+            let response = ctx
+                .view(
+                    query.package,
+                    "multisig_ism",
+                    "validators_and_threshold",
+                    vec![],
+                    vec![serde_json::json!(query.domain)],
+                )
+                .await;
+            println!("Validators: {}", response[0]);
+            println!("Threshold: {}", response[1]);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use hyperlane_aptos::AptosValidatorAnnounce;
+use hyperlane_core::{ContractLocator, HyperlaneDomain, KnownHyperlaneDomain, ValidatorAnnounce};
+
+use crate::{init_validator_announce, Context, ValidatorAnnounceCmd, ValidatorAnnounceSubCmd};
+
+pub(crate) async fn process_validator_announce_cmd(ctx: Context, cmd: ValidatorAnnounceCmd) {
+    match cmd.cmd {
+        ValidatorAnnounceSubCmd::Init(init) => {
+            // the package account owns the validator announce, so the payer has to be it
+            assert_eq!(
+                ctx.payer().account_address(),
+                init.package,
+                "The validator announce must be initialized by its package account"
+            );
+            init_validator_announce(&ctx, ctx.payer(), init.mailbox, init.local_domain).await;
+        }
+        ValidatorAnnounceSubCmd::Query(query) => {
+            if query.validators.is_empty() {
+                let announced = ctx
+                    .view(
+                        query.package,
+                        "validator_announce",
+                        "get_announced_validators",
+                        vec![],
+                        vec![],
+                    )
+                    .await;
+                println!("Announced validators: {}", announced[0]);
+                return;
+            }
+
+            let validator_announce = AptosValidatorAnnounce::new(
+                &ctx.connection_conf(),
+                ContractLocator {
+                    domain: &HyperlaneDomain::Known(KnownHyperlaneDomain::AptosLocalnet1),
+                    address: query.package.into_bytes().into(),
+                },
+                None,
+            );
+            let storage_locations = validator_announce
+                .get_announced_storage_locations(&query.validators)
+                .await
+                .expect("Failed to query announced storage locations");
+            for (validator, locations) in query.validators.iter().zip(storage_locations) {
+                println!("Validator {validator:?}: {locations:?}");
+            }
+        }
+    }
+}
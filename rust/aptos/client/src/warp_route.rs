@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap};
+
+use hyperlane_aptos::{
+    AptosCollateralInfo, AptosSyntheticMetadata, AptosTokenType, AptosWarpRoute,
+    TransactionOverrides,
+};
+use hyperlane_core::{
+    ContractLocator, HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneDomainTechnicalStack,
+    KnownHyperlaneDomain, H256,
+};
+
+use crate::{
+    artifacts::{read_json, write_json, HexAndBase58ProgramIdArtifact},
+    cmd_utils::{create_and_write_key, create_new_directory, publish_package_idempotent},
+    read_core_package_addresses, Context, TokenType, WarpRouteArgs, WarpRouteCmd, WarpRouteDeploy,
+    WarpRouteSubCmd,
+};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct TokenConfig {
+    #[serde(flatten)]
+    token_type: AptosTokenType,
+    /// Router deployed on a chain this client doesn't deploy to
+    foreign_deployment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct RpcUrlConfig {
+    http: String,
+}
+
+/// An abridged version of the Typescript ChainMetadata
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ChainMetadata {
+    chain_id: u32,
+    /// Hyperlane domain, only required if differs from id above
+    domain_id: Option<u32>,
+    name: String,
+    /// Collection of RPC endpoints
+    rpc_urls: Vec<RpcUrlConfig>,
+}
+
+impl ChainMetadata {
+    fn domain_id(&self) -> u32 {
+        self.domain_id.unwrap_or(self.chain_id)
+    }
+
+    fn domain(&self) -> HyperlaneDomain {
+        HyperlaneDomain::from_config(
+            self.domain_id(),
+            &self.name,
+            HyperlaneDomainProtocol::Aptos,
+            HyperlaneDomainTechnicalStack::Other,
+        )
+        .expect("Invalid domain")
+    }
+}
+
+/// Package directory of each kind of token router, and the named address it's published at
+fn token_package(token_type: &AptosTokenType) -> (&'static str, &'static str) {
+    match token_type {
+        AptosTokenType::Native => ("token", "hp_token"),
+        AptosTokenType::Synthetic(_) => ("token-synthetic", "hp_token_synthetic"),
+        AptosTokenType::Collateral(_) => ("token-collateral", "hp_token_collateral"),
+    }
+}
+
+pub(crate) async fn process_warp_route_cmd(ctx: Context, cmd: WarpRouteCmd) {
+    match cmd.cmd {
+        WarpRouteSubCmd::Deploy(deploy) => deploy_warp_route(ctx, deploy).await,
+        WarpRouteSubCmd::TransferRemote(transfer) => {
+            let warp_route = warp_route_from_args(&ctx, &transfer.router);
+            let payload = warp_route.transfer_remote_payload(
+                transfer.destination_domain,
+                transfer.recipient,
+                transfer.amount,
+            );
+            ctx.send_with_payer(payload).await;
+            println!(
+                "Transferred {} to {:?} on domain {}",
+                transfer.amount, transfer.recipient, transfer.destination_domain
+            );
+        }
+        WarpRouteSubCmd::Query(query) => {
+            let warp_route = warp_route_from_args(&ctx, &query.router);
+            let remote_routers = warp_route
+                .remote_routers()
+                .await
+                .expect("Failed to query remote routers");
+            for (domain, router) in remote_routers {
+                println!("Domain {domain}: {router:?}");
+            }
+        }
+    }
+}
+
+async fn deploy_warp_route(ctx: Context, deploy: WarpRouteDeploy) {
+    let token_configs: HashMap<String, TokenConfig> = read_json(&deploy.token_config_file);
+    let chain_configs: HashMap<String, ChainMetadata> = read_json(&deploy.chain_config_file);
+
+    let environments_dir = create_new_directory(
+        &deploy.env_args.environments_dir,
+        &deploy.env_args.environment,
+    );
+    let warp_routes_dir = create_new_directory(&environments_dir, "warp-routes");
+    let warp_route_dir = create_new_directory(&warp_routes_dir, &deploy.warp_route_name);
+    let key_dir = create_new_directory(&warp_route_dir, "keys");
+
+    let mut routers = HashMap::new();
+    let mut deployments = vec![];
+    for (chain_name, token_config) in &token_configs {
+        let chain_config = chain_configs
+            .get(chain_name)
+            .unwrap_or_else(|| panic!("Chain config not found for chain: {chain_name}"));
+
+        if let Some(foreign_deployment) = &token_config.foreign_deployment {
+            let router: H256 = foreign_deployment
+                .parse()
+                .unwrap_or_else(|_| panic!("Invalid foreign deployment on {chain_name}"));
+            routers.insert(chain_name.clone(), (chain_config.domain_id(), router));
+            continue;
+        }
+
+        let chain_ctx = ctx.for_url(chain_config.rpc_urls[0].http.clone());
+        let (signer, key_path) = create_and_write_key(
+            &key_dir,
+            &format!("{chain_name}.key"),
+            deploy.use_existing_keys,
+        );
+        let core_packages = read_core_package_addresses(
+            &deploy.env_args.environments_dir,
+            &deploy.env_args.environment,
+            chain_name,
+        );
+
+        let (package_dir, named_address) = token_package(&token_config.token_type);
+        let mut named_addresses = core_packages.named_addresses();
+        named_addresses.push((named_address, signer.account_address()));
+        named_addresses.push(("examples", signer.account_address()));
+        publish_package_idempotent(
+            &chain_ctx,
+            signer.account_address(),
+            &key_path,
+            &deploy.move_dir.join(package_dir),
+            &named_addresses,
+            deploy.funding_amount,
+        )
+        .await;
+
+        let router = H256(signer.account_address().into_bytes());
+        routers.insert(chain_name.clone(), (chain_config.domain_id(), router));
+        deployments.push((
+            chain_name,
+            chain_ctx,
+            signer,
+            H256(core_packages.router.into_bytes()),
+        ));
+    }
+
+    for (chain_name, chain_ctx, signer, router_package) in deployments {
+        let chain_config = &chain_configs[chain_name];
+        let domain = chain_config.domain();
+        let warp_route = AptosWarpRoute::new(
+            &chain_ctx.connection_conf(),
+            ContractLocator {
+                domain: &domain,
+                address: H256(signer.account_address().into_bytes()),
+            },
+            router_package,
+            token_configs[chain_name].token_type.clone(),
+        );
+        let remote_routers = routers
+            .iter()
+            .filter(|(name, _)| *name != chain_name)
+            .map(|(_, (domain, router))| (*domain, *router))
+            .collect::<BTreeMap<_, _>>();
+
+        // the package account owns the router, so it configures it
+        let outcomes = warp_route
+            .configure(
+                &chain_ctx.nonce_manager(&signer),
+                &TransactionOverrides::default(),
+                &remote_routers,
+            )
+            .await
+            .unwrap_or_else(|err| panic!("Failed to configure router on {chain_name}: {err}"));
+        println!(
+            "Configured router on {} with {} transactions",
+            chain_name,
+            outcomes.len()
+        );
+    }
+
+    let program_ids = routers
+        .into_iter()
+        .map(|(chain_name, (_, router))| (chain_name, HexAndBase58ProgramIdArtifact::from(router)))
+        .collect::<BTreeMap<_, _>>();
+    write_json(&warp_route_dir.join("program-ids.json"), program_ids);
+}
+
+fn warp_route_from_args(ctx: &Context, args: &WarpRouteArgs) -> AptosWarpRoute {
+    // the asset details are only read when initializing, which these commands don't
+    let token_type = match args.token_type {
+        TokenType::Native => AptosTokenType::Native,
+        TokenType::Synthetic => AptosTokenType::Synthetic(AptosSyntheticMetadata {
+            name: String::new(),
+            symbol: String::new(),
+            decimals: 0,
+            remote_decimals: None,
+        }),
+        TokenType::Collateral => AptosTokenType::Collateral(AptosCollateralInfo {
+            metadata: H256::zero(),
+            remote_decimals: 0,
+        }),
+    };
+    AptosWarpRoute::new(
+        &ctx.connection_conf(),
+        ContractLocator {
+            domain: &HyperlaneDomain::Known(KnownHyperlaneDomain::AptosLocalnet1),
+            address: H256(args.package.into_bytes()),
+        },
+        H256(args.router.into_bytes()),
+        token_type,
+    )
+}
@@ -0,0 +1,12 @@
+//! An in-process mock of the Aptos node REST API, so hyperlane-aptos can be
+//! tested without running a local testnet.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+#![deny(warnings)]
+
+pub use node::MockAptosNode;
+pub use state::*;
+
+mod node;
+mod state;
@@ -0,0 +1,795 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use aptos_sdk::crypto::HashValue;
+use aptos_sdk::types::transaction::{SignedTransaction, TransactionPayload};
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing, Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::state::{
+    normalize_tag, parse_address, MockAptosState, MockBlock, TransactionOutcome, ViewCall,
+};
+
+const BCS_CONTENT_TYPE: &str = "application/x-bcs";
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// Number of events returned when a request does not set a limit
+const DEFAULT_EVENT_LIMIT: usize = 25;
+
+type SharedState = Arc<Mutex<MockAptosState>>;
+
+/// An in-process fake of the Aptos node REST API, serving the endpoints the
+/// agents use from a [`MockAptosState`] that tests script.
+/// The server runs on its own thread and runtime, so it can be used from
+/// synchronous tests and from code that blocks the caller's runtime.
+/// It is shut down when dropped.
+pub struct MockAptosNode {
+    addr: SocketAddr,
+    state: SharedState,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl MockAptosNode {
+    /// Start a node on a free local port with a fresh chain holding only a genesis block
+    pub fn spawn() -> Self {
+        Self::spawn_with_state(MockAptosState::default())
+    }
+
+    /// Start a node on a free local port serving `state`
+    pub fn spawn_with_state(state: MockAptosState) -> Self {
+        let state = Arc::new(Mutex::new(state));
+        let app = router(state.clone());
+        let (addr_tx, addr_rx) = std::sync::mpsc::channel();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to build mock Aptos node runtime");
+            runtime.block_on(async move {
+                let server = axum::Server::bind(&"127.0.0.1:0".parse().unwrap())
+                    .serve(app.into_make_service());
+                addr_tx.send(server.local_addr()).unwrap();
+                server
+                    .with_graceful_shutdown(async {
+                        shutdown_rx.await.ok();
+                    })
+                    .await
+                    .expect("Mock Aptos node failed");
+            });
+        });
+
+        let addr = addr_rx.recv().expect("Mock Aptos node failed to start");
+        Self {
+            addr,
+            state,
+            shutdown: Some(shutdown_tx),
+        }
+    }
+
+    /// REST API url of the node, including the `/v1` prefix
+    pub fn url(&self) -> Url {
+        format!("http://{}/v1", self.addr).parse().unwrap()
+    }
+
+    /// Url of a stub of the Aptos indexer GraphQL API serving the same chain.
+    /// It answers the `Events`, `EventsBySequence`, `BlockHashes`, `BlockByHash`
+    /// and `ProcessorStatus` operations only.
+    pub fn graphql_url(&self) -> Url {
+        format!("http://{}/v1/graphql", self.addr).parse().unwrap()
+    }
+
+    /// Lock the node state to inspect or script it
+    pub fn state(&self) -> MutexGuard<'_, MockAptosState> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl std::fmt::Debug for MockAptosNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockAptosNode")
+            .field("addr", &self.addr)
+            .finish()
+    }
+}
+
+impl Drop for MockAptosNode {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/v1", routing::get(ledger_info))
+        .route("/v1/", routing::get(ledger_info))
+        .route("/v1/estimate_gas_price", routing::get(estimate_gas_price))
+        .route(
+            "/v1/blocks/by_height/:height",
+            routing::get(block_by_height),
+        )
+        .route(
+            "/v1/blocks/by_version/:version",
+            routing::get(block_by_version),
+        )
+        .route("/v1/accounts/:address", routing::get(account))
+        .route(
+            "/v1/accounts/:address/resources",
+            routing::get(account_resources),
+        )
+        .route(
+            "/v1/accounts/:address/resource/:resource_type",
+            routing::get(account_resource),
+        )
+        .route(
+            "/v1/accounts/:address/module/:module_name",
+            routing::get(account_module),
+        )
+        .route(
+            "/v1/accounts/:address/events/:resource_type/:field_name",
+            routing::get(account_events),
+        )
+        .route("/v1/view", routing::post(view))
+        .route("/v1/graphql", routing::post(graphql))
+        .route("/v1/transactions", routing::post(submit))
+        .route("/v1/transactions/simulate", routing::post(simulate))
+        .route(
+            "/v1/transactions/by_hash/:hash",
+            routing::get(transaction_by_hash),
+        )
+        .route(
+            "/v1/transactions/wait_by_hash/:hash",
+            routing::get(transaction_by_hash),
+        )
+        .with_state(state)
+}
+
+/// Build a response carrying the ledger state headers the SDK requires on every response
+fn respond(
+    state: &MockAptosState,
+    status: StatusCode,
+    content_type: &'static str,
+    body: Vec<u8>,
+) -> Response {
+    let latest = state.latest_block();
+    let mut response = (status, body).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    for (name, value) in [
+        ("x-aptos-chain-id", state.chain_id as u64),
+        ("x-aptos-ledger-version", latest.last_version),
+        ("x-aptos-ledger-oldest-version", 0),
+        ("x-aptos-ledger-timestampusec", latest.timestamp_usecs),
+        ("x-aptos-epoch", state.epoch),
+        ("x-aptos-block-height", latest.height),
+        ("x-aptos-oldest-block-height", 0),
+    ] {
+        headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+    }
+    response
+}
+
+fn json_response(state: &MockAptosState, status: StatusCode, body: Value) -> Response {
+    respond(
+        state,
+        status,
+        JSON_CONTENT_TYPE,
+        body.to_string().into_bytes(),
+    )
+}
+
+/// An error in the format of the Aptos API
+fn error_response(
+    state: &MockAptosState,
+    status: StatusCode,
+    error_code: &str,
+    message: impl Into<String>,
+) -> Response {
+    json_response(
+        state,
+        status,
+        json!({
+            "message": message.into(),
+            "error_code": error_code,
+            "vm_error_code": null,
+        }),
+    )
+}
+
+fn wants_bcs(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .map_or(false, |accept| accept.contains(BCS_CONTENT_TYPE))
+}
+
+/// BCS layout of the ledger info returned to BCS requests
+#[derive(Serialize)]
+struct IndexResponseBcs {
+    chain_id: u8,
+    epoch: u64,
+    ledger_version: u64,
+    oldest_ledger_version: u64,
+    ledger_timestamp: u64,
+    /// `RoleType::FullNode`
+    node_role: u8,
+    oldest_block_height: u64,
+    block_height: u64,
+    git_hash: Option<String>,
+}
+
+async fn ledger_info(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let state = state.lock().unwrap();
+    let latest = state.latest_block();
+    if wants_bcs(&headers) {
+        let index = IndexResponseBcs {
+            chain_id: state.chain_id,
+            epoch: state.epoch,
+            ledger_version: latest.last_version,
+            oldest_ledger_version: 0,
+            ledger_timestamp: latest.timestamp_usecs,
+            node_role: 1,
+            oldest_block_height: 0,
+            block_height: latest.height,
+            git_hash: None,
+        };
+        let body = bcs::to_bytes(&index).unwrap();
+        return respond(&state, StatusCode::OK, BCS_CONTENT_TYPE, body);
+    }
+    let body = json!({
+        "chain_id": state.chain_id,
+        "epoch": state.epoch.to_string(),
+        "ledger_version": latest.last_version.to_string(),
+        "oldest_ledger_version": "0",
+        "ledger_timestamp": latest.timestamp_usecs.to_string(),
+        "node_role": "full_node",
+        "oldest_block_height": "0",
+        "block_height": latest.height.to_string(),
+    });
+    json_response(&state, StatusCode::OK, body)
+}
+
+async fn estimate_gas_price(State(state): State<SharedState>) -> Response {
+    let state = state.lock().unwrap();
+    let body = json!({
+        "deprioritized_gas_estimate": state.gas_estimate,
+        "gas_estimate": state.gas_estimate,
+        "prioritized_gas_estimate": state.gas_estimate,
+    });
+    json_response(&state, StatusCode::OK, body)
+}
+
+fn block_json(block: &MockBlock) -> Value {
+    json!({
+        "block_height": block.height.to_string(),
+        "block_hash": block.hash.to_hex_literal(),
+        "block_timestamp": block.timestamp_usecs.to_string(),
+        "first_version": block.first_version.to_string(),
+        "last_version": block.last_version.to_string(),
+        "transactions": null,
+    })
+}
+
+async fn block_by_height(State(state): State<SharedState>, Path(height): Path<u64>) -> Response {
+    let state = state.lock().unwrap();
+    match state.block(height) {
+        Some(block) => json_response(&state, StatusCode::OK, block_json(block)),
+        None => error_response(
+            &state,
+            StatusCode::NOT_FOUND,
+            "block_not_found",
+            format!("Block not found by Height({height})"),
+        ),
+    }
+}
+
+async fn block_by_version(State(state): State<SharedState>, Path(version): Path<u64>) -> Response {
+    let state = state.lock().unwrap();
+    match state.block_by_version(version) {
+        Some(block) => json_response(&state, StatusCode::OK, block_json(block)),
+        None => error_response(
+            &state,
+            StatusCode::NOT_FOUND,
+            "block_not_found",
+            format!("Block not found by Version({version})"),
+        ),
+    }
+}
+
+async fn account(State(state): State<SharedState>, Path(address): Path<String>) -> Response {
+    let state = state.lock().unwrap();
+    let Some(account) = parse_address(&address) else {
+        return error_response(
+            &state,
+            StatusCode::BAD_REQUEST,
+            "invalid_input",
+            "Invalid address",
+        );
+    };
+    match state.sequence_number(account) {
+        Some(sequence_number) => json_response(
+            &state,
+            StatusCode::OK,
+            json!({
+                "sequence_number": sequence_number.to_string(),
+                "authentication_key": format!("0x{}", account.to_hex()),
+            }),
+        ),
+        None => error_response(
+            &state,
+            StatusCode::NOT_FOUND,
+            "account_not_found",
+            format!("Account not found by Address({address})"),
+        ),
+    }
+}
+
+async fn account_resources(
+    State(state): State<SharedState>,
+    Path(address): Path<String>,
+) -> Response {
+    let state = state.lock().unwrap();
+    let Some(account) = parse_address(&address) else {
+        return error_response(
+            &state,
+            StatusCode::BAD_REQUEST,
+            "invalid_input",
+            "Invalid address",
+        );
+    };
+    let resources = state.resources(account);
+    if resources.is_empty() && state.sequence_number(account).is_none() {
+        return error_response(
+            &state,
+            StatusCode::NOT_FOUND,
+            "account_not_found",
+            format!("Account not found by Address({address})"),
+        );
+    }
+    let resources = resources
+        .into_iter()
+        .map(|(resource_type, data)| json!({ "type": resource_type, "data": data }))
+        .collect();
+    json_response(&state, StatusCode::OK, Value::Array(resources))
+}
+
+async fn account_resource(
+    State(state): State<SharedState>,
+    Path((address, resource_type)): Path<(String, String)>,
+) -> Response {
+    let state = state.lock().unwrap();
+    let resource =
+        parse_address(&address).and_then(|account| state.resource(account, &resource_type));
+    match resource {
+        Some(data) => json_response(
+            &state,
+            StatusCode::OK,
+            json!({ "type": resource_type, "data": data }),
+        ),
+        None => error_response(
+            &state,
+            StatusCode::NOT_FOUND,
+            "resource_not_found",
+            format!("Resource not found by Address({address}), Struct tag({resource_type})"),
+        ),
+    }
+}
+
+async fn account_module(
+    State(state): State<SharedState>,
+    Path((address, module_name)): Path<(String, String)>,
+) -> Response {
+    let state = state.lock().unwrap();
+    let published =
+        parse_address(&address).map_or(false, |account| state.has_module(account, &module_name));
+    if published {
+        json_response(
+            &state,
+            StatusCode::OK,
+            json!({ "bytecode": "0x", "abi": null }),
+        )
+    } else {
+        error_response(
+            &state,
+            StatusCode::NOT_FOUND,
+            "module_not_found",
+            format!("Module not found by Address({address}), Module name({module_name})"),
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct EventsQuery {
+    start: Option<u64>,
+    limit: Option<u16>,
+}
+
+async fn account_events(
+    State(state): State<SharedState>,
+    Path((address, resource_type, field_name)): Path<(String, String, String)>,
+    Query(query): Query<EventsQuery>,
+) -> Response {
+    let state = state.lock().unwrap();
+    let Some(account) = parse_address(&address) else {
+        return error_response(
+            &state,
+            StatusCode::BAD_REQUEST,
+            "invalid_input",
+            "Invalid address",
+        );
+    };
+    let start = query.start.unwrap_or_default() as usize;
+    let limit = query.limit.map_or(DEFAULT_EVENT_LIMIT, usize::from);
+    let events: Vec<Value> = state
+        .events(account, &resource_type, &field_name)
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(limit)
+        .map(|(sequence_number, event)| {
+            json!({
+                "version": event.version.to_string(),
+                "guid": {
+                    "creation_number": "0",
+                    "account_address": account.to_hex_literal(),
+                },
+                "sequence_number": sequence_number.to_string(),
+                "type": event.event_type,
+                "data": event.data,
+            })
+        })
+        .collect();
+    json_response(&state, StatusCode::OK, Value::Array(events))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlRequest {
+    operation_name: String,
+    variables: Value,
+}
+
+/// Answer a GraphQL operation from the chain state, ignoring the query text
+async fn graphql(
+    State(state): State<SharedState>,
+    Json(request): Json<GraphqlRequest>,
+) -> Response {
+    let mut state = state.lock().unwrap();
+    state.record_graphql_operation(request.operation_name.clone());
+    let variables = &request.variables;
+    let body = match request.operation_name.as_str() {
+        operation @ ("Events" | "EventsBySequence") => {
+            let account = variables["account"].as_str().and_then(parse_address);
+            let event_type = variables["type"].as_str().unwrap_or_default();
+            let from = variables["from"].as_u64().unwrap_or_default();
+            let to = variables["to"].as_u64().unwrap_or_default();
+            let offset = variables["offset"].as_u64().unwrap_or_default() as usize;
+            let limit = variables["limit"].as_u64().unwrap_or(u64::MAX) as usize;
+            let indexed_version = state.latest_indexed_version();
+            let events: Vec<Value> = account
+                .map(|account| state.events_of_type(account, event_type))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, event)| event.version <= indexed_version)
+                .filter(|(sequence_number, event)| {
+                    if operation == "EventsBySequence" {
+                        return (from..=to).contains(sequence_number);
+                    }
+                    state
+                        .block_by_version(event.version)
+                        .map_or(false, |block| (from..=to).contains(&block.height))
+                })
+                .skip(offset)
+                .take(limit)
+                .map(|(sequence_number, event)| {
+                    json!({
+                        "account_address": format!("0x{}", account.unwrap().to_hex()),
+                        "creation_number": 0,
+                        "sequence_number": sequence_number,
+                        "transaction_version": event.version,
+                        "type": event.event_type,
+                        "data": event.data,
+                    })
+                })
+                .collect();
+            json!({ "data": { "events": events } })
+        }
+        "BlockHashes" => {
+            let blocks: Vec<Value> = variables["heights"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|height| state.block(height.as_u64()?))
+                .map(|block| {
+                    json!({
+                        "block_height": block.height,
+                        "id": block.hash.to_hex_literal(),
+                    })
+                })
+                .collect();
+            json!({ "data": { "block_metadata_transactions": blocks } })
+        }
+        "BlockByHash" => {
+            let blocks: Vec<Value> = variables["id"]
+                .as_str()
+                .and_then(|id| HashValue::from_hex_literal(id).ok())
+                .and_then(|hash| state.block_by_hash(hash))
+                .into_iter()
+                .map(|block| {
+                    json!({
+                        "block_height": block.height,
+                        "id": block.hash.to_hex_literal(),
+                    })
+                })
+                .collect();
+            json!({ "data": { "block_metadata_transactions": blocks } })
+        }
+        "ProcessorStatus" => json!({
+            "data": {
+                "processor_status": [{
+                    "last_success_version": state.latest_indexed_version(),
+                }],
+            },
+        }),
+        operation_name => json!({
+            "errors": [{ "message": format!("Unknown operation {operation_name}") }],
+        }),
+    };
+    json_response(&state, StatusCode::OK, body)
+}
+
+#[derive(Deserialize)]
+struct ViewRequest {
+    function: String,
+    #[serde(default)]
+    arguments: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct ViewQuery {
+    ledger_version: Option<u64>,
+}
+
+async fn view(
+    State(state): State<SharedState>,
+    Query(query): Query<ViewQuery>,
+    Json(request): Json<ViewRequest>,
+) -> Response {
+    let handler = {
+        let mut state = state.lock().unwrap();
+        let latest_version = state.latest_block().last_version;
+        if let Some(ledger_version) = query.ledger_version.filter(|v| *v > latest_version) {
+            return error_response(
+                &state,
+                StatusCode::NOT_FOUND,
+                "version_not_found",
+                format!("Ledger version not found: {ledger_version}"),
+            );
+        }
+        state.record_view_call(ViewCall {
+            function: normalize_tag(&request.function),
+            arguments: request.arguments.clone(),
+            ledger_version: query.ledger_version,
+        });
+        state.view(&request.function)
+    };
+    // Run the handler without holding the lock so it may script the state itself
+    let response = handler.map(|handler| handler(&request.arguments));
+    let state = state.lock().unwrap();
+    match response {
+        Some(response) => json_response(&state, StatusCode::OK, Value::Array(response)),
+        None => error_response(
+            &state,
+            StatusCode::BAD_REQUEST,
+            "invalid_input",
+            format!("Function {} not found", request.function),
+        ),
+    }
+}
+
+/// The JSON payload of an entry function call, arguments as hex encoded BCS
+fn payload_json(payload: &TransactionPayload) -> Option<Value> {
+    let TransactionPayload::EntryFunction(entry_function) = payload else {
+        return None;
+    };
+    Some(json!({
+        "type": "entry_function_payload",
+        "function": format!(
+            "{}::{}::{}",
+            entry_function.module().address().to_hex_literal(),
+            entry_function.module().name(),
+            entry_function.function()
+        ),
+        "type_arguments": entry_function
+            .ty_args()
+            .iter()
+            .map(|ty| ty.to_string())
+            .collect::<Vec<_>>(),
+        "arguments": entry_function
+            .args()
+            .iter()
+            .map(|arg| format!("0x{}", hex::encode(arg)))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+/// The request fields of a user transaction, without signature
+fn user_transaction_request_json(transaction: &SignedTransaction, payload: Value) -> Value {
+    json!({
+        "sender": transaction.sender().to_hex_literal(),
+        "sequence_number": transaction.sequence_number().to_string(),
+        "max_gas_amount": transaction.max_gas_amount().to_string(),
+        "gas_unit_price": transaction.gas_unit_price().to_string(),
+        "expiration_timestamp_secs": transaction.expiration_timestamp_secs().to_string(),
+        "payload": payload,
+    })
+}
+
+/// A committed user transaction as returned by the REST API
+fn user_transaction_json(
+    transaction: &SignedTransaction,
+    payload: Value,
+    hash: HashValue,
+    block: &MockBlock,
+    gas_used: u64,
+    vm_status: Option<&str>,
+) -> Value {
+    let mut json = user_transaction_request_json(transaction, payload);
+    let fields = json!({
+        "type": "user_transaction",
+        "version": block.first_version.to_string(),
+        "hash": hash.to_hex_literal(),
+        "state_change_hash": HashValue::zero().to_hex_literal(),
+        "event_root_hash": HashValue::zero().to_hex_literal(),
+        "state_checkpoint_hash": null,
+        "gas_used": gas_used.to_string(),
+        "success": vm_status.is_none(),
+        "vm_status": vm_status.unwrap_or("Executed successfully"),
+        "accumulator_root_hash": HashValue::zero().to_hex_literal(),
+        "changes": [],
+        "events": [],
+        "timestamp": block.timestamp_usecs.to_string(),
+    });
+    json.as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    json
+}
+
+/// Decode a BCS signed transaction and its JSON payload from a request body
+fn decode_transaction(
+    state: &MockAptosState,
+    body: &[u8],
+) -> Result<(SignedTransaction, Value), Response> {
+    let transaction: SignedTransaction = bcs::from_bytes(body).map_err(|e| {
+        error_response(
+            state,
+            StatusCode::BAD_REQUEST,
+            "invalid_input",
+            format!("Failed to deserialize signed transaction: {e}"),
+        )
+    })?;
+    let payload = payload_json(transaction.payload()).ok_or_else(|| {
+        error_response(
+            state,
+            StatusCode::BAD_REQUEST,
+            "invalid_input",
+            "Only entry function payloads are supported",
+        )
+    })?;
+    Ok((transaction, payload))
+}
+
+async fn submit(State(state): State<SharedState>, body: Bytes) -> Response {
+    let mut state = state.lock().unwrap();
+    let (transaction, payload) = match decode_transaction(&state, &body) {
+        Ok(decoded) => decoded,
+        Err(response) => return response,
+    };
+
+    let expected = state
+        .sequence_number(transaction.sender())
+        .unwrap_or_default();
+    let status_code = match transaction.sequence_number().cmp(&expected) {
+        std::cmp::Ordering::Less => Some("SEQUENCE_NUMBER_TOO_OLD"),
+        std::cmp::Ordering::Greater => Some("SEQUENCE_NUMBER_TOO_NEW"),
+        std::cmp::Ordering::Equal => None,
+    };
+    if let Some(status_code) = status_code {
+        return error_response(
+            &state,
+            StatusCode::BAD_REQUEST,
+            "vm_error",
+            format!("Invalid transaction: Type: Validation Code: {status_code}"),
+        );
+    }
+
+    let vm_status = match state.next_submit_outcome() {
+        TransactionOutcome::Success => None,
+        TransactionOutcome::Failure { vm_status } => Some(vm_status),
+        TransactionOutcome::Rejection {
+            error_code,
+            message,
+        } => return error_response(&state, StatusCode::BAD_REQUEST, &error_code, message),
+    };
+
+    // Every accepted transaction is committed at once, in a block of its own
+    let hash = transaction.clone().committed_hash();
+    let block = state.add_block(1);
+    let committed = user_transaction_json(
+        &transaction,
+        payload.clone(),
+        hash,
+        &block,
+        state.gas_used,
+        vm_status.as_deref(),
+    );
+    let mut pending = user_transaction_request_json(&transaction, payload);
+    pending["hash"] = json!(hash.to_hex_literal());
+    state.record_submitted(hash, transaction, committed);
+    json_response(&state, StatusCode::ACCEPTED, pending)
+}
+
+async fn simulate(
+    State(state): State<SharedState>,
+    Query(_query): Query<HashMap<String, String>>,
+    body: Bytes,
+) -> Response {
+    let mut state = state.lock().unwrap();
+    let (transaction, payload) = match decode_transaction(&state, &body) {
+        Ok(decoded) => decoded,
+        Err(response) => return response,
+    };
+    let vm_status = match state.next_simulation_outcome() {
+        TransactionOutcome::Success => None,
+        TransactionOutcome::Failure { vm_status } => Some(vm_status),
+        TransactionOutcome::Rejection {
+            error_code,
+            message,
+        } => return error_response(&state, StatusCode::BAD_REQUEST, &error_code, message),
+    };
+    let hash = transaction.clone().committed_hash();
+    // Simulations are executed on top of the latest block without changing the chain
+    let block = state.latest_block().clone();
+    let simulated = user_transaction_json(
+        &transaction,
+        payload,
+        hash,
+        &block,
+        state.gas_used,
+        vm_status.as_deref(),
+    );
+    state.record_simulated(transaction);
+    json_response(&state, StatusCode::OK, json!([simulated]))
+}
+
+async fn transaction_by_hash(
+    State(state): State<SharedState>,
+    Path(hash): Path<String>,
+) -> Response {
+    let state = state.lock().unwrap();
+    let transaction = HashValue::from_hex(hash.trim_start_matches("0x"))
+        .ok()
+        .and_then(|hash| state.transaction(&hash).cloned());
+    match transaction {
+        Some(transaction) => json_response(&state, StatusCode::OK, transaction),
+        None => error_response(
+            &state,
+            StatusCode::NOT_FOUND,
+            "transaction_not_found",
+            format!("Transaction not found by Transaction hash({hash})"),
+        ),
+    }
+}
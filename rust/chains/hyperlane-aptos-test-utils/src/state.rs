@@ -0,0 +1,480 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+
+use aptos_sdk::crypto::HashValue;
+use aptos_sdk::types::{account_address::AccountAddress, transaction::SignedTransaction};
+use serde_json::Value;
+
+/// Chain id reported by the mock node, the same as a local testnet
+pub const MOCK_CHAIN_ID: u8 = 4;
+
+/// Gas used by transactions unless changed with [`MockAptosState::gas_used`]
+pub const DEFAULT_GAS_USED: u64 = 1_000;
+
+/// Responds to a view function call given its JSON arguments
+pub type ViewHandler = Arc<dyn Fn(&[Value]) -> Vec<Value> + Send + Sync>;
+
+/// A block of the mock chain. Blocks hold no transaction bodies, only versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockBlock {
+    /// Block height
+    pub height: u64,
+    /// Block hash, derived from the height
+    pub hash: HashValue,
+    /// Block timestamp in microseconds
+    pub timestamp_usecs: u64,
+    /// Version of the first transaction in the block
+    pub first_version: u64,
+    /// Version of the last transaction in the block
+    pub last_version: u64,
+}
+
+/// An event emitted to an event handle
+#[derive(Debug, Clone)]
+pub struct MockEvent {
+    /// Version of the transaction that emitted the event
+    pub version: u64,
+    /// Move type of the event, e.g. `0x1::mailbox::DispatchEvent`
+    pub event_type: String,
+    /// Event data as returned by the REST API
+    pub data: Value,
+}
+
+/// What happens to the next transaction submitted or simulated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionOutcome {
+    /// The transaction is committed and succeeds
+    Success,
+    /// The transaction is committed but fails with `vm_status`,
+    /// e.g. `Move abort in 0x1::coin: 0x10006`
+    Failure {
+        /// VM status reported for the transaction
+        vm_status: String,
+    },
+    /// The node refuses the transaction with an API error
+    Rejection {
+        /// Aptos API error code, e.g. `mempool_is_full`
+        error_code: String,
+        /// Error message
+        message: String,
+    },
+}
+
+/// A view function call received by the node
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewCall {
+    /// Function called, e.g. `0x1::mailbox::delivered`
+    pub function: String,
+    /// JSON arguments of the call
+    pub arguments: Vec<Value>,
+    /// Ledger version the call was pinned to, `None` for the latest state
+    pub ledger_version: Option<u64>,
+}
+
+/// An event handle of a resource, identified by account, resource type and field
+type EventHandleKey = (AccountAddress, String, String);
+
+/// Scriptable state of a [`crate::MockAptosNode`]
+pub struct MockAptosState {
+    /// Chain id in responses and ledger headers
+    pub chain_id: u8,
+    /// Epoch in ledger headers
+    pub epoch: u64,
+    /// Gas price returned by the gas estimation endpoint
+    pub gas_estimate: u64,
+    /// Gas used by committed and simulated transactions
+    pub gas_used: u64,
+    /// Latest version processed by the GraphQL indexer, the latest version
+    /// if `None`. GraphQL queries don't see events of later versions.
+    pub indexed_version: Option<u64>,
+    blocks: BTreeMap<u64, MockBlock>,
+    sequence_numbers: HashMap<AccountAddress, u64>,
+    resources: HashMap<(AccountAddress, String), Value>,
+    events: HashMap<EventHandleKey, Vec<MockEvent>>,
+    views: HashMap<String, ViewHandler>,
+    view_calls: Vec<ViewCall>,
+    graphql_operations: Vec<String>,
+    submit_outcomes: VecDeque<TransactionOutcome>,
+    simulation_outcomes: VecDeque<TransactionOutcome>,
+    transactions: HashMap<HashValue, Value>,
+    submitted: Vec<SignedTransaction>,
+    simulated: Vec<SignedTransaction>,
+}
+
+impl Default for MockAptosState {
+    fn default() -> Self {
+        let mut state = Self {
+            chain_id: MOCK_CHAIN_ID,
+            epoch: 1,
+            gas_estimate: 100,
+            gas_used: DEFAULT_GAS_USED,
+            indexed_version: None,
+            blocks: Default::default(),
+            sequence_numbers: Default::default(),
+            resources: Default::default(),
+            events: Default::default(),
+            views: Default::default(),
+            view_calls: Default::default(),
+            graphql_operations: Default::default(),
+            submit_outcomes: Default::default(),
+            simulation_outcomes: Default::default(),
+            transactions: Default::default(),
+            submitted: Default::default(),
+            simulated: Default::default(),
+        };
+        // genesis
+        state.insert_block(0, 1);
+        state
+    }
+}
+
+impl std::fmt::Debug for MockAptosState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockAptosState")
+            .field("chain_id", &self.chain_id)
+            .field("latest_block", self.latest_block())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockAptosState {
+    /// Latest version processed by the GraphQL indexer
+    pub fn latest_indexed_version(&self) -> u64 {
+        self.indexed_version
+            .unwrap_or_else(|| self.latest_block().last_version)
+    }
+
+    /// The most recent block
+    pub fn latest_block(&self) -> &MockBlock {
+        self.blocks
+            .values()
+            .next_back()
+            .expect("the mock chain always has a genesis block")
+    }
+
+    /// The block at `height`
+    pub fn block(&self, height: u64) -> Option<&MockBlock> {
+        self.blocks.get(&height)
+    }
+
+    /// The block with hash `hash`
+    pub fn block_by_hash(&self, hash: HashValue) -> Option<&MockBlock> {
+        self.blocks.values().find(|block| block.hash == hash)
+    }
+
+    /// The block containing the transaction at `version`
+    pub fn block_by_version(&self, version: u64) -> Option<&MockBlock> {
+        self.blocks
+            .values()
+            .find(|block| (block.first_version..=block.last_version).contains(&version))
+    }
+
+    /// Append a block holding `transactions` transactions, at least one
+    pub fn add_block(&mut self, transactions: u64) -> MockBlock {
+        let height = self.latest_block().height + 1;
+        self.insert_block(height, transactions)
+    }
+
+    /// Append `count` blocks of one transaction each
+    pub fn add_blocks(&mut self, count: u64) {
+        for _ in 0..count {
+            self.add_block(1);
+        }
+    }
+
+    fn insert_block(&mut self, height: u64, transactions: u64) -> MockBlock {
+        let first_version = self
+            .blocks
+            .values()
+            .next_back()
+            .map_or(0, |block| block.last_version + 1);
+        let block = MockBlock {
+            height,
+            hash: HashValue::sha3_256_of(&height.to_le_bytes()),
+            timestamp_usecs: 1_700_000_000_000_000 + height * 1_000_000,
+            first_version,
+            last_version: first_version + transactions.max(1) - 1,
+        };
+        self.blocks.insert(height, block.clone());
+        block
+    }
+
+    /// Create `address` if needed and set its sequence number
+    pub fn set_sequence_number(&mut self, address: AccountAddress, sequence_number: u64) {
+        self.sequence_numbers.insert(address, sequence_number);
+    }
+
+    /// Sequence number of `address`, if the account exists
+    pub fn sequence_number(&self, address: AccountAddress) -> Option<u64> {
+        self.sequence_numbers.get(&address).copied()
+    }
+
+    /// Store a resource of type `resource_type` at `address`.
+    /// Event handles of the resource are filled in from emitted events.
+    pub fn set_resource(&mut self, address: AccountAddress, resource_type: &str, data: Value) {
+        self.resources
+            .insert((address, normalize_tag(resource_type)), data);
+    }
+
+    /// Get a resource with its event handle counters filled in
+    pub fn resource(&self, address: AccountAddress, resource_type: &str) -> Option<Value> {
+        let resource_type = normalize_tag(resource_type);
+        let handles: Vec<_> = self
+            .events
+            .iter()
+            .filter(|((account, tag, _), _)| *account == address && *tag == resource_type)
+            .collect();
+        let stored = self.resources.get(&(address, resource_type.clone()));
+        if stored.is_none() && handles.is_empty() {
+            return None;
+        }
+        let mut data = stored
+            .cloned()
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        for (creation_number, ((_, _, field_name), events)) in handles.into_iter().enumerate() {
+            data[field_name.as_str()] = serde_json::json!({
+                "counter": events.len().to_string(),
+                "guid": {
+                    "id": {
+                        "addr": address.to_hex_literal(),
+                        "creation_num": creation_number.to_string(),
+                    }
+                }
+            });
+        }
+        Some(data)
+    }
+
+    /// Get every resource of `address`, by type, with their event handle
+    /// counters filled in
+    pub fn resources(&self, address: AccountAddress) -> BTreeMap<String, Value> {
+        self.resources
+            .keys()
+            .map(|(account, tag)| (account, tag))
+            .chain(self.events.keys().map(|(account, tag, _)| (account, tag)))
+            .filter(|(account, _)| **account == address)
+            .filter_map(|(_, tag)| Some((tag.clone(), self.resource(address, tag)?)))
+            .collect()
+    }
+
+    /// Emit an event at `version` to the event handle `field_name` of the
+    /// `resource_type` resource at `address`. Returns its sequence number.
+    pub fn emit_event(
+        &mut self,
+        address: AccountAddress,
+        resource_type: &str,
+        field_name: &str,
+        version: u64,
+        event_type: &str,
+        data: Value,
+    ) -> u64 {
+        let events = self
+            .events
+            .entry((address, normalize_tag(resource_type), field_name.to_owned()))
+            .or_default();
+        events.push(MockEvent {
+            version,
+            event_type: event_type.to_owned(),
+            data,
+        });
+        events.len() as u64 - 1
+    }
+
+    /// Events of an event handle, in sequence number order
+    pub fn events(
+        &self,
+        address: AccountAddress,
+        resource_type: &str,
+        field_name: &str,
+    ) -> &[MockEvent] {
+        self.events
+            .get(&(address, normalize_tag(resource_type), field_name.to_owned()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Events of type `event_type` emitted to any event handle of `address`,
+    /// with their sequence numbers, in version order
+    pub(crate) fn events_of_type(
+        &self,
+        address: AccountAddress,
+        event_type: &str,
+    ) -> Vec<(u64, &MockEvent)> {
+        let event_type = normalize_tag(event_type);
+        let mut events: Vec<_> = self
+            .events
+            .iter()
+            .filter(|((account, _, _), _)| *account == address)
+            .flat_map(|(_, events)| events.iter().enumerate())
+            .filter(|(_, event)| normalize_tag(&event.event_type) == event_type)
+            .map(|(sequence_number, event)| (sequence_number as u64, event))
+            .collect();
+        events.sort_by_key(|(sequence_number, event)| (event.version, *sequence_number));
+        events
+    }
+
+    /// Answer calls of the view function `function`, e.g. `0x1::mailbox::delivered`,
+    /// with `response` whatever the arguments
+    pub fn set_view(&mut self, function: &str, response: Vec<Value>) {
+        self.set_view_fn(function, move |_| response.clone());
+    }
+
+    /// Answer calls of the view function `function` with `handler`
+    pub fn set_view_fn(
+        &mut self,
+        function: &str,
+        handler: impl Fn(&[Value]) -> Vec<Value> + Send + Sync + 'static,
+    ) {
+        self.views
+            .insert(normalize_tag(function), Arc::new(handler));
+    }
+
+    /// The handler of the view function `function`
+    pub fn view(&self, function: &str) -> Option<ViewHandler> {
+        self.views.get(&normalize_tag(function)).cloned()
+    }
+
+    /// Whether `address` publishes the module `module_name`, i.e. a view
+    /// function of the module is set
+    pub fn has_module(&self, address: AccountAddress, module_name: &str) -> bool {
+        let prefix = format!("{}::{module_name}::", address.to_hex_literal());
+        self.views
+            .keys()
+            .any(|function| function.starts_with(&prefix))
+    }
+
+    /// View function calls received so far, in order
+    pub fn view_calls(&self) -> &[ViewCall] {
+        &self.view_calls
+    }
+
+    pub(crate) fn record_view_call(&mut self, call: ViewCall) {
+        self.view_calls.push(call);
+    }
+
+    /// Names of the GraphQL operations received so far, in order
+    pub fn graphql_operations(&self) -> &[String] {
+        &self.graphql_operations
+    }
+
+    pub(crate) fn record_graphql_operation(&mut self, operation_name: String) {
+        self.graphql_operations.push(operation_name);
+    }
+
+    /// Decide the outcome of the next submitted transaction.
+    /// Transactions succeed once the queue is empty.
+    pub fn push_submit_outcome(&mut self, outcome: TransactionOutcome) {
+        self.submit_outcomes.push_back(outcome);
+    }
+
+    /// Decide the outcome of the next simulated transaction.
+    /// Simulations succeed once the queue is empty.
+    pub fn push_simulation_outcome(&mut self, outcome: TransactionOutcome) {
+        self.simulation_outcomes.push_back(outcome);
+    }
+
+    pub(crate) fn next_submit_outcome(&mut self) -> TransactionOutcome {
+        self.submit_outcomes
+            .pop_front()
+            .unwrap_or(TransactionOutcome::Success)
+    }
+
+    pub(crate) fn next_simulation_outcome(&mut self) -> TransactionOutcome {
+        self.simulation_outcomes
+            .pop_front()
+            .unwrap_or(TransactionOutcome::Success)
+    }
+
+    /// Transactions accepted by the node, in submission order
+    pub fn submitted_transactions(&self) -> &[SignedTransaction] {
+        &self.submitted
+    }
+
+    /// Transactions simulated on the node, in order
+    pub fn simulated_transactions(&self) -> &[SignedTransaction] {
+        &self.simulated
+    }
+
+    /// A committed transaction as returned by the REST API
+    pub fn transaction(&self, hash: &HashValue) -> Option<&Value> {
+        self.transactions.get(hash)
+    }
+
+    pub(crate) fn record_submitted(
+        &mut self,
+        hash: HashValue,
+        transaction: SignedTransaction,
+        json: Value,
+    ) {
+        self.sequence_numbers
+            .insert(transaction.sender(), transaction.sequence_number() + 1);
+        self.transactions.insert(hash, json);
+        self.submitted.push(transaction);
+    }
+
+    pub(crate) fn record_simulated(&mut self, transaction: SignedTransaction) {
+        self.simulated.push(transaction);
+    }
+}
+
+/// Normalize the address part of a `<address>::<module>::<name>` tag,
+/// so short and long address forms match
+pub(crate) fn normalize_tag(tag: &str) -> String {
+    match tag.split_once("::") {
+        Some((address, rest)) => match parse_address(address) {
+            Some(address) => format!("{}::{rest}", address.to_hex_literal()),
+            None => tag.to_owned(),
+        },
+        None => tag.to_owned(),
+    }
+}
+
+/// Parse an account address in long or short form, with or without `0x`
+pub(crate) fn parse_address(address: &str) -> Option<AccountAddress> {
+    AccountAddress::from_hex_literal(&format!("0x{}", address.trim_start_matches("0x"))).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_are_contiguous() {
+        let mut state = MockAptosState::default();
+        let first = state.add_block(3);
+        let second = state.add_block(1);
+        assert_eq!(first.first_version, 1);
+        assert_eq!(first.last_version, 3);
+        assert_eq!(second.first_version, 4);
+        assert_eq!(state.latest_block(), &second);
+        assert_eq!(state.block_by_version(2), Some(&first));
+    }
+
+    #[test]
+    fn test_resource_event_handle_counters() {
+        let mut state = MockAptosState::default();
+        let address = AccountAddress::from_hex_literal("0xabc").unwrap();
+        let tag = format!("0x{}::mailbox::MailBoxState", address.to_hex());
+        assert_eq!(state.resource(address, &tag), None);
+
+        state.emit_event(
+            address,
+            &tag,
+            "dispatch_events",
+            1,
+            "0xabc::mailbox::DispatchEvent",
+            Value::Null,
+        );
+        state.emit_event(
+            address,
+            &tag,
+            "dispatch_events",
+            2,
+            "0xabc::mailbox::DispatchEvent",
+            Value::Null,
+        );
+        let resource = state
+            .resource(address, "0xabc::mailbox::MailBoxState")
+            .unwrap();
+        assert_eq!(resource["dispatch_events"]["counter"], "2");
+    }
+}
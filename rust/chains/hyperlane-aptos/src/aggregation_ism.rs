@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use hyperlane_core::{
+    AggregationIsm, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, RawHyperlaneMessage, H256,
+};
+
+use crate::utils;
+use crate::{AptosClient, AptosHpProvider, ConnectionConf, LazyIsmLocation};
+
+use aptos_sdk::types::account_address::AccountAddress;
+
+/// A reference to an m-of-n aggregation ISM on some Aptos chain
+#[derive(Debug)]
+pub struct AptosAggregationIsm {
+    domain: HyperlaneDomain,
+    aptos_client: AptosClient,
+    location: LazyIsmLocation,
+}
+
+impl AptosAggregationIsm {
+    /// Create a new Aptos AggregationIsm.
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> Self {
+        let location = LazyIsmLocation::new(AccountAddress::new(locator.address.0));
+        let aptos_client = AptosClient::new(conf, &locator.domain);
+
+        Self {
+            domain: locator.domain.clone(),
+            aptos_client,
+            location,
+        }
+    }
+}
+
+impl HyperlaneContract for AptosAggregationIsm {
+    fn address(&self) -> H256 {
+        self.location.address().into_bytes().into()
+    }
+}
+
+impl HyperlaneChain for AptosAggregationIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(AptosHpProvider::new(
+            self.domain.clone(),
+            self.aptos_client.clone(),
+        ))
+    }
+}
+
+#[async_trait]
+impl AggregationIsm for AptosAggregationIsm {
+    #[instrument(err, ret, skip(self))]
+    async fn modules_and_threshold(
+        &self,
+        message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let view_response = self
+            .location
+            .get(&self.aptos_client)
+            .await?
+            .view(
+                &self.aptos_client,
+                "aggregation_ism",
+                "modules_and_threshold",
+                vec![serde_json::json!(hex::encode(RawHyperlaneMessage::from(
+                    message
+                )))],
+            )
+            .await?;
+
+        let function = "aggregation_ism::modules_and_threshold";
+        let modules = utils::decode_view_addresses(function, &view_response, 0)?;
+        let threshold = utils::decode_view_uint(function, &view_response, 1)?;
+        Ok((modules, threshold))
+    }
+}
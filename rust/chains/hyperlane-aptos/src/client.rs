@@ -1,26 +1,499 @@
-use aptos_sdk::rest_client::Client;
-use std::str::FromStr;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use aptos_sdk::{
+    crypto::HashValue,
+    rest_client::{
+        aptos::Balance,
+        aptos_api_types::{
+            Block, GasEstimation, MoveModuleBytecode, PendingTransaction, Transaction,
+            UserTransaction, VersionedEvent, ViewRequest,
+        },
+        error::RestError,
+        Account, Client, Resource, Response, State,
+    },
+    types::{account_address::AccountAddress, transaction::SignedTransaction},
+};
+use async_trait::async_trait;
+use hyperlane_core::{
+    rpc_clients::{BlockNumberGetter, FallbackProvider},
+    ChainCommunicationError, ChainResult, HyperlaneDomain, H256,
+};
+use once_cell::sync::Lazy;
+use tracing::warn;
 use url::Url;
 
-/// Aptos RPC client
-pub struct AptosClient(Client);
+use crate::{
+    convert_hex_string_to_h256, AptosClientMetrics, AptosError, AptosFallbackProvider,
+    AptosGraphqlClient, ConnectionConf,
+};
+
+/// Number of blocks kept per node before the lowest heights are evicted
+const BLOCK_CACHE_CAPACITY: usize = 10_000;
+
+/// Number of failed requests after which a call gives up, like `FallbackProvider::call`
+const MAX_FAILED_REQUESTS: usize = 4;
+
+/// Pause before trying every endpoint again once all of them failed
+const RETRY_SLEEP_DURATION: Duration = Duration::from_millis(100);
+
+/// Block caches shared by every client talking to the same node
+static BLOCK_CACHES: Lazy<Mutex<HashMap<String, Arc<BlockCache>>>> = Lazy::new(Default::default);
+
+type AptosResult<T> = Result<T, RestError>;
+
+/// A single REST endpoint of an [`AptosClient`]
+#[derive(Clone)]
+pub struct AptosRestEndpoint {
+    client: Client,
+    url: Url,
+    node_host: String,
+    chain: String,
+    metrics: AptosClientMetrics,
+}
+
+impl AptosRestEndpoint {
+    fn new(url: &Url, chain: &HyperlaneDomain, metrics: AptosClientMetrics) -> Self {
+        let host = url.host_str().unwrap_or("unknown");
+        let node_host = match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        Self {
+            client: Client::new(url.clone()),
+            url: url.clone(),
+            node_host,
+            chain: chain.name().to_string(),
+            metrics,
+        }
+    }
+
+    fn record_request(&self, method: &str, success: bool, duration: Duration) {
+        self.metrics
+            .record_request(&self.node_host, &self.chain, method, success, duration);
+    }
+}
+
+impl Debug for AptosRestEndpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AptosRestEndpoint({})", self.url)
+    }
+}
+
+#[async_trait]
+impl BlockNumberGetter for AptosRestEndpoint {
+    async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
+        let state = self
+            .client
+            .get_ledger_information()
+            .await
+            .map_err(AptosError::from)?
+            .into_inner();
+        Ok(state.block_height)
+    }
+}
+
+/// Aptos RPC client.
+/// Requests go to the highest priority REST endpoint and fall back to the
+/// next ones when it fails, deprioritizing endpoints that stall.
+#[derive(Clone)]
+pub struct AptosClient {
+    provider: AptosFallbackProvider<AptosRestEndpoint>,
+    cache_key: String,
+    blocks: Arc<BlockCache>,
+    graphql_client: Option<AptosGraphqlClient>,
+}
+
 impl AptosClient {
-    /// Create a new aptos rpc client from node url
-    pub fn new(rpc_endpoint: String) -> Self {
-        Self(Client::new(Url::from_str(&rpc_endpoint).unwrap()))
+    /// Create a new aptos rpc client for the REST endpoints of `conf`,
+    /// in priority order
+    pub fn new(conf: &ConnectionConf, domain: &HyperlaneDomain) -> Self {
+        let endpoints = conf
+            .urls
+            .iter()
+            .map(|url| AptosRestEndpoint::new(url, domain, conf.client_metrics.clone()));
+        let provider = AptosFallbackProvider::new(FallbackProvider::new(endpoints));
+        let cache_key = conf
+            .urls
+            .iter()
+            .map(Url::as_str)
+            .collect::<Vec<_>>()
+            .join(",");
+        let blocks = BLOCK_CACHES
+            .lock()
+            .unwrap()
+            .entry(cache_key.clone())
+            .or_default()
+            .clone();
+        Self {
+            provider,
+            cache_key,
+            blocks,
+            graphql_client: conf.indexer_backend.graphql_client(),
+        }
+    }
+
+    /// Identifies the nodes this client talks to, the same for every
+    /// client of the same endpoints
+    pub fn cache_key(&self) -> &str {
+        &self.cache_key
+    }
+
+    /// Block cache shared with other clients of the same node
+    pub fn block_cache(&self) -> &BlockCache {
+        &self.blocks
+    }
+
+    /// Client of the indexer GraphQL API, if one is configured
+    pub fn graphql_client(&self) -> Option<&AptosGraphqlClient> {
+        self.graphql_client.as_ref()
+    }
+
+    /// Fetch the block at `height`, serving it from the cache when possible
+    pub async fn get_cached_block_by_height(&self, height: u64) -> ChainResult<CachedBlock> {
+        if let Some(block) = self.blocks.get(height) {
+            return Ok(block);
+        }
+        let block = self
+            .get_block_by_height(height, false)
+            .await
+            .map_err(AptosError::from)?
+            .into_inner();
+        let block = CachedBlock {
+            height: block.block_height.0,
+            hash: convert_hex_string_to_h256(&block.block_hash.to_string())
+                .map_err(|msg| ChainCommunicationError::ParseError { msg })?,
+            timestamp: block.block_timestamp.0,
+            first_version: block.first_version.0,
+            last_version: block.last_version.0,
+        };
+        self.blocks.insert(block.clone());
+        Ok(block)
+    }
+
+    /// Get the latest ledger information
+    pub async fn get_ledger_information(&self) -> AptosResult<Response<State>> {
+        self.call("get_ledger_information", |client| async move {
+            client.get_ledger_information().await
+        })
+        .await
+    }
+
+    /// Get the node's gas price estimate
+    pub async fn estimate_gas_price(&self) -> AptosResult<Response<GasEstimation>> {
+        self.call("estimate_gas_price", |client| async move {
+            client.estimate_gas_price().await
+        })
+        .await
+    }
+
+    /// Get an account, including its sequence number
+    pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
+        self.call("get_account", |client| async move {
+            client.get_account(address).await
+        })
+        .await
+    }
+
+    /// Get the APT balance of an account
+    pub async fn get_account_balance(
+        &self,
+        address: AccountAddress,
+    ) -> AptosResult<Response<Balance>> {
+        self.call("get_account_balance", |client| async move {
+            client.get_account_balance(address).await
+        })
+        .await
+    }
+
+    /// Get a resource of an account, `None` if the account doesn't hold it
+    pub async fn get_account_resource(
+        &self,
+        address: AccountAddress,
+        resource_type: &str,
+    ) -> AptosResult<Response<Option<Resource>>> {
+        self.call("get_account_resource", |client| async move {
+            client.get_account_resource(address, resource_type).await
+        })
+        .await
+    }
+
+    /// Get all resources of an account
+    pub async fn get_account_resources(
+        &self,
+        address: AccountAddress,
+    ) -> AptosResult<Response<Vec<Resource>>> {
+        self.call("get_account_resources", |client| async move {
+            client.get_account_resources(address).await
+        })
+        .await
+    }
+
+    /// Get a module published by an account
+    pub async fn get_account_module(
+        &self,
+        address: AccountAddress,
+        module_name: &str,
+    ) -> AptosResult<Response<MoveModuleBytecode>> {
+        self.call("get_account_module", |client| async move {
+            client.get_account_module(address, module_name).await
+        })
+        .await
+    }
+
+    /// Get events of an event handle of an account resource
+    pub async fn get_account_events(
+        &self,
+        address: AccountAddress,
+        struct_tag: &str,
+        field_name: &str,
+        start: Option<u64>,
+        limit: Option<u16>,
+    ) -> AptosResult<Response<Vec<VersionedEvent>>> {
+        self.call("get_account_events", |client| async move {
+            client
+                .get_account_events(address, struct_tag, field_name, start, limit)
+                .await
+        })
+        .await
+    }
+
+    /// Get a block by its height
+    pub async fn get_block_by_height(
+        &self,
+        height: u64,
+        with_transactions: bool,
+    ) -> AptosResult<Response<Block>> {
+        self.call("get_block_by_height", |client| async move {
+            client.get_block_by_height(height, with_transactions).await
+        })
+        .await
+    }
+
+    /// Get the block containing the transaction at `version`
+    pub async fn get_block_by_version(
+        &self,
+        version: u64,
+        with_transactions: bool,
+    ) -> AptosResult<Response<Block>> {
+        self.call("get_block_by_version", |client| async move {
+            client
+                .get_block_by_version(version, with_transactions)
+                .await
+        })
+        .await
+    }
+
+    /// Get a transaction by its hash
+    pub async fn get_transaction_by_hash(
+        &self,
+        hash: HashValue,
+    ) -> AptosResult<Response<Transaction>> {
+        self.call("get_transaction_by_hash", |client| async move {
+            client.get_transaction_by_hash(hash).await
+        })
+        .await
+    }
+
+    /// Call a view function
+    pub async fn view(
+        &self,
+        request: &ViewRequest,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<serde_json::Value>>> {
+        self.call("view", |client| async move {
+            client.view(request, version).await
+        })
+        .await
+    }
+
+    /// Simulate a transaction
+    pub async fn simulate(
+        &self,
+        txn: &SignedTransaction,
+    ) -> AptosResult<Response<Vec<UserTransaction>>> {
+        self.call(
+            "simulate",
+            |client| async move { client.simulate(txn).await },
+        )
+        .await
+    }
+
+    /// Submit a transaction. Resubmitting it to another endpoint after a
+    /// failure is safe, the transaction hash doesn't change.
+    pub async fn submit(
+        &self,
+        txn: &SignedTransaction,
+    ) -> AptosResult<Response<PendingTransaction>> {
+        self.call("submit", |client| async move { client.submit(txn).await })
+            .await
+    }
+
+    /// Wait for a submitted transaction to be committed
+    pub async fn wait_for_transaction(
+        &self,
+        pending_transaction: &PendingTransaction,
+    ) -> AptosResult<Response<Transaction>> {
+        self.call("wait_for_transaction", |client| async move {
+            client.wait_for_transaction(pending_transaction).await
+        })
+        .await
+    }
+
+    /// Call the endpoints in order of priority until one responds.
+    /// Errors the node answered deliberately, e.g. a missing resource or a
+    /// rejected transaction, are returned right away.
+    async fn call<T, F, Fut>(&self, method: &'static str, f: F) -> AptosResult<T>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = AptosResult<T>>,
+    {
+        let mut failures = 0;
+        loop {
+            for priority in self.provider.take_priorities_snapshot().await {
+                let endpoint = &self.provider.inner.providers[priority.index];
+                let start = Instant::now();
+                let result = f(endpoint.client.clone()).await;
+                let retryable = result.as_ref().err().is_some_and(is_retryable);
+                endpoint.record_request(method, result.is_ok(), start.elapsed());
+                self.provider
+                    .handle_stalled_provider(&priority, endpoint)
+                    .await;
+                match result {
+                    Err(err) if retryable => {
+                        warn!(?endpoint, method, error = %err, "Aptos REST request failed");
+                        failures += 1;
+                        if failures >= MAX_FAILED_REQUESTS {
+                            return Err(err);
+                        }
+                    }
+                    result => return result,
+                }
+            }
+            if failures == 0 {
+                return Err(RestError::Unknown(anyhow::anyhow!(
+                    "No Aptos REST endpoints configured"
+                )));
+            }
+            tokio::time::sleep(RETRY_SLEEP_DURATION).await;
+        }
     }
 }
 
-impl std::ops::Deref for AptosClient {
-    type Target = Client;
+/// Whether another endpoint may succeed where this request failed:
+/// connection errors, rate limiting and server errors. Other errors are
+/// answers from a healthy node, e.g. a missing resource, a rejected
+/// transaction or a timeout waiting for one.
+fn is_retryable(err: &RestError) -> bool {
+    match err {
+        RestError::Api(response) => {
+            let status = response.status_code.as_u16();
+            status == 429 || status >= 500
+        }
+        RestError::Http(..) => true,
+        _ => false,
+    }
+}
+
+impl Debug for AptosClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AptosClient")
+            .field("provider", &self.provider)
+            .finish()
+    }
+}
+
+/// The parts of an Aptos block the agents need, without its transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedBlock {
+    /// Block height
+    pub height: u64,
+    /// Block hash
+    pub hash: H256,
+    /// Block timestamp in microseconds
+    pub timestamp: u64,
+    /// Version of the first transaction in the block
+    pub first_version: u64,
+    /// Version of the last transaction in the block
+    pub last_version: u64,
+}
+
+/// Bounded cache of blocks by height, with a hash -> height index.
+/// Aptos blocks are final once committed, so entries never go stale.
+#[derive(Debug, Default)]
+pub struct BlockCache {
+    inner: Mutex<BlockCacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct BlockCacheInner {
+    by_height: BTreeMap<u64, CachedBlock>,
+    height_by_hash: HashMap<H256, u64>,
+}
+
+impl BlockCache {
+    /// Get the cached block at `height`
+    pub fn get(&self, height: u64) -> Option<CachedBlock> {
+        self.inner.lock().unwrap().by_height.get(&height).cloned()
+    }
+
+    /// Get the cached block with hash `hash`
+    pub fn get_by_hash(&self, hash: &H256) -> Option<CachedBlock> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .height_by_hash
+            .get(hash)
+            .and_then(|height| inner.by_height.get(height))
+            .cloned()
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    /// Cache `block`, evicting the lowest heights once full
+    pub fn insert(&self, block: CachedBlock) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.height_by_hash.insert(block.hash, block.height);
+        inner.by_height.insert(block.height, block);
+        while inner.by_height.len() > BLOCK_CACHE_CAPACITY {
+            if let Some((_, evicted)) = inner.by_height.pop_first() {
+                inner.height_by_hash.remove(&evicted.hash);
+            }
+        }
     }
 }
 
-impl std::fmt::Debug for AptosClient {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("AptosClient { ... }")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(height: u64) -> CachedBlock {
+        CachedBlock {
+            height,
+            hash: H256::from_low_u64_be(height),
+            timestamp: height * 1000,
+            first_version: height * 10,
+            last_version: height * 10 + 9,
+        }
+    }
+
+    #[test]
+    fn test_block_cache_evicts_lowest_heights() {
+        let cache = BlockCache::default();
+        for height in 0..(BLOCK_CACHE_CAPACITY as u64 + 2) {
+            cache.insert(block(height));
+        }
+        assert_eq!(cache.get(0), None);
+        assert_eq!(cache.get(1), None);
+        assert_eq!(cache.get(2), Some(block(2)));
+        assert_eq!(cache.get_by_hash(&block(0).hash), None);
+    }
+
+    #[test]
+    fn test_block_cache_lookup_by_hash() {
+        let cache = BlockCache::default();
+        cache.insert(block(7));
+        assert_eq!(cache.get_by_hash(&block(7).hash), Some(block(7)));
+        assert_eq!(cache.get_by_hash(&block(8).hash), None);
     }
 }
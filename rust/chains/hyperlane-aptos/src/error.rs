@@ -0,0 +1,151 @@
+use aptos_sdk::rest_client::{aptos_api_types::Transaction as AptosTransaction, error::RestError};
+use hyperlane_core::ChainCommunicationError;
+
+/// Errors from talking to an Aptos node and decoding its responses.
+/// This error can then be converted into the broader error type
+/// in hyperlane-core using the `From` trait impl
+#[derive(Debug, thiserror::Error)]
+pub enum AptosError {
+    /// Aptos REST API error
+    #[error("{0}")]
+    Rest(#[from] RestError),
+    /// Json error
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+    /// Hex error
+    #[error("{0}")]
+    Hex(#[from] hex::FromHexError),
+    /// The fully qualified name of a Move function could not be parsed
+    #[error("Invalid function {function}: {reason}")]
+    InvalidFunction {
+        /// Function name, e.g. `0x1::mailbox::delivered`
+        function: String,
+        /// Why it is invalid
+        reason: String,
+    },
+    /// A view function returned values of an unexpected shape
+    #[error("Unexpected response from view function {function}: {reason}")]
+    InvalidViewResponse {
+        /// Function called, e.g. `mailbox::delivered`
+        function: String,
+        /// What was wrong with the response
+        reason: String,
+    },
+    /// The node returned a response that can't be interpreted
+    #[error("Invalid response from Aptos node: {0}")]
+    InvalidResponse(String),
+    /// A request to the Aptos indexer GraphQL API failed
+    #[error("Aptos indexer GraphQL request failed: {0}")]
+    Graphql(String),
+}
+
+impl From<AptosError> for ChainCommunicationError {
+    fn from(err: AptosError) -> Self {
+        ChainCommunicationError::from_other(err)
+    }
+}
+
+/// Errors from submitting a transaction to an Aptos node
+#[derive(Debug, thiserror::Error)]
+pub enum AptosTxError {
+    /// The transaction was committed but aborted in a Move module
+    #[error("Transaction {hash} aborted in {location} with code {code}")]
+    MoveAbort {
+        /// Transaction hash
+        hash: String,
+        /// Module that aborted, e.g. `0x1::coin`
+        location: String,
+        /// Abort code
+        code: u64,
+        /// The committed transaction
+        transaction: Box<AptosTransaction>,
+    },
+    /// The transaction was committed but failed for another reason, e.g. out of gas
+    #[error("Transaction {hash} failed: {vm_status}")]
+    ExecutionFailure {
+        /// Transaction hash
+        hash: String,
+        /// VM status reported by the node
+        vm_status: String,
+        /// The committed transaction
+        transaction: Box<AptosTransaction>,
+    },
+    /// The node refused to accept the transaction into its mempool
+    #[error("Transaction rejected by mempool: {0}")]
+    MempoolRejection(String),
+    /// The transaction was not committed before it expired
+    #[error("Timed out waiting for transaction {0}")]
+    Timeout(String),
+    /// Any other error talking to the node
+    #[error("Aptos RPC error: {0}")]
+    Rpc(String),
+}
+
+impl AptosTxError {
+    /// Whether the node rejected the transaction because its sequence number
+    /// was already used or is too far ahead of the account's
+    pub fn is_stale_sequence_number(&self) -> bool {
+        match self {
+            AptosTxError::MempoolRejection(msg) => {
+                let msg = msg.to_uppercase();
+                msg.contains("SEQUENCE_NUMBER_TOO_OLD") || msg.contains("SEQUENCE_NUMBER_TOO_NEW")
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the committed transaction if the failure happened on chain,
+    /// in which case gas was still spent.
+    pub fn into_committed_transaction(self) -> Result<AptosTransaction, Self> {
+        match self {
+            AptosTxError::MoveAbort { transaction, .. }
+            | AptosTxError::ExecutionFailure { transaction, .. } => Ok(*transaction),
+            err => Err(err),
+        }
+    }
+}
+
+impl From<AptosTxError> for ChainCommunicationError {
+    fn from(err: AptosTxError) -> Self {
+        match err {
+            AptosTxError::Timeout(_) => ChainCommunicationError::TransactionTimeout(),
+            err => ChainCommunicationError::from_other(err),
+        }
+    }
+}
+
+/// Reasons a simulated transaction would fail on chain
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AptosSimulationError {
+    /// The simulation aborted in a Move module
+    #[error("Simulation aborted in {location} with code {code}: {vm_status}")]
+    MoveAbort {
+        /// Module that aborted, e.g. `0x1::coin`
+        location: String,
+        /// Abort code
+        code: u64,
+        /// VM status reported by the node
+        vm_status: String,
+    },
+    /// The simulation failed for another reason, e.g. out of gas
+    #[error("Simulation failed: {vm_status}")]
+    ExecutionFailure {
+        /// VM status reported by the node
+        vm_status: String,
+    },
+}
+
+impl From<AptosSimulationError> for ChainCommunicationError {
+    fn from(err: AptosSimulationError) -> Self {
+        match err {
+            // The transaction itself would revert, e.g. the recipient rejects the message
+            AptosSimulationError::MoveAbort { .. } => {
+                ChainCommunicationError::from_would_revert(err)
+            }
+            // Not about the transaction, e.g. out of gas or an unfunded signer
+            AptosSimulationError::ExecutionFailure { .. } => {
+                ChainCommunicationError::from_other(err)
+            }
+        }
+    }
+}
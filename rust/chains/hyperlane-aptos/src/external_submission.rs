@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use hyperlane_core::{ChainCommunicationError, ChainResult, H256};
+use serde::Serialize;
+use url::Url;
+
+/// Where to hand `process` payloads to when the relayer does not sign them itself,
+/// e.g. because the key is held by a separate custody system
+#[derive(Debug, Clone)]
+pub enum ExternalSubmission {
+    /// Append one JSON record per payload to a file
+    File(PathBuf),
+    /// POST each JSON record to an HTTP endpoint
+    Endpoint(Url),
+}
+
+/// A `handle_message` call to be signed and submitted by someone else
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessPayloadRecord {
+    /// Id of the message being delivered
+    pub message_id: H256,
+    /// Entry function to call, `<recipient>::<module>::handle_message`
+    pub function: String,
+    /// BCS encoded `EntryFunction`, hex encoded
+    pub payload: String,
+}
+
+impl ExternalSubmission {
+    /// Hand `record` over for external submission
+    pub async fn emit(&self, record: &ProcessPayloadRecord) -> ChainResult<()> {
+        let mut line = serde_json::to_vec(record)?;
+        match self {
+            ExternalSubmission::File(path) => {
+                line.push(b'\n');
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| file.write_all(&line))
+                    .map_err(ChainCommunicationError::from_other)
+            }
+            ExternalSubmission::Endpoint(url) => {
+                reqwest::Client::new()
+                    .post(url.clone())
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(line)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(ChainCommunicationError::from_other)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Hands `process` payloads over for external submission, once per message.
+///
+/// `process` reports handed over messages as not executed, so the relayer
+/// reprepares and processes them again until the external submitter lands
+/// the transaction. Payloads of messages already handed over are not emitted
+/// again while the relayer runs.
+#[derive(Debug)]
+pub(crate) struct ExternalSubmitter {
+    target: ExternalSubmission,
+    emitted: Mutex<HashSet<H256>>,
+}
+
+impl ExternalSubmitter {
+    pub(crate) fn new(target: ExternalSubmission) -> Self {
+        Self {
+            target,
+            emitted: Default::default(),
+        }
+    }
+
+    /// Hand `record` over unless a payload for the same message already was.
+    /// Returns whether `record` was handed over.
+    pub(crate) async fn submit(&self, record: &ProcessPayloadRecord) -> ChainResult<bool> {
+        if !self.emitted.lock().unwrap().insert(record.message_id) {
+            return Ok(false);
+        }
+        if let Err(err) = self.target.emit(record).await {
+            self.emitted.lock().unwrap().remove(&record.message_id);
+            return Err(err);
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_emit_appends_json_lines_to_file() {
+        let path = std::env::temp_dir().join(format!(
+            "aptos-process-payloads-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let target = ExternalSubmission::File(path.clone());
+        let record = ProcessPayloadRecord {
+            message_id: H256::from_low_u64_be(1),
+            function: "0x1::hello::handle_message".to_owned(),
+            payload: "00".to_owned(),
+        };
+
+        target.emit(&record).await.unwrap();
+        target.emit(&record).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["function"], "0x1::hello::handle_message");
+        assert_eq!(lines[0]["payload"], "00");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_submit_emits_each_message_once() {
+        let path = std::env::temp_dir().join(format!(
+            "aptos-process-payloads-once-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let submitter = ExternalSubmitter::new(ExternalSubmission::File(path.clone()));
+        let record = |message_id| ProcessPayloadRecord {
+            message_id: H256::from_low_u64_be(message_id),
+            function: "0x1::hello::handle_message".to_owned(),
+            payload: "00".to_owned(),
+        };
+
+        assert!(submitter.submit(&record(1)).await.unwrap());
+        assert!(!submitter.submit(&record(1)).await.unwrap());
+        assert!(submitter.submit(&record(2)).await.unwrap());
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_submit_retries_failed_emissions() {
+        let dir = std::env::temp_dir().join(format!(
+            "aptos-process-payloads-missing-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let submitter =
+            ExternalSubmitter::new(ExternalSubmission::File(dir.join("payloads.jsonl")));
+        let record = ProcessPayloadRecord {
+            message_id: H256::from_low_u64_be(1),
+            function: "0x1::hello::handle_message".to_owned(),
+            payload: "00".to_owned(),
+        };
+
+        // the file can't be created before its directory
+        assert!(submitter.submit(&record).await.is_err());
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(submitter.submit(&record).await.unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use aptos_sdk::{
+    rest_client::aptos_api_types::VersionedEvent, types::account_address::AccountAddress,
+};
+use hyperlane_core::{ChainResult, H256};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use tracing::debug;
+use url::Url;
+
+use crate::{convert_hex_string_to_h256, AptosError};
+
+/// Maximum number of rows fetched per GraphQL request
+const GRAPHQL_PAGE_SIZE: u64 = 100;
+
+/// Events of one type emitted by an account in a range of blocks
+const EVENTS_QUERY: &str = r#"query Events($account: String!, $type: String!, $from: bigint!, $to: bigint!, $limit: Int!, $offset: Int!) {
+  events(
+    where: {account_address: {_eq: $account}, type: {_eq: $type}, transaction_block_height: {_gte: $from, _lte: $to}}
+    order_by: [{transaction_version: asc}, {event_index: asc}]
+    limit: $limit
+    offset: $offset
+  ) {
+    account_address
+    creation_number
+    sequence_number
+    transaction_version
+    type
+    data
+  }
+}"#;
+
+/// Events of one type emitted by an account in a range of sequence numbers
+const EVENTS_BY_SEQUENCE_QUERY: &str = r#"query EventsBySequence($account: String!, $type: String!, $from: bigint!, $to: bigint!, $limit: Int!, $offset: Int!) {
+  events(
+    where: {account_address: {_eq: $account}, type: {_eq: $type}, sequence_number: {_gte: $from, _lte: $to}}
+    order_by: [{transaction_version: asc}, {event_index: asc}]
+    limit: $limit
+    offset: $offset
+  ) {
+    account_address
+    creation_number
+    sequence_number
+    transaction_version
+    type
+    data
+  }
+}"#;
+
+/// Hashes of a set of blocks
+const BLOCK_HASHES_QUERY: &str = r#"query BlockHashes($heights: [bigint!]!) {
+  block_metadata_transactions(where: {block_height: {_in: $heights}}) {
+    block_height
+    id
+  }
+}"#;
+
+/// The block with a given hash
+const BLOCK_BY_HASH_QUERY: &str = r#"query BlockByHash($id: String!) {
+  block_metadata_transactions(where: {id: {_eq: $id}}) {
+    block_height
+    id
+  }
+}"#;
+
+/// Latest transaction version processed by an indexer processor
+const PROCESSOR_STATUS_QUERY: &str = r#"query ProcessorStatus($processor: String!) {
+  processor_status(where: {processor: {_eq: $processor}}) {
+    last_success_version
+  }
+}"#;
+
+/// The indexer processor filling the `events` table
+const EVENTS_PROCESSOR: &str = "events_processor";
+
+/// Where Aptos indexers read events from
+#[derive(Debug, Clone, Default)]
+pub enum AptosIndexerBackend {
+    /// Event handles and blocks of the node REST API
+    #[default]
+    Rest,
+    /// An Aptos indexer GraphQL API, e.g. `https://api.mainnet.aptoslabs.com/v1/graphql`
+    Graphql(Url),
+}
+
+impl AptosIndexerBackend {
+    /// A client of the GraphQL API, if indexers should read from one
+    pub fn graphql_client(&self) -> Option<AptosGraphqlClient> {
+        match self {
+            AptosIndexerBackend::Rest => None,
+            AptosIndexerBackend::Graphql(url) => Some(AptosGraphqlClient::new(url.clone())),
+        }
+    }
+}
+
+/// The events of a type to fetch from the indexer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphqlEventRange {
+    /// Events emitted in this range of block heights
+    Blocks(RangeInclusive<u64>),
+    /// Events with sequence numbers in this range
+    Sequences(RangeInclusive<u64>),
+}
+
+/// An event as stored in the `events` table of the Aptos indexer
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GraphqlEvent {
+    /// Account of the event handle, in long form
+    pub account_address: String,
+    /// Creation number of the event handle
+    #[serde(deserialize_with = "deserialize_bigint")]
+    pub creation_number: u64,
+    /// Sequence number of the event in its handle
+    #[serde(deserialize_with = "deserialize_bigint")]
+    pub sequence_number: u64,
+    /// Version of the transaction that emitted the event
+    #[serde(deserialize_with = "deserialize_bigint")]
+    pub transaction_version: u64,
+    /// Move type of the event, e.g. `0x1::mailbox::DispatchEvent`
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Event data
+    pub data: Value,
+}
+
+impl TryFrom<GraphqlEvent> for VersionedEvent {
+    type Error = AptosError;
+
+    /// The event as the REST API returns it
+    fn try_from(event: GraphqlEvent) -> Result<Self, Self::Error> {
+        Ok(serde_json::from_value(json!({
+            "version": event.transaction_version.to_string(),
+            "guid": {
+                "creation_number": event.creation_number.to_string(),
+                "account_address": event.account_address,
+            },
+            "sequence_number": event.sequence_number.to_string(),
+            "type": event.event_type,
+            "data": event.data,
+        }))?)
+    }
+}
+
+#[derive(Deserialize)]
+struct EventsData {
+    events: Vec<GraphqlEvent>,
+}
+
+#[derive(Deserialize)]
+struct BlockMetadata {
+    #[serde(deserialize_with = "deserialize_bigint")]
+    block_height: u64,
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct BlockHashesData {
+    block_metadata_transactions: Vec<BlockMetadata>,
+}
+
+#[derive(Deserialize)]
+struct ProcessorStatus {
+    #[serde(deserialize_with = "deserialize_bigint")]
+    last_success_version: u64,
+}
+
+#[derive(Deserialize)]
+struct ProcessorStatusData {
+    processor_status: Vec<ProcessorStatus>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlRequest<'a> {
+    operation_name: &'a str,
+    query: &'a str,
+    variables: Value,
+}
+
+#[derive(Deserialize)]
+struct GraphqlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphqlResponseError>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlResponseError {
+    message: String,
+}
+
+/// Client of an Aptos indexer GraphQL API
+#[derive(Debug, Clone)]
+pub struct AptosGraphqlClient {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl AptosGraphqlClient {
+    /// Create a client of the GraphQL API at `url`
+    pub fn new(url: Url) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+
+    /// Events of type `event_type` emitted by `account_address` in `range`,
+    /// in the order they were emitted
+    pub async fn events(
+        &self,
+        account_address: AccountAddress,
+        event_type: &str,
+        range: GraphqlEventRange,
+    ) -> ChainResult<Vec<GraphqlEvent>> {
+        let (operation_name, query, bounds) = match &range {
+            GraphqlEventRange::Blocks(blocks) => ("Events", EVENTS_QUERY, blocks),
+            GraphqlEventRange::Sequences(sequences) => {
+                ("EventsBySequence", EVENTS_BY_SEQUENCE_QUERY, sequences)
+            }
+        };
+        let mut events = Vec::new();
+        loop {
+            let page = self
+                .query::<EventsData>(
+                    operation_name,
+                    query,
+                    json!({
+                        "account": format!("0x{}", account_address.to_hex()),
+                        "type": event_type,
+                        "from": bounds.start(),
+                        "to": bounds.end(),
+                        "limit": GRAPHQL_PAGE_SIZE,
+                        "offset": events.len(),
+                    }),
+                )
+                .await?
+                .events;
+            let page_len = page.len() as u64;
+            events.extend(page);
+            if page_len < GRAPHQL_PAGE_SIZE {
+                break;
+            }
+        }
+        debug!(
+            ?range,
+            event_type,
+            count = events.len(),
+            "Fetched events from GraphQL"
+        );
+        Ok(events)
+    }
+
+    /// Hashes of the blocks at `heights`.
+    /// Blocks the indexer doesn't know about are left out.
+    pub async fn block_hashes(&self, heights: &[u64]) -> ChainResult<HashMap<u64, H256>> {
+        if heights.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let blocks = self
+            .query::<BlockHashesData>(
+                "BlockHashes",
+                BLOCK_HASHES_QUERY,
+                json!({ "heights": heights }),
+            )
+            .await?
+            .block_metadata_transactions;
+        blocks
+            .into_iter()
+            .map(|block| -> ChainResult<(u64, H256)> {
+                let hash =
+                    convert_hex_string_to_h256(&block.id).map_err(AptosError::InvalidResponse)?;
+                Ok((block.block_height, hash))
+            })
+            .collect()
+    }
+
+    /// Height of the block with hash `hash`, if the indexer knows it.
+    /// Block metadata transactions have the hash of their block as id.
+    pub async fn block_height_by_hash(&self, hash: &H256) -> ChainResult<Option<u64>> {
+        let blocks = self
+            .query::<BlockHashesData>(
+                "BlockByHash",
+                BLOCK_BY_HASH_QUERY,
+                json!({ "id": format!("{hash:?}") }),
+            )
+            .await?
+            .block_metadata_transactions;
+        Ok(blocks.first().map(|block| block.block_height))
+    }
+
+    /// Latest transaction version whose events the indexer has stored.
+    /// The indexer lags the node, so events of later versions may be missing.
+    pub async fn latest_indexed_version(&self) -> ChainResult<u64> {
+        let status = self
+            .query::<ProcessorStatusData>(
+                "ProcessorStatus",
+                PROCESSOR_STATUS_QUERY,
+                json!({ "processor": EVENTS_PROCESSOR }),
+            )
+            .await?
+            .processor_status;
+        let version = status
+            .first()
+            .map(|status| status.last_success_version)
+            .ok_or_else(|| {
+                AptosError::Graphql(format!("No status of the {EVENTS_PROCESSOR} processor"))
+            })?;
+        Ok(version)
+    }
+
+    async fn query<T: DeserializeOwned>(
+        &self,
+        operation_name: &str,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, AptosError> {
+        let request = GraphqlRequest {
+            operation_name,
+            query,
+            variables,
+        };
+        let body = self
+            .client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&request)?)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AptosError::Graphql(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| AptosError::Graphql(e.to_string()))?;
+        let response: GraphqlResponse<T> = serde_json::from_slice(&body)?;
+        if !response.errors.is_empty() {
+            let messages: Vec<_> = response.errors.into_iter().map(|e| e.message).collect();
+            return Err(AptosError::Graphql(messages.join("; ")));
+        }
+        response
+            .data
+            .ok_or_else(|| AptosError::Graphql(format!("No data in response to {operation_name}")))
+    }
+}
+
+/// Hasura returns `bigint` columns as numbers or strings depending on its settings
+fn deserialize_bigint<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BigInt {
+        Number(u64),
+        String(String),
+    }
+    match BigInt::deserialize(deserializer)? {
+        BigInt::Number(value) => Ok(value),
+        BigInt::String(value) => value.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphql_event_into_versioned_event() {
+        let event: GraphqlEvent = serde_json::from_value(json!({
+            "account_address": "0x00000000000000000000000000000000000000000000000000000000000000ab",
+            "creation_number": 2,
+            "sequence_number": "7",
+            "transaction_version": 1234,
+            "type": "0xab::mailbox::DispatchEvent",
+            "data": { "dest_domain": 1 },
+        }))
+        .unwrap();
+        assert_eq!(event.sequence_number, 7);
+
+        let event = VersionedEvent::try_from(event).unwrap();
+        assert_eq!(*event.version.inner(), 1234);
+        assert_eq!(*event.sequence_number.inner(), 7);
+        assert_eq!(event.data, json!({ "dest_domain": 1 }));
+    }
+
+    #[test]
+    fn test_graphql_event_rejects_malformed_numbers() {
+        let event = serde_json::from_value::<GraphqlEvent>(json!({
+            "account_address": "0xab",
+            "creation_number": 2,
+            "sequence_number": "seven",
+            "transaction_version": 1234,
+            "type": "0xab::mailbox::DispatchEvent",
+            "data": {},
+        }));
+        assert!(event.is_err());
+    }
+}
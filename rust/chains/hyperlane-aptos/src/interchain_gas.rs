@@ -5,12 +5,16 @@ use std::ops::RangeInclusive;
 use async_trait::async_trait;
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, InterchainGasPaymaster,
-    InterchainGasPayment, LogMeta, Mailbox, SequenceAwareIndexer, H256,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, IndexMode, Indexed, Indexer,
+    InterchainGasPaymaster, InterchainGasPayment, LogMeta, Mailbox, SequenceAwareIndexer, H256,
+    U256,
 };
 use tracing::{debug, info, instrument};
 
-use crate::{get_filtered_events, AptosHpProvider, ConnectionConf, GasPaymentEventData};
+use crate::{
+    fetch_events_in_range, get_event_count, get_indexed_tip, send_view_request, utils,
+    AptosEventHandle, AptosGraphqlClient, AptosHpProvider, ConnectionConf, GasPaymentEventData,
+};
 
 use crate::AptosClient;
 use aptos_sdk::types::account_address::AccountAddress;
@@ -20,21 +24,102 @@ use aptos_sdk::types::account_address::AccountAddress;
 pub struct AptosInterchainGasPaymaster {
     domain: HyperlaneDomain,
     package_address: AccountAddress,
-    aptos_client_url: String,
+    aptos_client: AptosClient,
+}
+
+/// Exchange rate and gas price the gas oracle holds for a remote domain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AptosRemoteGasData {
+    /// Remote token value in local tokens, scaled by `TOKEN_EXCHANGE_RATE_SCALE`
+    pub token_exchange_rate: u128,
+    /// Gas price on the remote domain
+    pub gas_price: u128,
 }
 
 impl AptosInterchainGasPaymaster {
     /// Create a new Aptos IGP.
     pub fn new(conf: &ConnectionConf, locator: &ContractLocator) -> Self {
-        let package_address =
-            AccountAddress::from_bytes(<[u8; 32]>::from(locator.address)).unwrap();
-        let aptos_client_url = conf.url.to_string();
+        let package_address = AccountAddress::new(locator.address.0);
         Self {
             package_address,
             domain: locator.domain.clone(),
-            aptos_client_url,
+            aptos_client: AptosClient::new(conf, &locator.domain),
         }
     }
+
+    /// Payment in octas the IGP requires for `gas_amount` of gas on `destination`,
+    /// including the destination's gas overhead
+    pub async fn quote_gas_payment_octas(
+        &self,
+        destination: u32,
+        gas_amount: U256,
+    ) -> ChainResult<U256> {
+        // u256 arguments and results are passed as decimal strings
+        let view_response = self
+            .view(
+                "igps",
+                "quote_gas_payment",
+                vec![
+                    serde_json::json!(destination),
+                    serde_json::json!(gas_amount.to_string()),
+                ],
+            )
+            .await?;
+        Ok(utils::decode_view_u256(
+            "igps::quote_gas_payment",
+            &view_response,
+            0,
+        )?)
+    }
+
+    /// The gas oracle data of `destination`, zero if the oracle has none
+    pub async fn remote_gas_data(&self, destination: u32) -> ChainResult<AptosRemoteGasData> {
+        let view_response = self
+            .view(
+                "gas_oracle",
+                "get_exchange_rate_and_gas_price",
+                vec![serde_json::json!(destination)],
+            )
+            .await?;
+        let function = "gas_oracle::get_exchange_rate_and_gas_price";
+        Ok(AptosRemoteGasData {
+            token_exchange_rate: utils::decode_view_uint(function, &view_response, 0)?,
+            gas_price: utils::decode_view_uint(function, &view_response, 1)?,
+        })
+    }
+
+    /// The gas added to the gas amount of every payment for `destination`
+    pub async fn gas_overhead(&self, destination: u32) -> ChainResult<U256> {
+        let view_response = self
+            .view(
+                "igps",
+                "get_destination_gas_overhead",
+                vec![serde_json::json!(destination)],
+            )
+            .await?;
+        Ok(utils::decode_view_u256(
+            "igps::get_destination_gas_overhead",
+            &view_response,
+            0,
+        )?)
+    }
+
+    async fn view(
+        &self,
+        module_name: &str,
+        function_name: &str,
+        arguments: Vec<serde_json::Value>,
+    ) -> ChainResult<Vec<serde_json::Value>> {
+        send_view_request(
+            &self.aptos_client,
+            self.package_address.to_hex_literal(),
+            module_name.to_string(),
+            function_name.to_string(),
+            vec![],
+            arguments,
+        )
+        .await
+    }
 }
 
 impl HyperlaneContract for AptosInterchainGasPaymaster {
@@ -51,31 +136,67 @@ impl HyperlaneChain for AptosInterchainGasPaymaster {
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
         Box::new(AptosHpProvider::new(
             self.domain.clone(),
-            self.aptos_client_url.clone(),
+            self.aptos_client.clone(),
         ))
     }
 }
 
-impl InterchainGasPaymaster for AptosInterchainGasPaymaster {}
+#[async_trait]
+impl InterchainGasPaymaster for AptosInterchainGasPaymaster {
+    #[instrument(err, ret, skip(self))]
+    async fn quote_gas_payment(
+        &self,
+        destination: u32,
+        gas_amount: U256,
+    ) -> ChainResult<Option<U256>> {
+        self.quote_gas_payment_octas(destination, gas_amount)
+            .await
+            .map(Some)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    async fn destination_gas_overhead(&self, destination: u32) -> ChainResult<Option<U256>> {
+        self.gas_overhead(destination).await.map(Some)
+    }
+}
 
 /// Struct that retrieves event data for a Aptos IGP contract
 #[derive(Debug)]
 pub struct AptosInterchainGasPaymasterIndexer {
     aptos_client: AptosClient,
+    graphql_client: Option<AptosGraphqlClient>,
+    index_mode: IndexMode,
     package_address: AccountAddress,
 }
 
 impl AptosInterchainGasPaymasterIndexer {
     /// Create a new Aptos IGP indexer.
-    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> Self {
-        let package_address =
-            AccountAddress::from_bytes(<[u8; 32]>::from(locator.address)).unwrap();
-        let aptos_client = AptosClient::new(conf.url.to_string());
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator, index_mode: IndexMode) -> Self {
+        let package_address = AccountAddress::new(locator.address.0);
+        let aptos_client = AptosClient::new(conf, &locator.domain);
         Self {
             aptos_client,
+            graphql_client: conf.indexer_backend.graphql_client(),
+            index_mode,
             package_address,
         }
     }
+
+    fn igp_state_tag(&self) -> String {
+        format!("{}::igps::IgpState", self.package_address.to_hex_literal())
+    }
+
+    fn gas_payment_event_handle(&self) -> AptosEventHandle {
+        AptosEventHandle {
+            account_address: self.package_address,
+            struct_tag: self.igp_state_tag(),
+            field_name: "gas_payment_events",
+            event_type: format!(
+                "{}::igps::GasPaymentEvent",
+                self.package_address.to_hex_literal()
+            ),
+        }
+    }
 }
 
 #[async_trait]
@@ -88,25 +209,18 @@ impl Indexer<InterchainGasPayment> for AptosInterchainGasPaymasterIndexer {
             ?range,
             "AptosInterchainGasPaymasterIndexer::Indexer<InterchainGasPayment>::fetch_logs"
         );
-        get_filtered_events::<InterchainGasPayment, GasPaymentEventData>(
+        fetch_events_in_range::<InterchainGasPayment, GasPaymentEventData>(
             &self.aptos_client,
-            self.package_address,
-            &format!("{}::igps::IgpState", self.package_address.to_hex_literal()),
-            "gas_payment_events",
+            self.graphql_client.as_ref(),
+            self.index_mode,
+            &self.gas_payment_event_handle(),
             range,
         )
         .await
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        let chain_state = self
-            .aptos_client
-            .get_ledger_information()
-            .await
-            .map_err(ChainCommunicationError::from_other)
-            .unwrap()
-            .into_inner();
-        Ok(chain_state.block_height as u32)
+        get_indexed_tip(&self.aptos_client, self.graphql_client.as_ref()).await
     }
 }
 
@@ -114,6 +228,15 @@ impl Indexer<InterchainGasPayment> for AptosInterchainGasPaymasterIndexer {
 impl SequenceAwareIndexer<InterchainGasPayment> for AptosInterchainGasPaymasterIndexer {
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
         let tip = Indexer::<InterchainGasPayment>::get_finalized_block_number(self as _).await?;
-        Ok((None, tip))
+        let count = get_event_count(
+            &self.aptos_client,
+            self.package_address,
+            &self.igp_state_tag(),
+            "gas_payment_events",
+        )
+        .await?
+        .try_into()
+        .map_err(ChainCommunicationError::from_other)?;
+        Ok((Some(count), tip))
     }
 }
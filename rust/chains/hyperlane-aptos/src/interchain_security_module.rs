@@ -1,39 +1,165 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
 use async_trait::async_trait;
 use num_traits::cast::FromPrimitive;
-use solana_sdk::signature::Keypair;
-use tracing::warn;
+use once_cell::sync::Lazy;
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
 
 use hyperlane_core::{
-    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneMessage, InterchainSecurityModule, ModuleType, H256, U256,
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, InterchainSecurityModule, ModuleType, RawHyperlaneMessage,
+    H256, U256,
 };
 
+use crate::signers::AptosSigner;
+use crate::simulate_aptos_transaction;
 use crate::utils;
 use crate::AptosClient;
+use crate::AptosError;
 use crate::ConnectionConf;
 
-use aptos_sdk::types::account_address::AccountAddress;
+use aptos_sdk::{rest_client::error::RestError, types::account_address::AccountAddress};
+
+/// Modules of the ISM package that keep ISM state in the account hosting the ISM
+const ISM_MODULES: [&str; 3] = ["multisig_ism", "domain_routing_ism", "aggregation_ism"];
+
+/// Locations of hosted ISMs by node and ISM address. An account hosts an
+/// ISM for good, so locations are never invalidated.
+static ISM_LOCATIONS: Lazy<Mutex<HashMap<(String, AccountAddress), IsmLocation>>> =
+    Lazy::new(Default::default);
+
+/// Where the views of an ISM are called.
+///
+/// An ISM is either an ISM package or hosted by one at another account, as
+/// the mailbox only verifies ISMs of its ISM package. The views of hosted
+/// ISMs are the `_at` variants of the package views, which take the ISM
+/// address as first argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IsmLocation {
+    package: AccountAddress,
+    ism: AccountAddress,
+}
+
+impl IsmLocation {
+    /// Locate the ISM at `ism` from the package of the ISM state it holds.
+    /// An account holding none is taken to be an ISM package.
+    pub(crate) async fn resolve(
+        aptos_client: &AptosClient,
+        ism: AccountAddress,
+    ) -> ChainResult<Self> {
+        let key = (aptos_client.cache_key().to_owned(), ism);
+        if let Some(location) = ISM_LOCATIONS.lock().unwrap().get(&key) {
+            return Ok(*location);
+        }
+        let resources = aptos_client
+            .get_account_resources(ism)
+            .await
+            .map_err(AptosError::from)?
+            .into_inner();
+        let Some(package) = resources
+            .iter()
+            .map(|resource| &resource.resource_type)
+            .find(|tag| ISM_MODULES.contains(&tag.module.as_str()))
+            .map(|tag| tag.address)
+        else {
+            return Ok(Self { package: ism, ism });
+        };
+        let location = Self { package, ism };
+        ISM_LOCATIONS.lock().unwrap().insert(key, location);
+        Ok(location)
+    }
+
+    /// Address of the package whose code verifies the ISM
+    pub(crate) fn package(&self) -> AccountAddress {
+        self.package
+    }
+
+    /// Whether the ISM is hosted at an account other than its package
+    pub(crate) fn is_hosted(&self) -> bool {
+        self.package != self.ism
+    }
+
+    /// Call the view `module_name::function_name` of the ISM
+    pub(crate) async fn view(
+        &self,
+        aptos_client: &AptosClient,
+        module_name: &str,
+        function_name: &str,
+        arguments: Vec<serde_json::Value>,
+    ) -> ChainResult<Vec<serde_json::Value>> {
+        let (function_name, arguments) = if self.is_hosted() {
+            let mut hosted_arguments = vec![serde_json::json!(self.ism.to_hex_literal())];
+            hosted_arguments.extend(arguments);
+            (format!("{function_name}_at"), hosted_arguments)
+        } else {
+            (function_name.to_string(), arguments)
+        };
+        utils::send_view_request(
+            aptos_client,
+            self.package.to_hex_literal(),
+            module_name.to_string(),
+            function_name,
+            vec![],
+            arguments,
+        )
+        .await
+    }
+}
+
+/// Lazily resolved location of the ISM at `address`
+#[derive(Debug)]
+pub(crate) struct LazyIsmLocation {
+    address: AccountAddress,
+    location: OnceCell<IsmLocation>,
+}
+
+impl LazyIsmLocation {
+    pub(crate) fn new(address: AccountAddress) -> Self {
+        Self {
+            address,
+            location: OnceCell::new(),
+        }
+    }
+
+    /// Address of the ISM
+    pub(crate) fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    /// Location of the ISM, resolved on first use
+    pub(crate) async fn get(&self, aptos_client: &AptosClient) -> ChainResult<IsmLocation> {
+        self.location
+            .get_or_try_init(|| IsmLocation::resolve(aptos_client, self.address))
+            .await
+            .copied()
+    }
+}
 
 /// A reference to an InterchainSecurityModule contract on some Sealevel chain
 #[allow(unused)]
 #[derive(Debug)]
 pub struct AptosInterchainSecurityModule {
     aptos_client: AptosClient,
-    package_address: AccountAddress,
-    payer: Option<Keypair>,
+    location: LazyIsmLocation,
+    payer: Option<AptosSigner>,
     domain: HyperlaneDomain,
 }
 
 impl AptosInterchainSecurityModule {
     /// Create a new sealevel InterchainSecurityModule
-    pub fn new(conf: &ConnectionConf, locator: ContractLocator, payer: Option<Keypair>) -> Self {
-        let aptos_client = AptosClient::new(conf.url.to_string());
-        let package_address =
-            AccountAddress::from_bytes(<[u8; 32]>::from(locator.address)).unwrap();
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        payer: Option<AptosSigner>,
+    ) -> Self {
+        let aptos_client = AptosClient::new(conf, &locator.domain);
+        let location = LazyIsmLocation::new(AccountAddress::new(locator.address.0));
         Self {
             aptos_client,
             payer,
-            package_address,
+            location,
             domain: locator.domain.clone(),
         }
     }
@@ -41,7 +167,7 @@ impl AptosInterchainSecurityModule {
 
 impl HyperlaneContract for AptosInterchainSecurityModule {
     fn address(&self) -> H256 {
-        self.package_address.into_bytes().into()
+        self.location.address().into_bytes().into()
     }
 }
 
@@ -53,7 +179,7 @@ impl HyperlaneChain for AptosInterchainSecurityModule {
     fn provider(&self) -> Box<dyn hyperlane_core::HyperlaneProvider> {
         Box::new(crate::AptosHpProvider::new(
             self.domain.clone(),
-            self.aptos_client.path_prefix_string(),
+            self.aptos_client.clone(),
         ))
     }
 }
@@ -61,20 +187,28 @@ impl HyperlaneChain for AptosInterchainSecurityModule {
 #[async_trait]
 impl InterchainSecurityModule for AptosInterchainSecurityModule {
     async fn module_type(&self) -> ChainResult<ModuleType> {
-        let view_response = utils::send_view_request(
-            &self.aptos_client,
-            self.package_address.to_hex_literal(),
-            "multisig_ism".to_string(),
-            "get_module_type".to_string(),
-            vec![],
-            vec![],
-        )
-        .await?;
+        let location = self.location.get(&self.aptos_client).await?;
+        let view_response = match location
+            .view(&self.aptos_client, "ism", "get_module_type", vec![])
+            .await
+        {
+            Ok(view_response) => view_response,
+            Err(err) => {
+                // ISM packages published before the `ism` module only have the multisig ISM
+                return match self
+                    .aptos_client
+                    .get_account_module(location.package(), "ism")
+                    .await
+                {
+                    Err(RestError::Api(response)) if response.status_code.as_u16() == 404 => {
+                        Ok(ModuleType::MessageIdMultisig)
+                    }
+                    _ => Err(err),
+                };
+            }
+        };
 
-        let view_result: u64 = serde_json::from_str::<String>(&view_response[0].to_string())
-            .unwrap()
-            .parse()
-            .unwrap();
+        let view_result: u64 = utils::decode_view_uint("ism::get_module_type", &view_response, 0)?;
 
         if let Some(module_type) = ModuleType::from_u64(view_result) {
             Ok(module_type)
@@ -86,10 +220,42 @@ impl InterchainSecurityModule for AptosInterchainSecurityModule {
 
     async fn dry_run_verify(
         &self,
-        _message: &HyperlaneMessage,
-        _metadata: &[u8],
+        message: &HyperlaneMessage,
+        metadata: &[u8],
     ) -> ChainResult<Option<U256>> {
-        // TODO: Implement this once we have aggregation ISM support in Aptos
-        Ok(Some(U256::zero()))
+        let encoded_message = RawHyperlaneMessage::from(message);
+        let location = self.location.get(&self.aptos_client).await?;
+
+        // Verification gas can only be estimated by simulating with a signer
+        let payer = self
+            .payer
+            .as_ref()
+            .ok_or(ChainCommunicationError::SignerUnavailable)?;
+
+        let mut args = vec![
+            bcs::to_bytes(&metadata.to_vec()).map_err(ChainCommunicationError::from_other)?,
+            bcs::to_bytes(&encoded_message).map_err(ChainCommunicationError::from_other)?,
+        ];
+        let function_name = if location.is_hosted() {
+            args.insert(
+                0,
+                bcs::to_bytes(&self.location.address())
+                    .map_err(ChainCommunicationError::from_other)?,
+            );
+            "assert_verified_at"
+        } else {
+            "assert_verified"
+        };
+        let payload =
+            utils::make_aptos_payload(location.package(), "ism", function_name, vec![], args);
+
+        let response = simulate_aptos_transaction(&self.aptos_client, payer, payload)
+            .await
+            .map_err(|e| ChainCommunicationError::CustomError(e.to_string()))?;
+        if !response.success {
+            debug!(vm_status = %response.vm_status, "ISM verification simulation failed");
+            return Ok(None);
+        }
+        Ok(Some(U256::from(response.gas_used.0)))
     }
 }
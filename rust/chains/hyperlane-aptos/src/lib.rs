@@ -5,28 +5,43 @@
 #![deny(warnings)]
 
 pub use crate::multisig_ism::*;
-pub use client::AptosClient;
+pub use aggregation_ism::*;
+pub use client::{AptosClient, AptosRestEndpoint, BlockCache, CachedBlock};
+pub use error::*;
+pub use external_submission::*;
+pub use graphql::*;
 pub use interchain_gas::*;
 pub use interchain_security_module::*;
 pub use mailbox::*;
 pub use merkle_tree_hook::*;
+pub use nonce_manager::AptosNonceManager;
 pub use provider::*;
-pub use solana_sdk::signer::keypair::Keypair;
+pub use routing_ism::*;
+pub use rpc_clients::*;
 pub use trait_builder::*;
 pub use types::*;
 pub use utils::*;
 pub use validator_announce::*;
+pub use warp_route::*;
 
+mod aggregation_ism;
 mod client;
+mod error;
+mod external_submission;
+mod graphql;
 mod interchain_gas;
 mod interchain_security_module;
 mod mailbox;
 mod merkle_tree_hook;
 mod multisig_ism;
+mod nonce_manager;
 mod provider;
+mod routing_ism;
+mod rpc_clients;
 /// signers
 pub mod signers;
 mod trait_builder;
 mod types;
 mod utils;
 mod validator_announce;
+mod warp_route;
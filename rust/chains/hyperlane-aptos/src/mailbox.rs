@@ -1,6 +1,7 @@
 #![allow(warnings)] // FIXME remove
 
 use std::ops::RangeInclusive;
+use std::sync::Arc;
 use std::{collections::HashMap, num::NonZeroU64, str::FromStr as _};
 
 use aptos_sdk::move_types::identifier::Identifier;
@@ -9,21 +10,23 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use hyperlane_core::{FixedPointNumber, Indexed, SequenceAwareIndexer};
 use jsonrpc_core::futures_util::TryFutureExt;
 use jsonrpc_core::Middleware;
-use tracing::{debug, info, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 
 use hyperlane_core::{
     accumulator::incremental::IncrementalMerkle, ChainCommunicationError, ChainResult, Checkpoint,
     ContractLocator, Decode as _, Encode as _, HyperlaneAbi, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexer, LogMeta, Mailbox,
-    MerkleTreeHook, TxCostEstimate, TxOutcome, H256, H512, U256,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, IndexMode, Indexer, LogMeta, Mailbox,
+    MerkleTreeHook, RawHyperlaneMessage, TxCostEstimate, TxOutcome, H256, H512, U256,
 };
 
 use crate::{
-    convert_keypair_to_aptos_account, get_filtered_events, simulate_aptos_transaction, utils,
-    AptosHpProvider, ConnectionConf, MsgProcessEventData, GAS_UNIT_PRICE,
+    fetch_events_in_range, get_event_count, get_indexed_tip, simulate_aptos_transaction_at_price,
+    utils, AptosEventHandle, AptosGraphqlClient, AptosHpProvider, AptosNonceManager,
+    ConnectionConf, ExternalSubmitter, MsgProcessEventData, ProcessPayloadRecord,
+    TransactionOverrides, GAS_BUFFER_PERCENT, GAS_UNIT_LIMIT, GAS_UNIT_PRICE,
 };
 
-use solana_sdk::signature::Keypair;
+use crate::signers::AptosSigner;
 
 use crate::types::{DispatchEventData, MoveMerkleTree};
 use crate::utils::{convert_hex_string_to_h256, send_aptos_transaction};
@@ -51,9 +54,13 @@ use aptos_sdk::{
 /// A reference to a Mailbox contract on some Aptos chain
 pub struct AptosMailbox {
     pub(crate) domain: HyperlaneDomain,
-    payer: Option<Keypair>,
+    signer: Option<Arc<AptosNonceManager>>,
     pub(crate) aptos_client: AptosClient,
     pub(crate) package_address: AccountAddress,
+    transaction_overrides: TransactionOverrides,
+    external_submission: Option<ExternalSubmitter>,
+    /// Module names of recipients, which never change once registered
+    module_names: std::sync::Mutex<HashMap<AccountAddress, Vec<u8>>>,
 }
 
 impl AptosMailbox {
@@ -61,22 +68,28 @@ impl AptosMailbox {
     pub fn new(
         conf: &ConnectionConf,
         locator: ContractLocator,
-        payer: Option<Keypair>,
+        payer: Option<AptosSigner>,
     ) -> ChainResult<Self> {
         let domain = locator.domain.id();
-        let package_address =
-            AccountAddress::from_bytes(<[u8; 32]>::from(locator.address)).unwrap();
-        let aptos_client = AptosClient::new(conf.url.to_string());
+        let package_address = AccountAddress::new(locator.address.0);
+        let aptos_client = AptosClient::new(conf, &locator.domain);
 
         Ok(AptosMailbox {
             domain: locator.domain.clone(),
-            payer,
+            signer: payer.map(|payer| AptosNonceManager::for_signer(&aptos_client, &payer)),
             package_address,
             aptos_client,
+            transaction_overrides: conf.transaction_overrides.clone(),
+            external_submission: conf.external_submission.clone().map(ExternalSubmitter::new),
+            module_names: Default::default(),
         })
     }
 
     async fn fetch_module_name(&self, package_addy: &AccountAddress) -> ChainResult<Vec<u8>> {
+        if let Some(module_name) = self.module_names.lock().unwrap().get(package_addy) {
+            return Ok(module_name.clone());
+        }
+
         let view_response = utils::send_view_request(
             &self.aptos_client,
             self.package_address.to_hex_literal(),
@@ -87,20 +100,82 @@ impl AptosMailbox {
         )
         .await?;
 
-        let module_name = serde_json::from_str::<String>(&view_response[0].to_string()).unwrap();
         let module_name_bytes =
-            hex::decode(module_name.to_string().trim_start_matches("0x")).unwrap();
-
-        if module_name_bytes.len() > 0 {
-            Ok(module_name_bytes)
-        } else {
-            Err(ChainCommunicationError::from_other_str(
-                "Could not find a module",
-            ))
+            utils::decode_view_bytes("mailbox::recipient_module_name", &view_response, 0)?;
+
+        if module_name_bytes.is_empty() {
+            // The mailbox aborts deliveries to recipients that never registered
+            return Err(ChainCommunicationError::CustomError(format!(
+                "Recipient {} has no module registered with the mailbox",
+                package_addy.to_hex_literal()
+            )));
         }
+        self.module_names
+            .lock()
+            .unwrap()
+            .insert(*package_addy, module_name_bytes.clone());
+        Ok(module_name_bytes)
+    }
+
+    /// Payload of the recipient's `handle_message` call that delivers `message`
+    async fn process_payload(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TransactionPayload> {
+        let recipient: AccountAddress = message.recipient.0.into();
+        let module_name = self.fetch_module_name(&recipient).await?;
+        handle_message_payload(recipient, module_name, message, metadata)
+    }
+
+    /// Like `process_payload`, for callers that cannot await. It only knows
+    /// the module names of recipients looked up before, which preparing a
+    /// message with `process_estimate_costs` does.
+    fn cached_process_payload(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TransactionPayload> {
+        let recipient: AccountAddress = message.recipient.0.into();
+        let module_name = self
+            .module_names
+            .lock()
+            .unwrap()
+            .get(&recipient)
+            .cloned()
+            .ok_or_else(|| {
+                ChainCommunicationError::CustomError(format!(
+                    "Module name of recipient {} not looked up yet, the message must be prepared first",
+                    recipient.to_hex_literal()
+                ))
+            })?;
+        handle_message_payload(recipient, module_name, message, metadata)
     }
 }
 
+/// Build the payload calling `handle_message` on the recipient module
+fn handle_message_payload(
+    recipient: AccountAddress,
+    module_name: Vec<u8>,
+    message: &HyperlaneMessage,
+    metadata: &[u8],
+) -> ChainResult<TransactionPayload> {
+    let module_name =
+        Identifier::from_utf8(module_name).map_err(|e| ChainCommunicationError::ParseError {
+            msg: format!("Invalid recipient module name: {e}"),
+        })?;
+    Ok(TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(recipient, module_name),
+        ident_str!("handle_message").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&RawHyperlaneMessage::from(message))
+                .map_err(ChainCommunicationError::from_other)?,
+            bcs::to_bytes(&metadata.to_vec()).map_err(ChainCommunicationError::from_other)?,
+        ],
+    )))
+}
+
 impl HyperlaneContract for AptosMailbox {
     fn address(&self) -> H256 {
         self.package_address.into_bytes().into()
@@ -115,7 +190,7 @@ impl HyperlaneChain for AptosMailbox {
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
         Box::new(AptosHpProvider::new(
             self.domain.clone(),
-            self.aptos_client.path_prefix_string(),
+            self.aptos_client.clone(),
         ))
     }
 }
@@ -129,8 +204,8 @@ impl std::fmt::Debug for AptosMailbox {
 #[async_trait]
 impl Mailbox for AptosMailbox {
     #[instrument(err, ret, skip(self))]
-    async fn count(&self, _maybe_lag: Option<NonZeroU64>) -> ChainResult<u32> {
-        <Self as MerkleTreeHook>::count(self, _maybe_lag).await
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        <Self as MerkleTreeHook>::count(self, lag).await
     }
 
     #[instrument(err, ret, skip(self))]
@@ -144,8 +219,11 @@ impl Mailbox for AptosMailbox {
             vec![serde_json::json!(hex::encode(id.as_bytes()))],
         )
         .await?;
-        let view_result = serde_json::from_str::<bool>(&view_response[0].to_string()).unwrap();
-        Ok(view_result)
+        Ok(utils::decode_view_value(
+            "mailbox::delivered",
+            &view_response,
+            0,
+        )?)
     }
 
     #[instrument(err, ret, skip(self))]
@@ -160,15 +238,31 @@ impl Mailbox for AptosMailbox {
         )
         .await?;
 
-        let ism_address = serde_json::from_str::<String>(&view_response[0].to_string()).unwrap();
-
-        Ok(convert_hex_string_to_h256(&ism_address).unwrap())
+        Ok(utils::decode_view_address(
+            "mailbox::get_default_ism",
+            &view_response,
+            0,
+        )?)
     }
 
     #[instrument(err, ret, skip(self))]
     async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
-        // !TODO
-        self.default_ism().await
+        let view_response = utils::send_view_request(
+            &self.aptos_client,
+            self.package_address.to_hex_literal(),
+            "mailbox".to_string(),
+            "recipient_ism".to_string(),
+            vec![],
+            vec![serde_json::json!(
+                AccountAddress::new(recipient.0).to_hex_literal()
+            )],
+        )
+        .await?;
+
+        match parse_recipient_ism(&view_response)? {
+            Some(ism) => Ok(ism),
+            None => self.default_ism().await,
+        }
     }
 
     #[instrument(err, ret, skip(self))]
@@ -178,53 +272,59 @@ impl Mailbox for AptosMailbox {
         metadata: &[u8],
         _tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        // get recipient address
-        let recipient: AccountAddress = message.recipient.0.into();
-
-        let mut encoded_message = vec![];
-        message.write_to(&mut encoded_message).unwrap();
+        let payload = self.process_payload(message, metadata).await?;
+
+        if let Some(external_submission) = &self.external_submission {
+            let TransactionPayload::EntryFunction(entry_function) = &payload else {
+                unreachable!("process payloads are entry functions");
+            };
+            let record = ProcessPayloadRecord {
+                message_id: message.id(),
+                function: format!(
+                    "{}::{}",
+                    entry_function.module().short_str_lossless(),
+                    entry_function.function()
+                ),
+                payload: hex::encode(
+                    bcs::to_bytes(entry_function).map_err(ChainCommunicationError::from_other)?,
+                ),
+            };
+            if external_submission.submit(&record).await? {
+                info!(?record, "Handed process payload to external submitter");
+            } else {
+                debug!(message_id = ?record.message_id, "Process payload already handed to external submitter");
+            }
+            // Delivery is confirmed once the external submitter lands the transaction
+            return Ok(TxOutcome {
+                transaction_id: H512::zero(),
+                executed: false,
+                gas_price: FixedPointNumber::zero(),
+                gas_used: U256::zero(),
+            });
+        }
 
-        let payer = self
-            .payer
+        let signer = self
+            .signer
             .as_ref()
             .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
 
-        let mut signer_account = convert_keypair_to_aptos_account(&self.aptos_client, payer).await;
-
-        let recipient_module_name = self.fetch_module_name(&recipient).await?;
-        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
-            ModuleId::new(
-                recipient,
-                Identifier::from_utf8(recipient_module_name).unwrap(),
-            ),
-            ident_str!("handle_message").to_owned(),
-            vec![],
-            vec![
-                bcs::to_bytes(&encoded_message).unwrap(),
-                bcs::to_bytes(&metadata.to_vec()).unwrap(),
-            ],
-        ));
-
-        let response =
-            send_aptos_transaction(&self.aptos_client, &mut signer_account, payload.clone())
-                .await
-                .map_err(|e| {
-                    println!("tx error {}", e.to_string());
-                    ChainCommunicationError::TransactionTimeout()
-                })?;
-
-        // fetch transaction information from the response
-        let tx_hash =
-            convert_hex_string_to_h256(&response.transaction_info().unwrap().hash.to_string())
-                .unwrap();
-        let has_success = response.success();
-        let gas_used = response.transaction_info().unwrap().gas_used;
-        Ok(TxOutcome {
-            transaction_id: H512::from(tx_hash),
-            executed: has_success,
-            gas_price: FixedPointNumber::zero(),
-            gas_used: U256::from(gas_used.0),
-        })
+        let transaction = match send_aptos_transaction(
+            &self.aptos_client,
+            signer,
+            payload,
+            &self.transaction_overrides,
+        )
+        .await
+        {
+            Ok(transaction) => transaction,
+            // Failed transactions still spend gas, report them as not executed
+            Err(err) => err.into_committed_transaction().map_err(|err| {
+                warn!(error = %err, "Failed to submit Aptos process transaction");
+                ChainCommunicationError::from(err)
+            })?,
+        };
+
+        utils::tx_outcome_from_transaction(&transaction)
     }
 
     #[instrument(err, ret, skip(self))]
@@ -233,83 +333,136 @@ impl Mailbox for AptosMailbox {
         message: &HyperlaneMessage,
         metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        let recipient: AccountAddress = message.recipient.0.into();
-
-        let mut encoded_message = vec![];
-        message.write_to(&mut encoded_message).unwrap();
-
-        let payer = self
-            .payer
-            .as_ref()
-            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
-
-        let mut signer_account = convert_keypair_to_aptos_account(&self.aptos_client, payer).await;
-        let recipient_module_name = self.fetch_module_name(&recipient).await.unwrap();
-        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
-            ModuleId::new(
-                recipient,
-                Identifier::from_utf8(recipient_module_name).unwrap(),
-            ),
-            ident_str!("handle_message").to_owned(),
-            vec![],
-            vec![
-                bcs::to_bytes(&encoded_message).unwrap(),
-                bcs::to_bytes(&metadata.to_vec()).unwrap(),
-            ],
-        ));
-
-        let response =
-            simulate_aptos_transaction(&self.aptos_client, &mut signer_account, payload.clone())
-                .await
-                .map_err(|e| {
-                    println!("tx error {}", e.to_string());
-                    ChainCommunicationError::TransactionTimeout()
-                })
-                .unwrap();
+        let payload = self.process_payload(message, metadata).await?;
+
+        let Some(signer) = self.signer.as_ref() else {
+            // Without a key to simulate with, externally submitted payloads
+            // are budgeted at the configured gas limit
+            if self.external_submission.is_some() {
+                return Ok(TxCostEstimate {
+                    gas_limit: U256::from(
+                        self.transaction_overrides
+                            .max_gas_amount
+                            .unwrap_or(GAS_UNIT_LIMIT),
+                    ),
+                    gas_price: FixedPointNumber::zero(),
+                    l2_gas_limit: None,
+                });
+            }
+            return Err(ChainCommunicationError::SignerUnavailable);
+        };
+
+        let gas_unit_price = utils::gas_unit_price(&self.aptos_client, &self.transaction_overrides)
+            .await
+            .map_err(ChainCommunicationError::from)?;
+        let max_gas_amount = self
+            .transaction_overrides
+            .max_gas_amount
+            .unwrap_or(GAS_UNIT_LIMIT);
+        let response = simulate_aptos_transaction_at_price(
+            &self.aptos_client,
+            signer.signer(),
+            payload,
+            gas_unit_price,
+            max_gas_amount,
+        )
+        .await
+        .map_err(|e| ChainCommunicationError::CustomError(format!("Simulation failed: {e:#}")))?;
+        if !response.success {
+            return Err(utils::simulation_error(response.vm_status).into());
+        }
 
+        let gas_buffer_percent = self
+            .transaction_overrides
+            .gas_buffer_percent
+            .unwrap_or(GAS_BUFFER_PERCENT);
+        let gas_limit = U256::from(response.gas_used.0) * (100 + gas_buffer_percent) / 100;
         Ok(TxCostEstimate {
-            gas_limit: U256::from(response.gas_used.0),
-            gas_price: FixedPointNumber::zero(),
+            gas_limit,
+            gas_price: FixedPointNumber::from(gas_unit_price),
             l2_gas_limit: None,
         })
     }
 
-    fn process_calldata(&self, _message: &HyperlaneMessage, _metadata: &[u8]) -> Vec<u8> {
-        todo!()
+    /// Returns the BCS encoded `EntryFunction` calling the recipient's `handle_message`.
+    /// The message must have been prepared with `process_estimate_costs`, which
+    /// looks the recipient's module up. An empty result means the calldata
+    /// couldn't be built and must not be submitted.
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        let entry_function = match self.cached_process_payload(message, metadata) {
+            Ok(TransactionPayload::EntryFunction(entry_function)) => entry_function,
+            Ok(_) => unreachable!("process payloads are entry functions"),
+            Err(err) => {
+                error!(error = %err, message_id = ?message.id(), "Failed to build Aptos process payload");
+                return vec![];
+            }
+        };
+        bcs::to_bytes(&entry_function).unwrap_or_else(|err| {
+            error!(error = %err, message_id = ?message.id(), "Failed to encode Aptos process payload");
+            vec![]
+        })
     }
 }
 
+/// Parses the response of the `mailbox::recipient_ism` view.
+/// The Move side returns @0x0 for recipients that did not declare an ISM.
+fn parse_recipient_ism(view_response: &[serde_json::Value]) -> ChainResult<Option<H256>> {
+    let ism = utils::decode_view_address("mailbox::recipient_ism", view_response, 0)?;
+    Ok((!ism.is_zero()).then_some(ism))
+}
+
 /// Struct that retrieves event data for a Aptos Mailbox contract
 #[derive(Debug)]
 pub struct AptosMailboxIndexer {
     mailbox: AptosMailbox,
     aptos_client: AptosClient,
+    graphql_client: Option<AptosGraphqlClient>,
+    index_mode: IndexMode,
     package_address: AccountAddress,
 }
 
 impl AptosMailboxIndexer {
-    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> ChainResult<Self> {
-        let aptos_client = AptosClient::new(conf.url.to_string());
-        let package_address =
-            AccountAddress::from_bytes(<[u8; 32]>::from(locator.address)).unwrap();
+    /// Create a new Aptos mailbox indexer, fetching ranges of blocks or of
+    /// event sequence numbers depending on `index_mode`
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        index_mode: IndexMode,
+    ) -> ChainResult<Self> {
+        let aptos_client = AptosClient::new(conf, &locator.domain);
+        let package_address = AccountAddress::new(locator.address.0);
         let mailbox = AptosMailbox::new(conf, locator, None)?;
 
         Ok(Self {
             mailbox,
             aptos_client,
+            graphql_client: conf.indexer_backend.graphql_client(),
+            index_mode,
             package_address,
         })
     }
 
+    fn mailbox_state_tag(&self) -> String {
+        format!(
+            "{}::mailbox::MailBoxState",
+            self.package_address.to_hex_literal()
+        )
+    }
+
+    fn event_handle(&self, field_name: &'static str, event_name: &str) -> AptosEventHandle {
+        AptosEventHandle {
+            account_address: self.package_address,
+            struct_tag: self.mailbox_state_tag(),
+            field_name,
+            event_type: format!(
+                "{}::mailbox::{event_name}",
+                self.package_address.to_hex_literal()
+            ),
+        }
+    }
+
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        let chain_state = self
-            .aptos_client
-            .get_ledger_information()
-            .await
-            .map_err(ChainCommunicationError::from_other)
-            .unwrap()
-            .into_inner();
-        Ok(chain_state.block_height as u32)
+        get_indexed_tip(&self.aptos_client, self.graphql_client.as_ref()).await
     }
 }
 
@@ -323,14 +476,11 @@ impl Indexer<HyperlaneMessage> for AptosMailboxIndexer {
             ?range,
             "AptosMailboxIndexer::Indexer<HyperlaneMessage>::fetch_logs"
         );
-        get_filtered_events::<HyperlaneMessage, DispatchEventData>(
+        fetch_events_in_range::<HyperlaneMessage, DispatchEventData>(
             &self.aptos_client,
-            self.package_address,
-            &format!(
-                "{}::mailbox::MailBoxState",
-                self.package_address.to_hex_literal()
-            ),
-            "dispatch_events",
+            self.graphql_client.as_ref(),
+            self.index_mode,
+            &self.event_handle("dispatch_events", "DispatchEvent"),
             range,
         )
         .await
@@ -348,14 +498,11 @@ impl Indexer<H256> for AptosMailboxIndexer {
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
         debug!(?range, "AptosMailboxIndexer::Indexer<H256>::fetch_logs");
-        get_filtered_events::<H256, MsgProcessEventData>(
+        fetch_events_in_range::<H256, MsgProcessEventData>(
             &self.aptos_client,
-            self.package_address,
-            &format!(
-                "{}::mailbox::MailBoxState",
-                self.package_address.to_hex_literal()
-            ),
-            "process_events",
+            self.graphql_client.as_ref(),
+            self.index_mode,
+            &self.event_handle("process_events", "ProcessEvent"),
             range,
         )
         .await
@@ -379,6 +526,111 @@ impl SequenceAwareIndexer<HyperlaneMessage> for AptosMailboxIndexer {
 impl SequenceAwareIndexer<H256> for AptosMailboxIndexer {
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
         let tip = Indexer::<H256>::get_finalized_block_number(self as _).await?;
-        Ok((Some(1), tip))
+        let count = get_event_count(
+            &self.aptos_client,
+            self.package_address,
+            &self.mailbox_state_tag(),
+            "process_events",
+        )
+        .await?
+        .try_into()
+        .map_err(ChainCommunicationError::from_other)?;
+        Ok((Some(count), tip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aptos_sdk::types::account_address::AccountAddress;
+    use aptos_sdk::types::transaction::EntryFunction;
+    use hyperlane_core::{
+        ContractLocator, HyperlaneDomain, HyperlaneMessage, Mailbox, RawHyperlaneMessage, H256,
+    };
+
+    use super::{parse_recipient_ism, AptosMailbox};
+    use crate::ConnectionConf;
+
+    #[test]
+    fn test_parse_recipient_ism_declared() {
+        let response = vec![serde_json::json!(
+            "0x6bbae7820a27ff21f28ba5a4b64c8b746cdd95e2b3264a686dd15651ef90a2a1"
+        )];
+        let expected: H256 = "0x6bbae7820a27ff21f28ba5a4b64c8b746cdd95e2b3264a686dd15651ef90a2a1"
+            .parse()
+            .unwrap();
+        assert_eq!(parse_recipient_ism(&response).unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn test_parse_recipient_ism_falls_back_to_default() {
+        let response = vec![serde_json::json!("0x0")];
+        assert_eq!(parse_recipient_ism(&response).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_recipient_ism_malformed() {
+        assert!(parse_recipient_ism(&[]).is_err());
+        assert!(parse_recipient_ism(&[serde_json::json!(true)]).is_err());
+    }
+
+    fn offline_mailbox() -> AptosMailbox {
+        let conf = ConnectionConf {
+            urls: vec!["http://127.0.0.1:8080/v1".parse().unwrap()],
+            transaction_overrides: Default::default(),
+            external_submission: None,
+            client_metrics: Default::default(),
+            indexer_backend: Default::default(),
+        };
+        let domain = HyperlaneDomain::new_test_domain("aptostest");
+        AptosMailbox::new(
+            &conf,
+            ContractLocator {
+                domain: &domain,
+                address: H256::from_low_u64_be(1),
+            },
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_process_calldata_encodes_handle_message() {
+        let mailbox = offline_mailbox();
+        let recipient = H256::from_low_u64_be(0xb0b);
+        let message = HyperlaneMessage {
+            recipient,
+            body: b"hello".to_vec(),
+            ..Default::default()
+        };
+        let recipient = AccountAddress::new(recipient.0);
+        mailbox
+            .module_names
+            .lock()
+            .unwrap()
+            .insert(recipient, b"hello_world".to_vec());
+
+        let calldata = mailbox.process_calldata(&message, b"metadata");
+        let entry_function: EntryFunction = bcs::from_bytes(&calldata).unwrap();
+        assert_eq!(entry_function.module().address(), &recipient);
+        assert_eq!(entry_function.module().name().as_str(), "hello_world");
+        assert_eq!(entry_function.function().as_str(), "handle_message");
+        assert_eq!(
+            entry_function.args(),
+            &[
+                bcs::to_bytes(&RawHyperlaneMessage::from(&message)).unwrap(),
+                bcs::to_bytes(&b"metadata".to_vec()).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_process_calldata_of_unprepared_message_is_empty() {
+        let mailbox = offline_mailbox();
+        let message = HyperlaneMessage {
+            recipient: H256::from_low_u64_be(0xb0b),
+            ..Default::default()
+        };
+        // The recipient's module was never looked up
+        assert!(mailbox.process_calldata(&message, b"metadata").is_empty());
     }
 }
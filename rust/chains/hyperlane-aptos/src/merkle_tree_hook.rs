@@ -14,25 +14,26 @@ use hyperlane_core::{
 use hyperlane_core::{HyperlaneMessage, Indexed};
 use std::num::NonZeroU64;
 use std::ops::RangeInclusive;
-use std::str::FromStr;
 use tracing::{debug, instrument};
 
 #[async_trait]
 impl MerkleTreeHook for AptosMailbox {
     #[instrument(err, ret, skip(self))]
-    async fn tree(&self, _lag: Option<NonZeroU64>) -> ChainResult<IncrementalMerkle> {
-        let view_response = utils::send_view_request(
+    async fn tree(&self, lag: Option<NonZeroU64>) -> ChainResult<IncrementalMerkle> {
+        let ledger_version = utils::get_ledger_version_for_lag(&self.aptos_client, lag).await?;
+        let view_response = utils::send_view_request_at_version(
             &self.aptos_client,
             self.package_address.to_hex_literal(),
             "mailbox".to_string(),
             "outbox_get_tree".to_string(),
             vec![],
             vec![],
+            ledger_version,
         )
         .await?;
-        let view_result =
-            serde_json::from_str::<MoveMerkleTree>(&view_response[0].to_string()).unwrap();
-        Ok(view_result.into())
+        let view_result: MoveMerkleTree =
+            utils::decode_view_value("mailbox::outbox_get_tree", &view_response, 0)?;
+        Ok(view_result.try_into()?)
     }
 
     #[instrument(err, ret, skip(self))]
@@ -51,7 +52,7 @@ impl MerkleTreeHook for AptosMailbox {
         })?;
 
         let checkpoint = Checkpoint {
-            merkle_tree_hook_address: H256::from_str(&self.package_address.to_hex()).unwrap(),
+            merkle_tree_hook_address: H256(self.package_address.into_bytes()),
             mailbox_domain: self.domain.id(),
             root,
             index,
@@ -60,8 +61,8 @@ impl MerkleTreeHook for AptosMailbox {
     }
 
     #[instrument(err, ret, skip(self))]
-    async fn count(&self, _maybe_lag: Option<NonZeroU64>) -> ChainResult<u32> {
-        let tree = self.tree(_maybe_lag).await?;
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        let tree = self.tree(lag).await?;
         tree.count()
             .try_into()
             .map_err(ChainCommunicationError::from_other)
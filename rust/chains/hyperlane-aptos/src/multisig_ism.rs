@@ -3,13 +3,13 @@
 
 use async_trait::async_trait;
 
+use crate::signers::AptosSigner;
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
     HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, MultisigIsm, RawHyperlaneMessage, H256,
 };
-use solana_sdk::signature::Keypair;
 
-use crate::{AptosHpProvider, ConnectionConf};
+use crate::{AptosHpProvider, ConnectionConf, LazyIsmLocation};
 use serde::{Deserialize, Serialize};
 
 use crate::utils;
@@ -36,32 +36,35 @@ use std::str::FromStr;
 /// A reference to a MultisigIsm contract on some Aptos chain
 #[derive(Debug)]
 pub struct AptosMultisigISM {
-    payer: Option<Keypair>,
+    payer: Option<AptosSigner>,
     domain: HyperlaneDomain,
 
     aptos_client: AptosClient,
-    package_address: AccountAddress,
+    location: LazyIsmLocation,
 }
 
 impl AptosMultisigISM {
     /// Create a new Aptos MultisigIsm.
-    pub fn new(conf: &ConnectionConf, locator: ContractLocator, payer: Option<Keypair>) -> Self {
-        let package_address =
-            AccountAddress::from_bytes(<[u8; 32]>::from(locator.address)).unwrap();
-        let aptos_client = AptosClient::new(conf.url.to_string());
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        payer: Option<AptosSigner>,
+    ) -> Self {
+        let location = LazyIsmLocation::new(AccountAddress::new(locator.address.0));
+        let aptos_client = AptosClient::new(conf, &locator.domain);
 
         Self {
             payer,
             domain: locator.domain.clone(),
             aptos_client,
-            package_address,
+            location,
         }
     }
 }
 
 impl HyperlaneContract for AptosMultisigISM {
     fn address(&self) -> H256 {
-        self.package_address.into_bytes().into()
+        self.location.address().into_bytes().into()
     }
 }
 
@@ -72,7 +75,7 @@ impl HyperlaneChain for AptosMultisigISM {
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
         Box::new(AptosHpProvider::new(
             self.domain.clone(),
-            self.aptos_client.path_prefix_string(),
+            self.aptos_client.clone(),
         ))
     }
 }
@@ -84,25 +87,20 @@ impl MultisigIsm for AptosMultisigISM {
         &self,
         message: &HyperlaneMessage,
     ) -> ChainResult<(Vec<H256>, u8)> {
-        let view_response = utils::send_view_request(
-            &self.aptos_client,
-            self.package_address.to_hex_literal(),
-            "multisig_ism".to_string(),
-            "validators_and_threshold".to_string(),
-            vec![],
-            vec![serde_json::json!(message.origin)],
-        )
-        .await?;
-        let validators: Vec<H256> =
-            serde_json::from_str::<Vec<String>>(&view_response[0].to_string())
-                .unwrap()
-                .iter()
-                .map(|v| utils::convert_hex_string_to_h256(v).unwrap())
-                .collect();
-        let threshold = serde_json::from_str::<String>(&view_response[1].to_string())
-            .unwrap()
-            .parse::<u8>()
-            .unwrap();
+        let view_response = self
+            .location
+            .get(&self.aptos_client)
+            .await?
+            .view(
+                &self.aptos_client,
+                "multisig_ism",
+                "validators_and_threshold",
+                vec![serde_json::json!(message.origin)],
+            )
+            .await?;
+        let function = "multisig_ism::validators_and_threshold";
+        let validators = utils::decode_view_addresses(function, &view_response, 0)?;
+        let threshold = utils::decode_view_uint(function, &view_response, 1)?;
         Ok((validators, threshold))
     }
 }
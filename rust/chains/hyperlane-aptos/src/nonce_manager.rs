@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use aptos_sdk::{
+    transaction_builder::TransactionBuilder,
+    types::{account_address::AccountAddress, transaction::SignedTransaction},
+};
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use once_cell::sync::Lazy;
+use tracing::debug;
+
+use crate::signers::AptosSigner;
+use crate::AptosClient;
+
+/// Nonce managers shared by every contract signing with the same key on the same node
+static NONCE_MANAGERS: Lazy<Mutex<HashMap<(String, AccountAddress), Arc<AptosNonceManager>>>> =
+    Lazy::new(Default::default);
+
+/// Hands out sequence numbers for an Aptos signer.
+/// The account's sequence number is fetched once and then incremented
+/// locally, so several transactions from the same signer can be in flight
+/// at once. It is fetched again after [`AptosNonceManager::resync`].
+#[derive(Debug)]
+pub struct AptosNonceManager {
+    signer: AptosSigner,
+    next_sequence_number: tokio::sync::Mutex<Option<u64>>,
+}
+
+impl AptosNonceManager {
+    /// Get the nonce manager shared by all users of `signer` on the node behind `aptos_client`
+    pub fn for_signer(aptos_client: &AptosClient, signer: &AptosSigner) -> Arc<Self> {
+        NONCE_MANAGERS
+            .lock()
+            .unwrap()
+            .entry((
+                aptos_client.cache_key().to_string(),
+                signer.account_address(),
+            ))
+            .or_insert_with(|| {
+                Arc::new(Self {
+                    signer: signer.clone(),
+                    next_sequence_number: Default::default(),
+                })
+            })
+            .clone()
+    }
+
+    /// The signer whose sequence numbers are managed
+    pub fn signer(&self) -> &AptosSigner {
+        &self.signer
+    }
+
+    /// Sign the transaction built by `builder` with the next sequence number
+    pub async fn sign(
+        &self,
+        aptos_client: &AptosClient,
+        builder: TransactionBuilder,
+    ) -> ChainResult<SignedTransaction> {
+        let address = self.signer.account_address();
+        let mut next_sequence_number = self.next_sequence_number.lock().await;
+        let sequence_number = match *next_sequence_number {
+            Some(sequence_number) => sequence_number,
+            None => {
+                let sequence_number = aptos_client
+                    .get_account(address)
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?
+                    .into_inner()
+                    .sequence_number;
+                debug!(
+                    address = address.to_hex_literal(),
+                    sequence_number, "Synced Aptos signer sequence number"
+                );
+                sequence_number
+            }
+        };
+        let raw_tx = builder
+            .sender(address)
+            .sequence_number(sequence_number)
+            .build();
+        let signed_tx = self.signer.sign_transaction(raw_tx).await?;
+        *next_sequence_number = Some(sequence_number + 1);
+        Ok(signed_tx)
+    }
+
+    /// Forget the local sequence number so the next transaction refetches it from the node.
+    /// Called after a transaction was rejected or expired, which leaves a gap.
+    pub async fn resync(&self) {
+        self.next_sequence_number.lock().await.take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperlane_core::{HyperlaneDomain, KnownHyperlaneDomain};
+
+    use super::*;
+    use crate::ConnectionConf;
+
+    fn client(url: &str) -> AptosClient {
+        let conf = ConnectionConf {
+            urls: vec![url.parse().unwrap()],
+            transaction_overrides: Default::default(),
+            external_submission: None,
+            client_metrics: Default::default(),
+            indexer_backend: Default::default(),
+        };
+        AptosClient::new(
+            &conf,
+            &HyperlaneDomain::Known(KnownHyperlaneDomain::AptosLocalnet1),
+        )
+    }
+
+    #[test]
+    fn test_nonce_manager_shared_per_signer_and_node() {
+        let signer = AptosSigner::from_encoded_key(&hex::encode([1u8; 32])).unwrap();
+        let other_signer = AptosSigner::from_encoded_key(&hex::encode([2u8; 32])).unwrap();
+        let client = client("http://127.0.0.1:8080/v1");
+        let other_client = client("http://127.0.0.1:8081/v1");
+
+        let manager = AptosNonceManager::for_signer(&client, &signer);
+        assert!(Arc::ptr_eq(
+            &manager,
+            &AptosNonceManager::for_signer(&client, &signer)
+        ));
+        assert!(!Arc::ptr_eq(
+            &manager,
+            &AptosNonceManager::for_signer(&other_client, &signer)
+        ));
+        assert!(!Arc::ptr_eq(
+            &manager,
+            &AptosNonceManager::for_signer(&client, &other_signer)
+        ));
+    }
+}
@@ -1,15 +1,15 @@
 use aptos_sdk::crypto::HashValue;
-use aptos_sdk::rest_client::aptos_api_types::Transaction;
+use aptos_sdk::rest_client::{aptos_api_types::Transaction, error::RestError};
 
 use async_trait::async_trait;
 use tracing::warn;
 
 use hyperlane_core::{
     BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
-    HyperlaneProvider, TxnInfo, TxnReceiptInfo, H256, U256,
+    HyperlaneProvider, HyperlaneProviderError, TxnInfo, TxnReceiptInfo, H256, U256,
 };
 
-use crate::{convert_hex_string_to_h256, AptosClient};
+use crate::{AptosClient, AptosError, CachedBlock};
 
 /// A wrapper around a Aptos provider to get generic blockchain information.
 #[derive(Debug)]
@@ -20,8 +20,7 @@ pub struct AptosHpProvider {
 
 impl AptosHpProvider {
     /// Create a new Aptos provider.
-    pub fn new(domain: HyperlaneDomain, rest_url: String) -> Self {
-        let aptos_client = AptosClient::new(rest_url);
+    pub fn new(domain: HyperlaneDomain, aptos_client: AptosClient) -> Self {
         AptosHpProvider {
             domain,
             aptos_client,
@@ -37,36 +36,67 @@ impl HyperlaneChain for AptosHpProvider {
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
         Box::new(AptosHpProvider::new(
             self.domain.clone(),
-            self.aptos_client.path_prefix_string(),
+            self.aptos_client.clone(),
         ))
     }
 }
 
 #[async_trait]
 impl HyperlaneProvider for AptosHpProvider {
-    async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
-        // getting block by hash is not supported in Aptos
-        todo!() // FIXME
+    async fn get_block_by_hash(&self, hash: &H256) -> ChainResult<BlockInfo> {
+        // Aptos nodes cannot look a block up by hash, so blocks not seen while
+        // indexing are looked up by height in the indexer, if there is one
+        if let Some(block) = self.aptos_client.block_cache().get_by_hash(hash) {
+            return Ok(to_block_info(&block));
+        }
+
+        let height = match self.aptos_client.graphql_client() {
+            Some(graphql_client) => graphql_client.block_height_by_hash(hash).await?,
+            None => None,
+        };
+        let Some(height) = height else {
+            warn!(
+                ?hash,
+                "Block not found by hash, it wasn't seen while indexing nor known to the indexer"
+            );
+            return Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into());
+        };
+        // caches the block, so later lookups by hash find it
+        let block = self.aptos_client.get_cached_block_by_height(height).await?;
+        if block.hash != *hash {
+            warn!(?hash, ?block, "Indexer returned a block with another hash");
+            return Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into());
+        }
+        Ok(to_block_info(&block))
     }
 
     async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
-        let transaction: Transaction = self
+        let transaction: Transaction = match self
             .aptos_client
-            .get_transaction_by_hash(HashValue::from_slice(hash.as_bytes()).unwrap())
+            .get_transaction_by_hash(HashValue::new(hash.0))
             .await
-            .unwrap()
-            .into_inner();
+        {
+            Ok(response) => response.into_inner(),
+            Err(RestError::Api(response)) if response.status_code.as_u16() == 404 => {
+                return Err(HyperlaneProviderError::CouldNotFindObjectByHash(*hash).into());
+            }
+            Err(err) => return Err(AptosError::from(err).into()),
+        };
 
         let mut gas_price = None;
         let mut gas_limit = U256::zero();
         let mut sender = H256::zero();
 
-        let tx_info = transaction.transaction_info().unwrap().clone();
+        // Pending transactions have no execution info yet
+        let tx_info = transaction
+            .transaction_info()
+            .map_err(|e| AptosError::InvalidResponse(e.to_string()))?
+            .clone();
 
         if let Transaction::UserTransaction(tx) = transaction {
             gas_price = Some(U256::from(tx.request.gas_unit_price.0));
             gas_limit = U256::from(tx.request.max_gas_amount.0);
-            sender = convert_hex_string_to_h256(&tx.request.sender.to_string()).unwrap();
+            sender = H256(tx.request.sender.inner().into_bytes());
         }
 
         Ok(TxnInfo {
@@ -110,36 +140,24 @@ impl HyperlaneProvider for AptosHpProvider {
             .aptos_client
             .get_ledger_information()
             .await
-            .map_err(ChainCommunicationError::from_other)?;
-        let mut version = state.state().version;
-        let mut block_depth = 0;
-        while version > 0 && block_depth > 1000 {
-            let block = self
-                .aptos_client
-                .get_block_by_version(version, false)
-                .await
-                .unwrap()
-                .into_inner();
-            if block.block_height.0 != state.state().block_height {
-                version -= 1;
-                block_depth += 1;
-                continue;
-            }
-            let timestamp = block.block_timestamp.0;
-            return Ok(Some(ChainInfo {
-                latest_block: BlockInfo {
-                    hash: convert_hex_string_to_h256(&block.block_hash.to_string()).unwrap(),
-                    timestamp, // microseconds
-                    number: state.state().block_height,
-                },
-                min_gas_price: None,
-            }));
-        }
-        warn!(
-            "No block found for the current ledger version {} and block_height {}",
-            state.state().version,
-            state.state().block_height
-        );
-        Ok(None)
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+        let block = self
+            .aptos_client
+            .get_cached_block_by_height(state.block_height)
+            .await?;
+        Ok(Some(ChainInfo {
+            latest_block: to_block_info(&block),
+            min_gas_price: None,
+        }))
+    }
+}
+
+fn to_block_info(block: &CachedBlock) -> BlockInfo {
+    BlockInfo {
+        hash: block.hash,
+        // aptos timestamps are in microseconds
+        timestamp: block.timestamp / 1_000_000,
+        number: block.height,
     }
 }
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use hyperlane_core::{
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
+    HyperlaneMessage, HyperlaneProvider, RawHyperlaneMessage, RoutingIsm, H256,
+};
+
+use crate::utils;
+use crate::{AptosClient, AptosHpProvider, ConnectionConf, LazyIsmLocation};
+
+use aptos_sdk::types::account_address::AccountAddress;
+
+/// A reference to a domain routing ISM on some Aptos chain
+#[derive(Debug)]
+pub struct AptosRoutingIsm {
+    domain: HyperlaneDomain,
+    aptos_client: AptosClient,
+    location: LazyIsmLocation,
+}
+
+impl AptosRoutingIsm {
+    /// Create a new Aptos RoutingIsm.
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator) -> Self {
+        let location = LazyIsmLocation::new(AccountAddress::new(locator.address.0));
+        let aptos_client = AptosClient::new(conf, &locator.domain);
+
+        Self {
+            domain: locator.domain.clone(),
+            aptos_client,
+            location,
+        }
+    }
+}
+
+impl HyperlaneContract for AptosRoutingIsm {
+    fn address(&self) -> H256 {
+        self.location.address().into_bytes().into()
+    }
+}
+
+impl HyperlaneChain for AptosRoutingIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(AptosHpProvider::new(
+            self.domain.clone(),
+            self.aptos_client.clone(),
+        ))
+    }
+}
+
+#[async_trait]
+impl RoutingIsm for AptosRoutingIsm {
+    #[instrument(err, ret, skip(self))]
+    async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
+        let view_response = self
+            .location
+            .get(&self.aptos_client)
+            .await?
+            .view(
+                &self.aptos_client,
+                "domain_routing_ism",
+                "route",
+                vec![serde_json::json!(hex::encode(RawHyperlaneMessage::from(
+                    message
+                )))],
+            )
+            .await?;
+
+        Ok(utils::decode_view_address(
+            "domain_routing_ism::route",
+            &view_response,
+            0,
+        )?)
+    }
+}
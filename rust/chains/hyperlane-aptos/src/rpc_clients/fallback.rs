@@ -0,0 +1,30 @@
+use std::{
+    fmt::{Debug, Formatter},
+    ops::Deref,
+};
+
+use derive_new::new;
+use hyperlane_core::rpc_clients::FallbackProvider;
+
+/// Wrapper of `FallbackProvider` for use in `hyperlane-aptos`
+#[derive(new, Clone)]
+pub struct AptosFallbackProvider<T> {
+    fallback_provider: FallbackProvider<T, T>,
+}
+
+impl<T> Deref for AptosFallbackProvider<T> {
+    type Target = FallbackProvider<T, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.fallback_provider
+    }
+}
+
+impl<C> Debug for AptosFallbackProvider<C>
+where
+    C: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fallback_provider.fmt(f)
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use prometheus::{CounterVec, IntCounterVec};
+
+/// Expected label names for the request metrics.
+pub const APTOS_REQUEST_LABELS: &[&str] = &["provider_node", "chain", "method", "status"];
+/// Help string for the request count metric.
+pub const APTOS_REQUEST_COUNT_HELP: &str = "Total number of requests made to Aptos REST endpoints";
+/// Help string for the request duration metric.
+pub const APTOS_REQUEST_DURATION_SECONDS_HELP: &str =
+    "Total number of seconds spent making requests to Aptos REST endpoints";
+
+/// Metrics of the requests made to each Aptos REST endpoint.
+/// Nothing is recorded for metrics left unset.
+#[derive(Debug, Clone, Default)]
+pub struct AptosClientMetrics {
+    /// Total number of requests made to an endpoint.
+    /// - `provider_node`: host of the endpoint, e.g. `fullnode.mainnet.aptoslabs.com`.
+    /// - `chain`: chain name of the chain the request was made on.
+    /// - `method`: REST client method, e.g. `view`.
+    /// - `status`: `success` or `failure` depending on the response.
+    pub request_count: Option<IntCounterVec>,
+    /// Total number of seconds spent making requests to an endpoint,
+    /// with the same labels as `request_count`.
+    pub request_duration_seconds: Option<CounterVec>,
+}
+
+impl AptosClientMetrics {
+    /// Record a request to `provider_node` that took `duration`
+    pub fn record_request(
+        &self,
+        provider_node: &str,
+        chain: &str,
+        method: &str,
+        success: bool,
+        duration: Duration,
+    ) {
+        let status = if success { "success" } else { "failure" };
+        let labels = [provider_node, chain, method, status];
+        if let Some(request_count) = &self.request_count {
+            request_count.with_label_values(&labels).inc();
+        }
+        if let Some(request_duration_seconds) = &self.request_duration_seconds {
+            request_duration_seconds
+                .with_label_values(&labels)
+                .inc_by(duration.as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::{opts, CounterVec, IntCounterVec};
+
+    use super::*;
+
+    #[test]
+    fn test_record_request() {
+        let metrics = AptosClientMetrics {
+            request_count: Some(
+                IntCounterVec::new(opts!("request_count", "help"), APTOS_REQUEST_LABELS).unwrap(),
+            ),
+            request_duration_seconds: Some(
+                CounterVec::new(opts!("request_duration", "help"), APTOS_REQUEST_LABELS).unwrap(),
+            ),
+        };
+        metrics.record_request("node", "aptostest", "view", true, Duration::from_secs(2));
+        metrics.record_request("node", "aptostest", "view", false, Duration::from_secs(1));
+        metrics.record_request("node", "aptostest", "view", true, Duration::from_secs(1));
+
+        let request_count = metrics.request_count.as_ref().unwrap();
+        let duration = metrics.request_duration_seconds.as_ref().unwrap();
+        let success = ["node", "aptostest", "view", "success"];
+        assert_eq!(request_count.with_label_values(&success).get(), 2);
+        assert_eq!(duration.with_label_values(&success).get(), 3.0);
+        assert_eq!(
+            request_count
+                .with_label_values(&["node", "aptostest", "view", "failure"])
+                .get(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unset_metrics_record_nothing() {
+        AptosClientMetrics::default().record_request(
+            "node",
+            "aptostest",
+            "view",
+            true,
+            Duration::from_secs(1),
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub use self::fallback::*;
+pub use self::metrics::*;
+
+mod fallback;
+mod metrics;
@@ -1,18 +1,41 @@
-use aptos_sdk::crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
-use aptos_sdk::crypto::{PrivateKey, ValidCryptoMaterialStringExt};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use aptos_sdk::crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
+use aptos_sdk::crypto::{signing_message, SigningKey};
 use aptos_sdk::types::account_address::AccountAddress;
 use aptos_sdk::types::transaction::authenticator::AuthenticationKey;
+use aptos_sdk::types::transaction::{RawTransaction, SignedTransaction};
+use async_trait::async_trait;
 use ed25519_dalek::SecretKey;
 use hyperlane_core::ChainCommunicationError;
 use hyperlane_core::ChainResult;
-use solana_sdk::msg;
-use solana_sdk::signer::keypair::Keypair;
 
-#[derive(Debug)]
+/// Prefix of private keys in the format used by the Aptos CLI and wallets (AIP-80)
+const ED25519_PRIVATE_KEY_PREFIX: &str = "ed25519-priv-";
+
+/// A signer holding an Ed25519 key outside of the agent, e.g. in a KMS
+#[async_trait]
+pub trait AptosRemoteSigner: Send + Sync + std::fmt::Debug {
+    /// The Ed25519 public key of the remote key
+    async fn public_key(&self) -> ChainResult<[u8; 32]>;
+
+    /// Sign `message` with the remote key, returning the Ed25519 signature
+    async fn sign(&self, message: &[u8]) -> ChainResult<[u8; 64]>;
+}
+
+#[derive(Debug, Clone)]
+enum AptosSigningKey {
+    Local(Arc<Ed25519PrivateKey>),
+    Remote(Arc<dyn AptosRemoteSigner>),
+}
+
+#[derive(Debug, Clone)]
 /// Signer for aptos chain
 pub struct AptosSigner {
-    /// Key pair
-    pub keypair: Keypair,
+    key: AptosSigningKey,
+    public_key: Ed25519PublicKey,
     /// precomputed address, use aptos_cli code to derive address from keypair
     pub address: String,
 }
@@ -21,34 +44,99 @@ impl AptosSigner {
     /// create new signer
     ///
     /// # Arguments
-    /// * `private_key` - private key for signer
-    /// * `prefix` - prefix for signer address
+    /// * `secret` - ed25519 secret key for signer
     pub fn new(secret: SecretKey) -> ChainResult<Self> {
-        // TODO: how to simplify this?
-        // secret -> ed25519_dalek::Keypair -> solana_sdk::signer::keypair::Keypair
-        let keypair = Keypair::from_bytes(&ed25519_dalek::Keypair::from(secret).to_bytes())
-            .map_err(|err| {
-                msg!("{}", err);
-                ChainCommunicationError::from_other_str("Cannot create keypair")
+        let private_key = Ed25519PrivateKey::try_from(secret.as_bytes().as_ref())
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(Self::from_private_key(private_key))
+    }
+
+    /// Create a signer from an Aptos Ed25519 private key
+    pub fn from_private_key(private_key: Ed25519PrivateKey) -> Self {
+        let public_key = Ed25519PublicKey::from(&private_key);
+        Self::with_key(AptosSigningKey::Local(Arc::new(private_key)), public_key)
+    }
+
+    /// Create a signer from a private key string, either hex or
+    /// `ed25519-priv-0x...` as exported by the Aptos CLI
+    pub fn from_encoded_key(key: &str) -> ChainResult<Self> {
+        Ok(Self::from_private_key(parse_private_key(key)?))
+    }
+
+    /// Create a signer from a profile of an Aptos CLI config file, usually `.aptos/config.yaml`
+    pub fn from_cli_profile(path: &Path, profile: &str) -> ChainResult<Self> {
+        let config =
+            std::fs::read_to_string(path).map_err(|e| ChainCommunicationError::ParseError {
+                msg: format!("Cannot read Aptos CLI config {}: {e}", path.display()),
             })?;
+        Self::from_encoded_key(&parse_cli_profile_key(&config, profile)?)
+    }
 
-        let binding = hex::encode(keypair.secret().to_bytes());
-        let private_key_hex = binding.as_str();
-        let private_key = match Ed25519PrivateKey::from_encoded_string(private_key_hex) {
-            Ok(private_key) => private_key,
-            Err(_) => {
-                return Err(ChainCommunicationError::from_other_str(
-                    "Cannot create private key",
-                ));
+    /// Create a signer whose key is held by `remote`
+    pub async fn from_remote(remote: Arc<dyn AptosRemoteSigner>) -> ChainResult<Self> {
+        let public_key = Ed25519PublicKey::try_from(remote.public_key().await?.as_ref())
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(Self::with_key(AptosSigningKey::Remote(remote), public_key))
+    }
+
+    fn with_key(key: AptosSigningKey, public_key: Ed25519PublicKey) -> Self {
+        let address = account_address_from_public_key(&public_key);
+        Self {
+            key,
+            public_key,
+            address: address.to_string(),
+        }
+    }
+
+    /// Account address of the signer
+    pub fn account_address(&self) -> AccountAddress {
+        account_address_from_public_key(&self.public_key)
+    }
+
+    /// Public key of the signer
+    pub fn public_key(&self) -> &Ed25519PublicKey {
+        &self.public_key
+    }
+
+    /// Sign a raw transaction
+    pub async fn sign_transaction(&self, raw_tx: RawTransaction) -> ChainResult<SignedTransaction> {
+        let message = signing_message(&raw_tx).map_err(ChainCommunicationError::from_other)?;
+        let signature = match &self.key {
+            AptosSigningKey::Local(private_key) => private_key.sign_arbitrary_message(&message),
+            AptosSigningKey::Remote(remote) => {
+                Ed25519Signature::try_from(remote.sign(&message).await?.as_ref())
+                    .map_err(ChainCommunicationError::from_other)?
             }
         };
+        Ok(SignedTransaction::new(
+            raw_tx,
+            self.public_key.clone(),
+            signature,
+        ))
+    }
+}
 
-        let public_key = private_key.public_key();
-        let account_address = account_address_from_public_key(&public_key);
-        Ok(Self {
-            keypair,
-            address: account_address.to_string(),
-        })
+/// A remote signer backed by a key in memory, for tests and local development
+#[derive(Debug)]
+pub struct MockRemoteSigner {
+    private_key: Ed25519PrivateKey,
+}
+
+impl MockRemoteSigner {
+    /// Create a mock remote signer holding `private_key`
+    pub fn new(private_key: Ed25519PrivateKey) -> Self {
+        Self { private_key }
+    }
+}
+
+#[async_trait]
+impl AptosRemoteSigner for MockRemoteSigner {
+    async fn public_key(&self) -> ChainResult<[u8; 32]> {
+        Ok(Ed25519PublicKey::from(&self.private_key).to_bytes())
+    }
+
+    async fn sign(&self, message: &[u8]) -> ChainResult<[u8; 64]> {
+        Ok(self.private_key.sign_arbitrary_message(message).to_bytes())
     }
 }
 
@@ -59,28 +147,129 @@ pub fn account_address_from_public_key(public_key: &Ed25519PublicKey) -> Account
     AccountAddress::new(*auth_key.account_address())
 }
 
+/// Parse a hex private key, optionally prefixed with `ed25519-priv-`
+fn parse_private_key(key: &str) -> ChainResult<Ed25519PrivateKey> {
+    let key = key.trim();
+    let key = key.strip_prefix(ED25519_PRIVATE_KEY_PREFIX).unwrap_or(key);
+    let bytes = hex::decode(key.trim_start_matches("0x")).map_err(|e| {
+        ChainCommunicationError::ParseError {
+            msg: format!("Invalid Aptos private key: {e}"),
+        }
+    })?;
+    Ed25519PrivateKey::try_from(bytes.as_slice()).map_err(ChainCommunicationError::from_other)
+}
+
+#[derive(serde::Deserialize)]
+struct AptosCliConfig {
+    profiles: HashMap<String, AptosCliProfile>,
+}
+
+#[derive(serde::Deserialize)]
+struct AptosCliProfile {
+    private_key: Option<String>,
+}
+
+/// Get the private key of `profile` from the contents of an Aptos CLI config file
+fn parse_cli_profile_key(config: &str, profile: &str) -> ChainResult<String> {
+    let config: AptosCliConfig =
+        serde_yaml::from_str(config).map_err(|e| ChainCommunicationError::ParseError {
+            msg: format!("Invalid Aptos CLI config: {e}"),
+        })?;
+    config
+        .profiles
+        .get(profile)
+        .ok_or_else(|| ChainCommunicationError::ParseError {
+            msg: format!("Aptos CLI profile `{profile}` not found"),
+        })?
+        .private_key
+        .clone()
+        .ok_or_else(|| ChainCommunicationError::ParseError {
+            msg: format!("Aptos CLI profile `{profile}` has no private key"),
+        })
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use aptos_sdk::crypto::ed25519::Ed25519PrivateKey;
+    use aptos_sdk::crypto::Signature;
     use ed25519_dalek::SecretKey;
     use hyperlane_core::utils::hex_or_base58_to_h256;
     use hyperlane_core::Encode;
 
+    use super::{AptosRemoteSigner, AptosSigner, MockRemoteSigner};
+
+    const PRIVATE_KEY: &str = "0x8cb68128b8749613f8df7612e4efd281f8d70f6d195c53a14c27fc75980446c1";
+    const ACCOUNT_ADDRESS: &str =
+        "0x8b4376073a408ece791f4adc34a8afdde405bae071711dcbb95ca4e5d4f26c93";
+
     #[test]
     fn test_private_key_to_public_key() {
         // Replace with your actual private key in hex format
-        let private_key_str = "0x8cb68128b8749613f8df7612e4efd281f8d70f6d195c53a14c27fc75980446c1";
-        let private_key_bytes = hex_or_base58_to_h256(private_key_str).unwrap();
+        let private_key_bytes = hex_or_base58_to_h256(PRIVATE_KEY).unwrap();
 
         let secret = SecretKey::from_bytes(&private_key_bytes.to_vec())
             .expect("Invalid aptos ed25519 secret key");
 
-        let aptos_signer = match super::AptosSigner::new(secret) {
+        let aptos_signer = match AptosSigner::new(secret) {
             Ok(signer) => signer,
             Err(err) => panic!("Cannot create aptos signer, err: {:?}", err),
         };
 
-        let expected_account_address =
-            "0x8b4376073a408ece791f4adc34a8afdde405bae071711dcbb95ca4e5d4f26c93";
-        assert_eq!(aptos_signer.address, expected_account_address);
+        assert_eq!(aptos_signer.address, ACCOUNT_ADDRESS);
+    }
+
+    #[test]
+    fn test_encoded_key_formats() {
+        let prefixed =
+            AptosSigner::from_encoded_key(&format!("ed25519-priv-{PRIVATE_KEY}")).unwrap();
+        assert_eq!(prefixed.address, ACCOUNT_ADDRESS);
+
+        let plain = AptosSigner::from_encoded_key(PRIVATE_KEY.trim_start_matches("0x")).unwrap();
+        assert_eq!(plain.address, ACCOUNT_ADDRESS);
+
+        assert!(AptosSigner::from_encoded_key("ed25519-priv-0x1234").is_err());
+    }
+
+    #[test]
+    fn test_cli_profile_key() {
+        let config = format!(
+            r#"---
+profiles:
+  default:
+    network: Testnet
+    private_key: "ed25519-priv-{PRIVATE_KEY}"
+    account: 8b4376073a408ece791f4adc34a8afdde405bae071711dcbb95ca4e5d4f26c93
+    rest_url: "https://fullnode.testnet.aptoslabs.com"
+  ledger:
+    network: Testnet
+    account: 8b4376073a408ece791f4adc34a8afdde405bae071711dcbb95ca4e5d4f26c93
+"#
+        );
+        assert_eq!(
+            super::parse_cli_profile_key(&config, "default").unwrap(),
+            format!("ed25519-priv-{PRIVATE_KEY}")
+        );
+        assert!(super::parse_cli_profile_key(&config, "ledger").is_err());
+        assert!(super::parse_cli_profile_key(&config, "missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_matches_local_key() {
+        let private_key_bytes = hex::decode(PRIVATE_KEY.trim_start_matches("0x")).unwrap();
+        let remote = Arc::new(MockRemoteSigner::new(
+            Ed25519PrivateKey::try_from(private_key_bytes.as_slice()).unwrap(),
+        ));
+        let signer = AptosSigner::from_remote(remote.clone()).await.unwrap();
+        assert_eq!(signer.address, ACCOUNT_ADDRESS);
+
+        let message = b"hello aptos";
+        let signature = remote.sign(message).await.unwrap();
+        let signature =
+            aptos_sdk::crypto::ed25519::Ed25519Signature::try_from(signature.as_ref()).unwrap();
+        signature
+            .verify_arbitrary_msg(message, signer.public_key())
+            .unwrap();
     }
 }
@@ -1,5 +1,7 @@
 use url::Url;
 
+use crate::{AptosClientMetrics, AptosIndexerBackend, ExternalSubmission};
+
 use hyperlane_core::{
     config::{ConfigErrResultExt, ConfigPath, ConfigResult, FromRawConf},
     ChainCommunicationError,
@@ -8,8 +10,30 @@ use hyperlane_core::{
 /// Aptos connection configuration
 #[derive(Debug, Clone)]
 pub struct ConnectionConf {
-    /// Fully qualified string to connect to
-    pub url: Url,
+    /// Fully qualified REST urls to connect to, in order of priority
+    pub urls: Vec<Url>,
+    /// Transaction submission settings
+    pub transaction_overrides: TransactionOverrides,
+    /// If set, `process` payloads are handed to an external submitter instead of being signed
+    pub external_submission: Option<ExternalSubmission>,
+    /// Metrics recorded for every REST request, none by default
+    pub client_metrics: AptosClientMetrics,
+    /// Where indexers read events from, the REST API by default
+    pub indexer_backend: AptosIndexerBackend,
+}
+
+/// Overrides for transactions submitted to an Aptos chain
+#[derive(Debug, Clone, Default)]
+pub struct TransactionOverrides {
+    /// Gas unit price in octas. Defaults to the node's gas estimate.
+    pub gas_unit_price: Option<u64>,
+    /// Maximum gas units a transaction may use
+    pub max_gas_amount: Option<u64>,
+    /// Seconds after which an uncommitted transaction expires
+    pub expiration_secs: Option<u64>,
+    /// Percentage added to the simulated gas usage of a transaction when
+    /// estimating its cost. Defaults to `GAS_BUFFER_PERCENT`.
+    pub gas_buffer_percent: Option<u64>,
 }
 
 /// Raw Aptos connection configuration used for better deserialization errors.
@@ -38,10 +62,14 @@ impl FromRawConf<DeprecatedRawConnectionConf> for ConnectionConf {
         use ConnectionConfError::*;
         match raw {
             DeprecatedRawConnectionConf { url: Some(url) } => Ok(Self {
-                url: url
+                urls: vec![url
                     .parse()
                     .map_err(|e| InvalidConnectionUrl(url, e))
-                    .into_config_result(|| cwp.join("url"))?,
+                    .into_config_result(|| cwp.join("url"))?],
+                transaction_overrides: Default::default(),
+                external_submission: None,
+                client_metrics: Default::default(),
+                indexer_backend: Default::default(),
             }),
             DeprecatedRawConnectionConf { url: None } => {
                 Err(MissingConnectionUrl).into_config_result(|| cwp.join("url"))
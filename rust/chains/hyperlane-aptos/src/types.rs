@@ -7,7 +7,7 @@ use hyperlane_core::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::utils;
+use crate::{utils, AptosError};
 
 /// Merkle Tree content from MoveResource
 #[derive(Serialize, Deserialize)]
@@ -16,23 +16,26 @@ pub struct MoveMerkleTree {
     count: String,
 }
 
-impl From<MoveMerkleTree> for IncrementalMerkle {
-    fn from(val: MoveMerkleTree) -> Self {
-        let mut branches: Vec<H256> = vec![];
-        for branch in val.branch.iter() {
-            branches.push(H256::from_str(branch).unwrap());
+impl TryFrom<MoveMerkleTree> for IncrementalMerkle {
+    type Error = AptosError;
+    fn try_from(val: MoveMerkleTree) -> Result<Self, Self::Error> {
+        if val.branch.len() > TREE_DEPTH {
+            return Err(AptosError::InvalidResponse(format!(
+                "Merkle tree has {} branches, expected at most {TREE_DEPTH}",
+                val.branch.len()
+            )));
         }
-        if branches.len() < 32 {
-            while branches.len() < 32 {
-                branches.push(H256::zero());
-            }
+        let mut branch = [H256::zero(); TREE_DEPTH];
+        for (i, node) in val.branch.iter().enumerate() {
+            branch[i] = H256::from_str(node).map_err(|e| {
+                AptosError::InvalidResponse(format!("Invalid merkle tree branch {node}: {e}"))
+            })?;
         }
-        let count = val.count.parse::<usize>().unwrap();
+        let count = val.count.parse::<usize>().map_err(|e| {
+            AptosError::InvalidResponse(format!("Invalid merkle tree count {}: {e}", val.count))
+        })?;
 
-        IncrementalMerkle {
-            branch: branches[0..TREE_DEPTH].try_into().unwrap(),
-            count,
-        }
+        Ok(IncrementalMerkle { branch, count })
     }
 }
 
@@ -75,10 +78,10 @@ impl TryFrom<VersionedEvent> for DispatchEventData {
 }
 
 impl TryInto<HyperlaneMessage> for DispatchEventData {
-    type Error = hyperlane_core::HyperlaneProtocolError;
+    type Error = ChainCommunicationError;
     fn try_into(self) -> Result<HyperlaneMessage, Self::Error> {
-        let hex_bytes = hex::decode(&self.message.trim_start_matches("0x")).unwrap();
-        HyperlaneMessage::read_from(&mut &hex_bytes[..])
+        let hex_bytes = hex::decode(self.message.trim_start_matches("0x"))?;
+        Ok(HyperlaneMessage::read_from(&mut &hex_bytes[..])?)
     }
 }
 
@@ -112,13 +115,11 @@ impl TryInto<InterchainGasPayment> for GasPaymentEventData {
     fn try_into(self) -> Result<InterchainGasPayment, Self::Error> {
         Ok(InterchainGasPayment {
             destination: self.dest_domain,
-            message_id: utils::convert_hex_string_to_h256(&self.message_id).unwrap(),
-            payment: U256::from_str(&self.required_payment)
-                .map_err(ChainCommunicationError::from_other)
-                .unwrap(),
-            gas_amount: U256::from_str(&self.gas_amount)
-                .map_err(ChainCommunicationError::from_other)
-                .unwrap(),
+            message_id: utils::convert_hex_string_to_h256(&self.message_id)
+                .map_err(|msg| ChainCommunicationError::ParseError { msg })?,
+            // u256 event fields are decimal strings
+            payment: U256::from_dec_str(&self.required_payment)?,
+            gas_amount: U256::from_dec_str(&self.gas_amount)?,
         })
     }
 }
@@ -160,7 +161,8 @@ impl TryFrom<VersionedEvent> for MsgProcessEventData {
 impl TryInto<H256> for MsgProcessEventData {
     type Error = ChainCommunicationError;
     fn try_into(self) -> Result<H256, Self::Error> {
-        Ok(utils::convert_hex_string_to_h256(&self.message_id).unwrap())
+        utils::convert_hex_string_to_h256(&self.message_id)
+            .map_err(|msg| ChainCommunicationError::ParseError { msg })
     }
 }
 
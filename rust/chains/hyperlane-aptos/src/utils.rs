@@ -1,89 +1,273 @@
-use crate::{AptosClient, TxSpecificData};
+use crate::signers::AptosSigner;
+use crate::{
+    AptosClient, AptosError, AptosGraphqlClient, AptosNonceManager, AptosSimulationError,
+    AptosTxError, GraphqlEvent, GraphqlEventRange, TransactionOverrides, TxSpecificData,
+};
 use anyhow::{Context, Result};
 use aptos_sdk::{
-    crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    crypto::ed25519::Ed25519Signature,
     move_types::language_storage::TypeTag,
     move_types::{ident_str, language_storage::ModuleId},
-    rest_client::aptos_api_types::{
-        EntryFunctionId, MoveType, Transaction as AptosTransaction, TransactionInfo,
-        VersionedEvent, ViewRequest,
+    rest_client::{
+        aptos_api_types::{
+            EntryFunctionId, MoveType, Transaction as AptosTransaction, TransactionInfo,
+            VersionedEvent, ViewRequest,
+        },
+        error::RestError,
     },
     transaction_builder::TransactionFactory,
     types::{
         account_address::AccountAddress,
         chain_id::ChainId,
-        transaction::{
-            authenticator::AuthenticationKey, EntryFunction, SignedTransaction, TransactionPayload,
-        },
-        AccountKey, LocalAccount,
+        transaction::{EntryFunction, SignedTransaction, TransactionPayload},
     },
 };
-use hyperlane_core::{ChainCommunicationError, ChainResult, Indexed, LogMeta, H256, H512, U256};
-use solana_sdk::signature::Keypair;
-use std::{ops::RangeInclusive, str::FromStr};
-use tracing::info;
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, FixedPointNumber, IndexMode, Indexed, LogMeta, TxOutcome,
+    H256, H512, U256,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    num::NonZeroU64,
+    ops::{Range, RangeInclusive},
+    str::FromStr,
+};
+use tracing::debug;
 
 /// limit of gas unit
-const GAS_UNIT_LIMIT: u64 = 100000;
+pub const GAS_UNIT_LIMIT: u64 = 100000;
 /// minimum price of gas unit of aptos chains
 pub const GAS_UNIT_PRICE: u64 = 100;
+/// percentage added to simulated gas usage, as execution may cost more than simulation
+pub const GAS_BUFFER_PERCENT: u64 = 20;
 
-/// Send Aptos Transaction
+/// Sign and submit an Aptos transaction, then wait for it to be committed.
+/// The gas unit price comes from the node's estimate unless overridden.
+/// Transactions that are committed but fail are returned as
+/// [`AptosTxError::MoveAbort`] or [`AptosTxError::ExecutionFailure`].
 pub async fn send_aptos_transaction(
     aptos_client: &AptosClient,
-    signer: &mut LocalAccount,
+    signer: &AptosNonceManager,
     payload: TransactionPayload,
-) -> Result<AptosTransaction> {
+    overrides: &TransactionOverrides,
+) -> Result<AptosTransaction, AptosTxError> {
     let state = aptos_client
         .get_ledger_information()
         .await
-        .context("Failed in getting chain id")?
+        .map_err(|e| AptosTxError::Rpc(e.to_string()))?
         .into_inner();
 
-    let transaction_factory = TransactionFactory::new(ChainId::new(state.chain_id))
-        .with_gas_unit_price(100)
-        .with_max_gas_amount(GAS_UNIT_LIMIT);
+    let gas_unit_price = gas_unit_price(aptos_client, overrides).await?;
 
-    let signed_tx = signer.sign_with_transaction_builder(transaction_factory.payload(payload));
+    let mut transaction_factory = TransactionFactory::new(ChainId::new(state.chain_id))
+        .with_gas_unit_price(gas_unit_price)
+        .with_max_gas_amount(overrides.max_gas_amount.unwrap_or(GAS_UNIT_LIMIT));
+    if let Some(expiration_secs) = overrides.expiration_secs {
+        transaction_factory = transaction_factory.with_transaction_expiration_time(expiration_secs);
+    }
 
-    let response = aptos_client
-        .submit_and_wait(&signed_tx)
-        .await
-        .map_err(|e| anyhow::anyhow!(e.to_string()))?
-        .into_inner();
-    Ok(response)
+    // A stale sequence number means another process used the signer,
+    // so resync it and try again once.
+    let mut retried = false;
+    let (signed_tx, pending_tx) = loop {
+        let signed_tx = signer
+            .sign(aptos_client, transaction_factory.payload(payload.clone()))
+            .await
+            .map_err(|e| AptosTxError::Rpc(e.to_string()))?;
+        match aptos_client.submit(&signed_tx).await {
+            Ok(response) => break (signed_tx, response.into_inner()),
+            Err(e) => {
+                let err = submit_error(e);
+                signer.resync().await;
+                if retried || !err.is_stale_sequence_number() {
+                    return Err(err);
+                }
+                debug!(error = %err, "Retrying Aptos transaction with a resynced sequence number");
+                retried = true;
+            }
+        }
+    };
+    let hash = signed_tx.clone().committed_hash();
+
+    let transaction = match aptos_client.wait_for_transaction(&pending_tx).await {
+        Ok(response) => response.into_inner(),
+        Err(RestError::Timeout(_)) => {
+            signer.resync().await;
+            return Err(AptosTxError::Timeout(hash.to_hex_literal()));
+        }
+        // The client reports committed failures as errors, fetch the transaction
+        // itself to find out why it failed.
+        Err(_) => aptos_client
+            .get_transaction_by_hash(hash)
+            .await
+            .map_err(|e| AptosTxError::Rpc(e.to_string()))?
+            .into_inner(),
+    };
+
+    if transaction.is_pending() {
+        signer.resync().await;
+        return Err(AptosTxError::Timeout(hash.to_hex_literal()));
+    }
+    if transaction.success() {
+        return Ok(transaction);
+    }
+
+    let vm_status = transaction.vm_status();
+    debug!(
+        hash = hash.to_hex_literal(),
+        vm_status, "Aptos transaction failed"
+    );
+    Err(match parse_move_abort(&vm_status) {
+        Some((location, code)) => AptosTxError::MoveAbort {
+            hash: hash.to_hex_literal(),
+            location,
+            code,
+            transaction: Box::new(transaction),
+        },
+        None => AptosTxError::ExecutionFailure {
+            hash: hash.to_hex_literal(),
+            vm_status,
+            transaction: Box::new(transaction),
+        },
+    })
+}
+
+/// Classifies an error submitting a transaction. Only an API error means
+/// the node looked at the transaction and refused it, anything else is
+/// about reaching the node.
+fn submit_error(err: RestError) -> AptosTxError {
+    match err {
+        RestError::Api(_) => AptosTxError::MempoolRejection(err.to_string()),
+        err => AptosTxError::Rpc(err.to_string()),
+    }
+}
+
+/// The gas unit price to submit transactions at: the override if set,
+/// otherwise the node's estimate but no less than [`GAS_UNIT_PRICE`]
+pub async fn gas_unit_price(
+    aptos_client: &AptosClient,
+    overrides: &TransactionOverrides,
+) -> Result<u64, AptosTxError> {
+    match overrides.gas_unit_price {
+        Some(gas_unit_price) => Ok(gas_unit_price),
+        None => Ok(aptos_client
+            .estimate_gas_price()
+            .await
+            .map_err(|e| AptosTxError::Rpc(e.to_string()))?
+            .into_inner()
+            .gas_estimate
+            .max(GAS_UNIT_PRICE)),
+    }
 }
 
-/// Send Aptos Transaction
+/// Classifies the VM status of a failed simulation
+pub fn simulation_error(vm_status: String) -> AptosSimulationError {
+    match parse_move_abort(&vm_status) {
+        Some((location, code)) => AptosSimulationError::MoveAbort {
+            location,
+            code,
+            vm_status,
+        },
+        None => AptosSimulationError::ExecutionFailure { vm_status },
+    }
+}
+
+/// Parses the abort location and code out of a Move abort VM status.
+/// The node reports aborts as `Move abort in 0x1::coin: 0x10006` or, when it
+/// knows the error name, `Move abort in 0x1::coin: EINSUFFICIENT_BALANCE(0x10006): ...`.
+fn parse_move_abort(vm_status: &str) -> Option<(String, u64)> {
+    let (location, rest) = vm_status.strip_prefix("Move abort in ")?.split_once(": ")?;
+    let code = match rest.split_once('(') {
+        Some((_, named_code)) => named_code.split(')').next()?,
+        None => rest.split(|c: char| c == ':' || c.is_whitespace()).next()?,
+    };
+    let code = match code.strip_prefix("0x") {
+        Some(hex_code) => u64::from_str_radix(hex_code, 16).ok()?,
+        None => code.parse().ok()?,
+    };
+    Some((location.to_string(), code))
+}
+
+/// Build the outcome of a committed Aptos transaction
+pub fn tx_outcome_from_transaction(transaction: &AptosTransaction) -> ChainResult<TxOutcome> {
+    let info = transaction
+        .transaction_info()
+        .map_err(|e| ChainCommunicationError::CustomError(e.to_string()))?;
+    let tx_hash = convert_hex_string_to_h256(&info.hash.to_string())
+        .map_err(|msg| ChainCommunicationError::ParseError { msg })?;
+    let gas_unit_price = match transaction {
+        AptosTransaction::UserTransaction(user_tx) => user_tx.request.gas_unit_price.0,
+        _ => 0,
+    };
+    Ok(TxOutcome {
+        transaction_id: H512::from(tx_hash),
+        executed: transaction.success(),
+        gas_price: FixedPointNumber::from(gas_unit_price),
+        gas_used: U256::from(info.gas_used.0),
+    })
+}
+
+/// Simulate an Aptos transaction from `signer` at its on-chain sequence number
 pub async fn simulate_aptos_transaction(
     aptos_client: &AptosClient,
-    signer: &mut LocalAccount,
+    signer: &AptosSigner,
     payload: TransactionPayload,
+) -> Result<TransactionInfo> {
+    simulate_aptos_transaction_at_price(
+        aptos_client,
+        signer,
+        payload,
+        GAS_UNIT_PRICE,
+        GAS_UNIT_LIMIT,
+    )
+    .await
+}
+
+/// Simulate an Aptos transaction from `signer` at its on-chain sequence number,
+/// paying `gas_unit_price` for up to `max_gas_amount` gas units
+pub async fn simulate_aptos_transaction_at_price(
+    aptos_client: &AptosClient,
+    signer: &AptosSigner,
+    payload: TransactionPayload,
+    gas_unit_price: u64,
+    max_gas_amount: u64,
 ) -> Result<TransactionInfo> {
     let state = aptos_client
         .get_ledger_information()
         .await
         .context("Failed in getting chain id")?
         .into_inner();
+    let sequence_number = aptos_client
+        .get_account(signer.account_address())
+        .await
+        .context("Failed in getting sequence number")?
+        .into_inner()
+        .sequence_number;
 
     let transaction_factory = TransactionFactory::new(ChainId::new(state.chain_id))
-        .with_gas_unit_price(GAS_UNIT_PRICE)
-        .with_max_gas_amount(GAS_UNIT_LIMIT);
+        .with_gas_unit_price(gas_unit_price)
+        .with_max_gas_amount(max_gas_amount);
 
     let raw_tx = transaction_factory
         .payload(payload)
-        .sender(signer.address())
-        .sequence_number(signer.sequence_number())
+        .sender(signer.account_address())
+        .sequence_number(sequence_number)
         .build();
 
+    // Simulations must not carry a valid signature
     let signed_tx = SignedTransaction::new(
         raw_tx,
         signer.public_key().clone(),
         Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
     );
 
-    let response_txns = aptos_client.simulate(&signed_tx).await?.into_inner();
-    let response = response_txns[0].clone();
+    let response = aptos_client
+        .simulate(&signed_tx)
+        .await?
+        .into_inner()
+        .into_iter()
+        .next()
+        .context("Empty simulation response")?;
 
     Ok(response.info)
 }
@@ -113,50 +297,356 @@ pub async fn send_view_request(
     type_arguments: Vec<MoveType>,
     arguments: Vec<serde_json::Value>,
 ) -> ChainResult<Vec<serde_json::Value>> {
+    send_view_request_at_version(
+        aptos_client,
+        package_address,
+        module_name,
+        function_name,
+        type_arguments,
+        arguments,
+        None,
+    )
+    .await
+}
+
+/// Send View Request against the ledger state at `ledger_version`,
+/// or the latest state if `None`
+pub async fn send_view_request_at_version(
+    aptos_client: &AptosClient,
+    package_address: String,
+    module_name: String,
+    function_name: String,
+    type_arguments: Vec<MoveType>,
+    arguments: Vec<serde_json::Value>,
+    ledger_version: Option<u64>,
+) -> ChainResult<Vec<serde_json::Value>> {
+    let function = format!("{package_address}::{module_name}::{function_name}");
+    let function_id =
+        EntryFunctionId::from_str(&function).map_err(|e| AptosError::InvalidFunction {
+            function: function.clone(),
+            reason: e.to_string(),
+        })?;
     let view_response = aptos_client
         .view(
             &ViewRequest {
-                function: EntryFunctionId::from_str(&format!(
-                    "{package_address}::{module_name}::{function_name}"
-                ))
-                .unwrap(),
+                function: function_id,
                 type_arguments,
                 arguments,
             },
-            Option::None,
+            ledger_version,
         )
         .await
-        .map_err(ChainCommunicationError::from_other)?
+        .map_err(AptosError::from)?
         .into_inner();
     Ok(view_response)
 }
 
+/// Return value `index` of a view function response, decoded into `T`.
+/// `function` names the view function in errors.
+pub fn decode_view_value<T: DeserializeOwned>(
+    function: &str,
+    response: &[serde_json::Value],
+    index: usize,
+) -> Result<T, AptosError> {
+    let value = view_value(function, response, index)?;
+    serde_json::from_value(value.clone()).map_err(|e| AptosError::InvalidViewResponse {
+        function: function.to_string(),
+        reason: format!("return value {index} ({value}) is invalid: {e}"),
+    })
+}
+
+/// Unsigned integer return value `index` of a view function response.
+/// The REST API returns `u8`, `u16` and `u32` as numbers and wider
+/// integers as decimal strings, so both are accepted.
+pub fn decode_view_uint<T: FromStr>(
+    function: &str,
+    response: &[serde_json::Value],
+    index: usize,
+) -> Result<T, AptosError> {
+    let value = view_value(function, response, index)?;
+    let parsed = match value {
+        serde_json::Value::String(s) => s.parse().ok(),
+        serde_json::Value::Number(n) => n.to_string().parse().ok(),
+        _ => None,
+    };
+    parsed.ok_or_else(|| AptosError::InvalidViewResponse {
+        function: function.to_string(),
+        reason: format!("return value {index} ({value}) is not a valid unsigned integer"),
+    })
+}
+
+/// `u256` return value `index` of a view function response, a decimal string
+pub fn decode_view_u256(
+    function: &str,
+    response: &[serde_json::Value],
+    index: usize,
+) -> Result<U256, AptosError> {
+    let value = view_value(function, response, index)?;
+    value
+        .as_str()
+        .and_then(|s| U256::from_dec_str(s).ok())
+        .ok_or_else(|| AptosError::InvalidViewResponse {
+            function: function.to_string(),
+            reason: format!("return value {index} ({value}) is not a valid u256"),
+        })
+}
+
+/// `address` return value `index` of a view function response
+pub fn decode_view_address(
+    function: &str,
+    response: &[serde_json::Value],
+    index: usize,
+) -> Result<H256, AptosError> {
+    let value = view_value(function, response, index)?;
+    value
+        .as_str()
+        .and_then(|s| convert_hex_string_to_h256(s).ok())
+        .ok_or_else(|| AptosError::InvalidViewResponse {
+            function: function.to_string(),
+            reason: format!("return value {index} ({value}) is not a valid address"),
+        })
+}
+
+/// `vector<address>` return value `index` of a view function response
+pub fn decode_view_addresses(
+    function: &str,
+    response: &[serde_json::Value],
+    index: usize,
+) -> Result<Vec<H256>, AptosError> {
+    let addresses: Vec<String> = decode_view_value(function, response, index)?;
+    addresses
+        .iter()
+        .map(|address| {
+            convert_hex_string_to_h256(address).map_err(|_| AptosError::InvalidViewResponse {
+                function: function.to_string(),
+                reason: format!("return value {index} contains invalid address {address}"),
+            })
+        })
+        .collect()
+}
+
+/// `vector<u8>` return value `index` of a view function response, a hex string
+pub fn decode_view_bytes(
+    function: &str,
+    response: &[serde_json::Value],
+    index: usize,
+) -> Result<Vec<u8>, AptosError> {
+    let value = view_value(function, response, index)?;
+    value
+        .as_str()
+        .and_then(|s| hex::decode(s.trim_start_matches("0x")).ok())
+        .ok_or_else(|| AptosError::InvalidViewResponse {
+            function: function.to_string(),
+            reason: format!("return value {index} ({value}) is not a valid hex string"),
+        })
+}
+
+fn view_value<'a>(
+    function: &str,
+    response: &'a [serde_json::Value],
+    index: usize,
+) -> Result<&'a serde_json::Value, AptosError> {
+    response
+        .get(index)
+        .ok_or_else(|| AptosError::InvalidViewResponse {
+            function: function.to_string(),
+            reason: format!(
+                "expected at least {} return values, got {}",
+                index + 1,
+                response.len()
+            ),
+        })
+}
+
+/// Given a lag, returns the ledger version of the last transaction in the
+/// block `lag` blocks behind the tip.
+/// If the lag is None, a version of None is given, indicating that the
+/// latest ledger state can be used.
+pub async fn get_ledger_version_for_lag(
+    aptos_client: &AptosClient,
+    lag: Option<NonZeroU64>,
+) -> ChainResult<Option<u64>> {
+    let Some(lag) = lag else {
+        return Ok(None);
+    };
+    let tip = aptos_client
+        .get_ledger_information()
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .into_inner()
+        .block_height;
+    let block = aptos_client
+        .get_cached_block_by_height(tip.saturating_sub(lag.get()))
+        .await?;
+    Ok(Some(block.last_version))
+}
+
 /// Convert address string to H256
 pub fn convert_hex_string_to_h256(addr: &str) -> Result<H256, String> {
     let formated_addr = format!("{:0>64}", addr.to_string().trim_start_matches("0x"));
     H256::from_str(&formated_addr).map_err(|e| e.to_string())
 }
 
-/// Convert payer(Keypair) into Aptos LocalAccount
-pub async fn convert_keypair_to_aptos_account(
+/// Maximum number of events fetched from an event handle per request
+const EVENT_PAGE_SIZE: u16 = 100;
+
+/// Fetch the event with sequence number `sequence` from an event handle
+async fn get_event_by_sequence(
     aptos_client: &AptosClient,
-    payer: &Keypair,
-) -> LocalAccount {
-    let signer_priv_key = Ed25519PrivateKey::try_from(payer.secret().to_bytes().as_ref()).unwrap();
-    let signer_address =
-        AuthenticationKey::ed25519(&Ed25519PublicKey::from(&signer_priv_key)).account_address();
-    let signer_account = LocalAccount::new(
-        signer_address,
-        AccountKey::from_private_key(signer_priv_key),
-        aptos_client
-            .get_account(signer_address)
+    account_address: AccountAddress,
+    struct_tag: &str,
+    field_name: &str,
+    sequence: u64,
+) -> ChainResult<Option<VersionedEvent>> {
+    let events = aptos_client
+        .get_account_events(
+            account_address,
+            struct_tag,
+            field_name,
+            Some(sequence),
+            Some(1),
+        )
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .into_inner();
+    Ok(events.into_iter().next())
+}
+
+/// Number of events emitted so far to an event handle, read from the
+/// counter of the `EventHandle` stored in `struct_tag`
+pub async fn get_event_count(
+    aptos_client: &AptosClient,
+    account_address: AccountAddress,
+    struct_tag: &str,
+    field_name: &str,
+) -> ChainResult<u64> {
+    let resource = aptos_client
+        .get_account_resource(account_address, struct_tag)
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .into_inner()
+        .ok_or_else(|| ChainCommunicationError::ParseError {
+            msg: format!("Resource {struct_tag} not found at {account_address}"),
+        })?;
+    parse_event_handle_counter(&resource.data, field_name)
+}
+
+/// Read the counter of the event handle `field_name` from resource data
+fn parse_event_handle_counter(data: &serde_json::Value, field_name: &str) -> ChainResult<u64> {
+    let counter = data
+        .get(field_name)
+        .and_then(|handle| handle.get("counter"))
+        .and_then(|counter| counter.as_str())
+        .ok_or_else(|| ChainCommunicationError::ParseError {
+            msg: format!("Missing event handle counter for `{field_name}`"),
+        })?;
+    Ok(counter.parse()?)
+}
+
+/// Binary search the sequence number of the first event emitted at or after
+/// `version`. Returns `event_count` if there is no such event.
+async fn find_first_sequence_at_version(
+    aptos_client: &AptosClient,
+    account_address: AccountAddress,
+    struct_tag: &str,
+    field_name: &str,
+    event_count: u64,
+    version: u64,
+) -> ChainResult<u64> {
+    let (mut low, mut high) = (0, event_count);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let event =
+            get_event_by_sequence(aptos_client, account_address, struct_tag, field_name, mid)
+                .await?;
+        match event {
+            Some(event) if event.version.0 < version => low = mid + 1,
+            _ => high = mid,
+        }
+    }
+    Ok(low)
+}
+
+/// Fetch the events of an event handle with sequence numbers in
+/// `[start, end)`, at most `EVENT_PAGE_SIZE` per request.
+/// Stops early once an event emitted after `max_version` is seen.
+pub async fn get_events_by_sequence_range(
+    aptos_client: &AptosClient,
+    account_address: AccountAddress,
+    struct_tag: &str,
+    field_name: &str,
+    sequences: Range<u64>,
+    max_version: Option<u64>,
+) -> ChainResult<Vec<VersionedEvent>> {
+    let mut events = Vec::new();
+    let mut next = sequences.start;
+    while next < sequences.end {
+        let limit = (sequences.end - next).min(EVENT_PAGE_SIZE as u64) as u16;
+        let page = aptos_client
+            .get_account_events(
+                account_address,
+                struct_tag,
+                field_name,
+                Some(next),
+                Some(limit),
+            )
             .await
-            .map_err(ChainCommunicationError::from_other)
-            .unwrap()
-            .into_inner()
-            .sequence_number,
-    );
-    signer_account
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+        let page_len = page.len() as u64;
+        for event in page {
+            if max_version.is_some_and(|max| event.version.0 > max) {
+                return Ok(events);
+            }
+            events.push(event);
+        }
+        if page_len < limit as u64 {
+            break;
+        }
+        next += page_len;
+    }
+    Ok(events)
+}
+
+/// Decode the data of an event along with the height of the block it was emitted in
+fn decode_event<S>(event: &VersionedEvent) -> ChainResult<(S, u64)>
+where
+    S: TryFrom<VersionedEvent> + TxSpecificData,
+    ChainCommunicationError: From<<S as TryFrom<VersionedEvent>>::Error>,
+{
+    let evt_data: S = event.clone().try_into()?;
+    let block_height: u64 = evt_data.block_height().parse()?;
+    Ok((evt_data, block_height))
+}
+
+/// Build the indexed value and log metadata of an event
+fn event_to_log<T, S>(
+    account_address: AccountAddress,
+    event: VersionedEvent,
+    evt_data: S,
+    block_height: u64,
+    block_hash: H256,
+) -> ChainResult<(Indexed<T>, LogMeta)>
+where
+    S: TxSpecificData + TryInto<T>,
+    ChainCommunicationError: From<<S as TryInto<T>>::Error>,
+{
+    let sequence: u32 = (*event.sequence_number.inner())
+        .try_into()
+        .map_err(ChainCommunicationError::from_other)?;
+    let transaction_hash = convert_hex_string_to_h256(&evt_data.transaction_hash())
+        .map_err(|msg| ChainCommunicationError::ParseError { msg })?;
+    Ok((
+        Indexed::new(evt_data.try_into()?).with_sequence(sequence),
+        LogMeta {
+            address: account_address.into_bytes().into(),
+            block_number: block_height,
+            block_hash,
+            transaction_id: H512::from(transaction_hash),
+            transaction_index: *event.version.inner(),
+            log_index: U256::from(*event.sequence_number.inner()),
+        },
+    ))
 }
 
 /// Filter events based on range
@@ -172,61 +662,438 @@ where
     ChainCommunicationError:
         From<<S as TryFrom<VersionedEvent>>::Error> + From<<S as TryInto<T>>::Error>,
 {
-    // fetch events from global storage
-    let events: Vec<VersionedEvent> = aptos_client
-        .get_account_events(account_address, struct_tag, field_name, None, Some(10000))
-        .await
-        .map_err(ChainCommunicationError::from_other)?
-        .into_inner();
-
-    // get start block and end block
-    let blk_start_no: u32 = *range.start();
-    let blk_end_no = *range.end();
+    // translate the block range into a transaction version range
     let start_block = aptos_client
-        .get_block_by_height(blk_start_no as u64, false)
-        .await
-        .map_err(ChainCommunicationError::from_other)?
-        .into_inner();
+        .get_cached_block_by_height(*range.start() as u64)
+        .await?;
     let end_block = aptos_client
-        .get_block_by_height(blk_end_no as u64, false)
-        .await
-        .map_err(ChainCommunicationError::from_other)?
-        .into_inner();
-    let start_tx_version = start_block.first_version;
-    let end_tx_version = end_block.last_version;
+        .get_cached_block_by_height(*range.end() as u64)
+        .await?;
 
-    // filter events which are from `start_tx_version` to `end_tx_version`
-    let filtered_events: Vec<VersionedEvent> = events
-        .into_iter()
-        .filter(|e| e.version.0 > start_tx_version.0 && e.version.0 <= end_tx_version.0)
-        .collect();
+    let event_count =
+        get_event_count(aptos_client, account_address, struct_tag, field_name).await?;
+    let first_sequence = find_first_sequence_at_version(
+        aptos_client,
+        account_address,
+        struct_tag,
+        field_name,
+        event_count,
+        start_block.first_version,
+    )
+    .await?;
+
+    let events = get_events_by_sequence_range(
+        aptos_client,
+        account_address,
+        struct_tag,
+        field_name,
+        first_sequence..event_count,
+        Some(end_block.last_version),
+    )
+    .await?;
 
     // prepare result
-    let mut messages: Vec<(Indexed<T>, LogMeta)> =
-        Vec::with_capacity((range.end() - range.start()) as usize);
-    for filtered_event in filtered_events {
-        info!("filtered_event: {:?}", filtered_event);
-        let evt_data: S = filtered_event.clone().try_into()?;
-        let block_height = evt_data.block_height().parse().unwrap();
+    let mut messages: Vec<(Indexed<T>, LogMeta)> = Vec::with_capacity(events.len());
+    for event in events {
+        debug!(?event, "filtered event");
+        let (evt_data, block_height) = decode_event::<S>(&event)?;
         let block = aptos_client
-            .get_block_by_height(block_height as u64, false)
+            .get_cached_block_by_height(block_height)
+            .await?;
+        messages.push(event_to_log(
+            account_address,
+            event,
+            evt_data,
+            block_height,
+            block.hash,
+        )?);
+    }
+
+    Ok(messages)
+}
+
+/// Filter events based on range, reading them from an Aptos indexer GraphQL
+/// API. Gives the same output as [`get_filtered_events`] in a few requests,
+/// however long the range.
+pub async fn get_filtered_events_from_graphql<T, S>(
+    graphql_client: &AptosGraphqlClient,
+    aptos_client: &AptosClient,
+    account_address: AccountAddress,
+    event_type: &str,
+    range: RangeInclusive<u32>,
+) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>
+where
+    S: TryFrom<VersionedEvent> + TxSpecificData + TryInto<T> + Clone,
+    ChainCommunicationError:
+        From<<S as TryFrom<VersionedEvent>>::Error> + From<<S as TryInto<T>>::Error>,
+{
+    let range = GraphqlEventRange::Blocks(*range.start() as u64..=*range.end() as u64);
+    let events = graphql_client
+        .events(account_address, event_type, range)
+        .await?;
+    graphql_events_to_logs::<T, S>(graphql_client, aptos_client, account_address, events).await
+}
+
+/// Fetch the events of an event handle with sequence numbers in `range`
+pub async fn get_events_by_sequence<T, S>(
+    aptos_client: &AptosClient,
+    account_address: AccountAddress,
+    struct_tag: &str,
+    field_name: &str,
+    range: RangeInclusive<u32>,
+) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>
+where
+    S: TryFrom<VersionedEvent> + TxSpecificData + TryInto<T> + Clone,
+    ChainCommunicationError:
+        From<<S as TryFrom<VersionedEvent>>::Error> + From<<S as TryInto<T>>::Error>,
+{
+    let events = get_events_by_sequence_range(
+        aptos_client,
+        account_address,
+        struct_tag,
+        field_name,
+        *range.start() as u64..*range.end() as u64 + 1,
+        None,
+    )
+    .await?;
+
+    let mut logs = Vec::with_capacity(events.len());
+    for event in events {
+        let (evt_data, block_height) = decode_event::<S>(&event)?;
+        let block = aptos_client
+            .get_cached_block_by_height(block_height)
+            .await?;
+        logs.push(event_to_log(
+            account_address,
+            event,
+            evt_data,
+            block_height,
+            block.hash,
+        )?);
+    }
+    Ok(logs)
+}
+
+/// Fetch the events of type `event_type` with sequence numbers in `range`
+/// from an Aptos indexer GraphQL API
+pub async fn get_events_by_sequence_from_graphql<T, S>(
+    graphql_client: &AptosGraphqlClient,
+    aptos_client: &AptosClient,
+    account_address: AccountAddress,
+    event_type: &str,
+    range: RangeInclusive<u32>,
+) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>
+where
+    S: TryFrom<VersionedEvent> + TxSpecificData + TryInto<T> + Clone,
+    ChainCommunicationError:
+        From<<S as TryFrom<VersionedEvent>>::Error> + From<<S as TryInto<T>>::Error>,
+{
+    let range = GraphqlEventRange::Sequences(*range.start() as u64..=*range.end() as u64);
+    let events = graphql_client
+        .events(account_address, event_type, range)
+        .await?;
+    graphql_events_to_logs::<T, S>(graphql_client, aptos_client, account_address, events).await
+}
+
+/// Build the indexed values and log metadata of events read from GraphQL,
+/// looking up the hashes of blocks that aren't cached in one request
+async fn graphql_events_to_logs<T, S>(
+    graphql_client: &AptosGraphqlClient,
+    aptos_client: &AptosClient,
+    account_address: AccountAddress,
+    events: Vec<GraphqlEvent>,
+) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>
+where
+    S: TryFrom<VersionedEvent> + TxSpecificData + TryInto<T> + Clone,
+    ChainCommunicationError:
+        From<<S as TryFrom<VersionedEvent>>::Error> + From<<S as TryInto<T>>::Error>,
+{
+    let decoded = events
+        .into_iter()
+        .map(|event| -> ChainResult<_> {
+            let event = VersionedEvent::try_from(event)?;
+            let (evt_data, block_height) = decode_event::<S>(&event)?;
+            Ok((event, evt_data, block_height))
+        })
+        .collect::<ChainResult<Vec<_>>>()?;
+
+    let block_cache = aptos_client.block_cache();
+    let mut uncached_heights: Vec<u64> = decoded
+        .iter()
+        .map(|(_, _, block_height)| *block_height)
+        .filter(|block_height| block_cache.get(*block_height).is_none())
+        .collect();
+    uncached_heights.dedup();
+    let block_hashes = graphql_client.block_hashes(&uncached_heights).await?;
+
+    decoded
+        .into_iter()
+        .map(|(event, evt_data, block_height)| -> ChainResult<_> {
+            let block_hash = match block_cache.get(block_height) {
+                Some(block) => block.hash,
+                None => *block_hashes.get(&block_height).ok_or_else(|| {
+                    AptosError::InvalidResponse(format!(
+                        "Block {block_height} not found in the indexer"
+                    ))
+                })?,
+            };
+            event_to_log(account_address, event, evt_data, block_height, block_hash)
+        })
+        .collect()
+}
+
+/// An event handle of a resource and the type of the events emitted to it
+#[derive(Debug, Clone)]
+pub struct AptosEventHandle {
+    /// Account holding the resource
+    pub account_address: AccountAddress,
+    /// Resource type, e.g. `0x1::mailbox::MailBoxState`
+    pub struct_tag: String,
+    /// Field of the resource holding the event handle, e.g. `dispatch_events`
+    pub field_name: &'static str,
+    /// Move type of the events, e.g. `0x1::mailbox::DispatchEvent`
+    pub event_type: String,
+}
+
+/// Latest block height indexers fetch events up to. Events read from
+/// `graphql_client` are only available once the indexer has processed their
+/// block, so the tip is the last block it fully indexed rather than the node's.
+pub async fn get_indexed_tip(
+    aptos_client: &AptosClient,
+    graphql_client: Option<&AptosGraphqlClient>,
+) -> ChainResult<u32> {
+    let node_tip = aptos_client
+        .get_ledger_information()
+        .await
+        .map_err(AptosError::from)?
+        .into_inner()
+        .block_height;
+    let Some(graphql_client) = graphql_client else {
+        return Ok(node_tip as u32);
+    };
+    let indexed_version = graphql_client.latest_indexed_version().await?;
+    let block = aptos_client
+        .get_block_by_version(indexed_version, false)
+        .await
+        .map_err(AptosError::from)?
+        .into_inner();
+    let indexed_tip = if block.last_version.0 > indexed_version {
+        // the block containing the version is only partially indexed
+        block.block_height.0.saturating_sub(1)
+    } else {
+        block.block_height.0
+    };
+    if indexed_tip < node_tip {
+        debug!(indexed_tip, node_tip, "GraphQL indexer lags the node");
+    }
+    Ok(indexed_tip.min(node_tip) as u32)
+}
+
+/// Fetch the events of `event_handle` in `range`, a range of block heights
+/// in block mode and of event sequence numbers in sequence mode.
+/// Events are read from `graphql_client` if set and from the node otherwise.
+pub async fn fetch_events_in_range<T, S>(
+    aptos_client: &AptosClient,
+    graphql_client: Option<&AptosGraphqlClient>,
+    index_mode: IndexMode,
+    event_handle: &AptosEventHandle,
+    range: RangeInclusive<u32>,
+) -> ChainResult<Vec<(Indexed<T>, LogMeta)>>
+where
+    S: TryFrom<VersionedEvent> + TxSpecificData + TryInto<T> + Clone,
+    ChainCommunicationError:
+        From<<S as TryFrom<VersionedEvent>>::Error> + From<<S as TryInto<T>>::Error>,
+{
+    let AptosEventHandle {
+        account_address,
+        struct_tag,
+        field_name,
+        event_type,
+    } = event_handle;
+    match (index_mode, graphql_client) {
+        (IndexMode::Block, None) => {
+            get_filtered_events::<T, S>(
+                aptos_client,
+                *account_address,
+                struct_tag,
+                field_name,
+                range,
+            )
             .await
-            .map_err(ChainCommunicationError::from_other)?
-            .into_inner();
-        messages.push((
-            Indexed::new(evt_data.clone().try_into()?),
-            LogMeta {
-                address: account_address.into_bytes().into(),
-                block_number: block_height,
-                block_hash: convert_hex_string_to_h256(&block.block_hash.to_string()).unwrap(),
-                transaction_id: H512::from(
-                    convert_hex_string_to_h256(&evt_data.transaction_hash()).unwrap(),
-                ),
-                transaction_index: *filtered_event.version.inner(),
-                log_index: U256::from(*filtered_event.sequence_number.inner()),
+        }
+        (IndexMode::Block, Some(graphql_client)) => {
+            get_filtered_events_from_graphql::<T, S>(
+                graphql_client,
+                aptos_client,
+                *account_address,
+                event_type,
+                range,
+            )
+            .await
+        }
+        (IndexMode::Sequence, None) => {
+            get_events_by_sequence::<T, S>(
+                aptos_client,
+                *account_address,
+                struct_tag,
+                field_name,
+                range,
+            )
+            .await
+        }
+        (IndexMode::Sequence, Some(graphql_client)) => {
+            get_events_by_sequence_from_graphql::<T, S>(
+                graphql_client,
+                aptos_client,
+                *account_address,
+                event_type,
+                range,
+            )
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aptos_sdk::rest_client::error::RestError;
+    use hyperlane_core::{ChainCommunicationError, H256, U256};
+    use serde_json::json;
+
+    use super::{
+        decode_view_address, decode_view_addresses, decode_view_bytes, decode_view_u256,
+        decode_view_uint, decode_view_value, parse_event_handle_counter, parse_move_abort,
+        simulation_error, submit_error,
+    };
+    use crate::{AptosSimulationError, AptosTxError};
+
+    #[test]
+    fn test_parse_event_handle_counter() {
+        let data = serde_json::json!({
+            "dispatch_events": {
+                "counter": "12",
+                "guid": { "id": { "addr": "0x1", "creation_num": "4" } }
             },
+            "process_events": {
+                "counter": "3",
+                "guid": { "id": { "addr": "0x1", "creation_num": "5" } }
+            }
+        });
+        assert_eq!(
+            parse_event_handle_counter(&data, "dispatch_events").unwrap(),
+            12
+        );
+        assert_eq!(
+            parse_event_handle_counter(&data, "process_events").unwrap(),
+            3
+        );
+        assert!(parse_event_handle_counter(&data, "gas_payment_events").is_err());
+    }
+
+    #[test]
+    fn test_parse_move_abort() {
+        assert_eq!(
+            parse_move_abort(
+                "Move abort in 0x1::coin: EINSUFFICIENT_BALANCE(0x10006): Not enough coins"
+            ),
+            Some(("0x1::coin".to_string(), 0x10006))
+        );
+        assert_eq!(
+            parse_move_abort("Move abort in 0xabc::mailbox: 0x4"),
+            Some(("0xabc::mailbox".to_string(), 4))
+        );
+        assert_eq!(parse_move_abort("OUT_OF_GAS"), None);
+    }
+
+    #[test]
+    fn test_simulation_error() {
+        assert_eq!(
+            simulation_error("Move abort in 0xabc::mailbox: 0x4".to_string()),
+            AptosSimulationError::MoveAbort {
+                location: "0xabc::mailbox".to_string(),
+                code: 4,
+                vm_status: "Move abort in 0xabc::mailbox: 0x4".to_string(),
+            }
+        );
+        assert_eq!(
+            simulation_error("OUT_OF_GAS".to_string()),
+            AptosSimulationError::ExecutionFailure {
+                vm_status: "OUT_OF_GAS".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_submit_error() {
+        assert!(matches!(
+            submit_error(RestError::Timeout("submit")),
+            AptosTxError::Rpc(_)
         ));
     }
 
-    Ok(messages)
+    #[test]
+    fn test_only_simulated_aborts_would_revert() {
+        assert!(matches!(
+            ChainCommunicationError::from(simulation_error(
+                "Move abort in 0xabc::mailbox: 0x4".to_string()
+            )),
+            ChainCommunicationError::TransactionWouldRevert(_)
+        ));
+        for vm_status in ["OUT_OF_GAS", "INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE"] {
+            assert!(matches!(
+                ChainCommunicationError::from(simulation_error(vm_status.to_string())),
+                ChainCommunicationError::Other(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_decode_view_response() {
+        let response = vec![
+            json!(true),
+            json!("18446744073709551616"),
+            json!(7),
+            json!("0x15"),
+            json!(["0x1", "0x2"]),
+            json!("0x68656c6c6f"),
+        ];
+        assert!(decode_view_value::<bool>("f", &response, 0).unwrap());
+        assert_eq!(
+            decode_view_uint::<u128>("f", &response, 1).unwrap(),
+            u64::MAX as u128 + 1
+        );
+        assert_eq!(decode_view_uint::<u8>("f", &response, 2).unwrap(), 7);
+        assert_eq!(
+            decode_view_u256("f", &response, 1).unwrap(),
+            U256::from(u64::MAX) + 1
+        );
+        assert_eq!(
+            decode_view_address("f", &response, 3).unwrap(),
+            H256::from_low_u64_be(0x15)
+        );
+        assert_eq!(
+            decode_view_addresses("f", &response, 4).unwrap(),
+            vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)]
+        );
+        assert_eq!(decode_view_bytes("f", &response, 5).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_decode_malformed_view_response() {
+        let response = vec![json!("yes"), json!("-1"), json!(["0xzz"]), json!(null)];
+        assert!(decode_view_value::<bool>("f", &response, 0).is_err());
+        assert!(decode_view_uint::<u64>("f", &response, 1).is_err());
+        assert!(decode_view_uint::<u8>("f", &[json!("256")], 0).is_err());
+        assert!(decode_view_u256("f", &response, 1).is_err());
+        assert!(decode_view_address("f", &response, 0).is_err());
+        assert!(decode_view_addresses("f", &response, 2).is_err());
+        assert!(decode_view_bytes("f", &response, 3).is_err());
+        // missing return values
+        let err = decode_view_value::<bool>("mailbox::delivered", &response, 4).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unexpected response from view function mailbox::delivered: \
+             expected at least 5 return values, got 4"
+        );
+    }
 }
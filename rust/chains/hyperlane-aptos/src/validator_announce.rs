@@ -1,15 +1,15 @@
 #![allow(unused)]
 
+use crate::signers::AptosSigner;
 use aptos_sdk::crypto::ed25519::Ed25519PublicKey;
 use aptos_sdk::types::transaction::authenticator::AuthenticationKey;
 use async_trait::async_trait;
-use solana_sdk::signature::Keypair;
 use tracing::info;
 use tracing::{instrument, warn};
 
 use crate::utils::{self, send_aptos_transaction};
-use crate::{convert_hex_string_to_h256, convert_keypair_to_aptos_account, AptosClient};
-use crate::{simulate_aptos_transaction, ConnectionConf};
+use crate::{convert_hex_string_to_h256, AptosClient};
+use crate::{simulate_aptos_transaction, AptosNonceManager, ConnectionConf, TransactionOverrides};
 use hyperlane_core::{
     Announcement, ChainCommunicationError, ChainResult, ContractLocator, FixedPointNumber,
     HyperlaneChain, HyperlaneContract, HyperlaneDomain, SignedType, TxOutcome, ValidatorAnnounce,
@@ -36,6 +36,7 @@ use aptos_sdk::{
 use anyhow::{Context, Result};
 use once_cell::sync::Lazy;
 use std::str::FromStr;
+use std::sync::Arc;
 use url::Url;
 
 /// A reference to a ValidatorAnnounce contract on Aptos chain
@@ -43,67 +44,72 @@ use url::Url;
 pub struct AptosValidatorAnnounce {
     package_address: AccountAddress,
     aptos_client: AptosClient,
-    payer: Option<Keypair>,
+    signer: Option<Arc<AptosNonceManager>>,
     domain: HyperlaneDomain,
+    transaction_overrides: TransactionOverrides,
 }
 
 impl AptosValidatorAnnounce {
     /// Create a new Aptos ValidatorAnnounce
-    pub fn new(conf: &ConnectionConf, locator: ContractLocator, payer: Option<Keypair>) -> Self {
-        let aptos_client = AptosClient::new(conf.url.to_string());
-        let package_address =
-            AccountAddress::from_bytes(<[u8; 32]>::from(locator.address)).unwrap();
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        payer: Option<AptosSigner>,
+    ) -> Self {
+        let aptos_client = AptosClient::new(conf, &locator.domain);
+        let package_address = AccountAddress::new(locator.address.0);
         Self {
             package_address,
             aptos_client,
-            payer,
+            signer: payer.map(|payer| AptosNonceManager::for_signer(&aptos_client, &payer)),
             domain: locator.domain.clone(),
+            transaction_overrides: conf.transaction_overrides.clone(),
         }
     }
 
-    /// Returns a ContractCall that processes the provided message.
-    /// If the provided tx_gas_limit is None, gas estimation occurs.
-    #[allow(unused)]
+    /// Submits the announcement and returns the outcome of the transaction
     async fn announce_contract_call(
         &self,
         announcement: SignedType<Announcement>,
-    ) -> Result<(String, bool)> {
+    ) -> ChainResult<TxOutcome> {
         let serialized_signature: [u8; 65] = announcement.signature.into();
 
-        let payer = self
-            .payer
+        let signer = self
+            .signer
             .as_ref()
             .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
 
-        let mut signer_account = convert_keypair_to_aptos_account(&self.aptos_client, payer).await;
-
         let payload = utils::make_aptos_payload(
             self.package_address,
             "validator_announce",
             "announce",
             vec![],
             vec![
-                bcs::to_bytes(
-                    &AccountAddress::from_hex_literal(&format!(
-                        "0x{}",
-                        hex::encode(announcement.value.validator.as_bytes())
-                    ))
-                    .unwrap(),
-                )
+                bcs::to_bytes(&AccountAddress::new(
+                    H256::from(announcement.value.validator).0,
+                ))
                 .unwrap(),
                 bcs::to_bytes(&serialized_signature.to_vec()).unwrap(),
                 bcs::to_bytes(&announcement.value.storage_location).unwrap(),
             ],
         );
 
-        let response =
-            send_aptos_transaction(&self.aptos_client, &mut signer_account, payload.clone())
-                .await?;
-
-        // fetch transaction information from the response
-        let tx_hash = response.transaction_info().unwrap().hash.to_string();
-        let has_success = response.success();
-        Ok((tx_hash, has_success))
+        let transaction = match send_aptos_transaction(
+            &self.aptos_client,
+            signer,
+            payload,
+            &self.transaction_overrides,
+        )
+        .await
+        {
+            Ok(transaction) => transaction,
+            Err(err) => err.into_committed_transaction().map_err(|err| {
+                warn!(error = %err, "Failed to submit Aptos announce transaction");
+                ChainCommunicationError::from(err)
+            })?,
+        };
+
+        utils::tx_outcome_from_transaction(&transaction)
     }
 }
 
@@ -121,7 +127,7 @@ impl HyperlaneChain for AptosValidatorAnnounce {
     fn provider(&self) -> Box<dyn hyperlane_core::HyperlaneProvider> {
         Box::new(crate::AptosHpProvider::new(
             self.domain.clone(),
-            self.aptos_client.path_prefix_string(),
+            self.aptos_client.clone(),
         ))
     }
 }
@@ -134,13 +140,7 @@ impl ValidatorAnnounce for AptosValidatorAnnounce {
     ) -> ChainResult<Vec<Vec<String>>> {
         let validator_addresses: Vec<serde_json::Value> = validators
             .iter()
-            .map(|v| {
-                serde_json::Value::String(
-                    AccountAddress::from_bytes(v.as_bytes())
-                        .unwrap()
-                        .to_hex_literal(),
-                )
-            })
+            .map(|v| serde_json::Value::String(AccountAddress::new(v.0).to_hex_literal()))
             .collect();
 
         let view_response = utils::send_view_request(
@@ -153,8 +153,11 @@ impl ValidatorAnnounce for AptosValidatorAnnounce {
         )
         .await?;
 
-        let view_result = serde_json::from_str::<Vec<Vec<String>>>(&view_response[0].to_string());
-        let mut view_result = view_result.unwrap();
+        let mut view_result: Vec<Vec<String>> = utils::decode_view_value(
+            "validator_announce::get_announced_storage_locations",
+            &view_response,
+            0,
+        )?;
         if view_result.len() == 0 {
             view_result.push(vec![]);
         }
@@ -175,19 +178,6 @@ impl ValidatorAnnounce for AptosValidatorAnnounce {
             announcement
         );
 
-        let (tx_hash, is_success) =
-            self.announce_contract_call(announcement)
-                .await
-                .map_err(|e| {
-                    println!("tx error {}", e.to_string());
-                    ChainCommunicationError::TransactionTimeout()
-                })?;
-
-        Ok(TxOutcome {
-            transaction_id: H512::from(convert_hex_string_to_h256(&tx_hash).unwrap()),
-            executed: is_success,
-            gas_used: U256::zero(),
-            gas_price: FixedPointNumber::zero(),
-        })
+        self.announce_contract_call(announcement).await
     }
 }
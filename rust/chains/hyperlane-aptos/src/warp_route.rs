@@ -0,0 +1,577 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use aptos_sdk::{
+    move_types::{
+        identifier::Identifier,
+        language_storage::{StructTag, TypeTag},
+    },
+    rest_client::aptos_api_types::MoveType,
+    types::{account_address::AccountAddress, transaction::TransactionPayload},
+};
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, Decode, Encode, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProtocolError, TxOutcome, H256, U256,
+};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::utils::{self, send_aptos_transaction};
+use crate::{AptosClient, AptosNonceManager, ConnectionConf, TransactionOverrides};
+
+/// Message body sent by the synthetic and collateral token routers on
+/// `transfer_remote`. Shares its layout with the EVM and Sealevel token
+/// routers, with the amount in the decimals of the remote token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AptosTokenMessage {
+    recipient: H256,
+    amount_or_id: U256,
+    metadata: Vec<u8>,
+}
+
+impl Encode for AptosTokenMessage {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        writer.write_all(self.recipient.as_ref())?;
+
+        let mut amount_or_id = [0_u8; 32];
+        self.amount_or_id.to_big_endian(&mut amount_or_id);
+        writer.write_all(&amount_or_id)?;
+
+        writer.write_all(&self.metadata)?;
+
+        Ok(32 + 32 + self.metadata.len())
+    }
+}
+
+impl Decode for AptosTokenMessage {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+    {
+        let mut recipient = H256::zero();
+        reader.read_exact(recipient.as_mut())?;
+
+        let mut amount_or_id = [0_u8; 32];
+        reader.read_exact(&mut amount_or_id)?;
+        let amount_or_id = U256::from_big_endian(&amount_or_id);
+
+        let mut metadata = vec![];
+        reader.read_to_end(&mut metadata)?;
+
+        Ok(Self {
+            recipient,
+            amount_or_id,
+            metadata,
+        })
+    }
+}
+
+impl AptosTokenMessage {
+    /// Creates a new token message.
+    pub fn new(recipient: H256, amount_or_id: U256, metadata: Vec<u8>) -> Self {
+        Self {
+            recipient,
+            amount_or_id,
+            metadata,
+        }
+    }
+
+    /// The recipient of the token transfer.
+    pub fn recipient(&self) -> H256 {
+        self.recipient
+    }
+
+    /// The amount or ID of the token transfer.
+    pub fn amount(&self) -> U256 {
+        self.amount_or_id
+    }
+
+    /// The amount of the token transfer in the `decimals` of the Aptos token,
+    /// if it can be credited on Aptos, where coin and fungible asset balances
+    /// are `u64`.
+    pub fn aptos_amount(&self, remote_decimals: u8, decimals: u8) -> Option<u64> {
+        convert_decimals(self.amount_or_id, remote_decimals, decimals)
+            .filter(|amount| *amount <= U256::from(u64::MAX))
+            .map(|amount| amount.as_u64())
+    }
+
+    /// The metadata of the token transfer.
+    pub fn metadata(&self) -> &[u8] {
+        &self.metadata
+    }
+}
+
+/// Message body sent by native token routers on `transfer_remote`.
+/// Deployed native routes only carry the amount, as a little-endian `u64`,
+/// and credit the recipient of the Hyperlane message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AptosNativeTokenMessage {
+    amount: u64,
+}
+
+impl Encode for AptosNativeTokenMessage {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        writer.write_all(&self.amount.to_le_bytes())?;
+        Ok(8)
+    }
+}
+
+impl Decode for AptosNativeTokenMessage {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+    {
+        let mut amount = [0_u8; 8];
+        reader.read_exact(&mut amount)?;
+        Ok(Self {
+            amount: u64::from_le_bytes(amount),
+        })
+    }
+}
+
+impl AptosNativeTokenMessage {
+    /// Creates a new native token message.
+    pub fn new(amount: u64) -> Self {
+        Self { amount }
+    }
+
+    /// The amount of the token transfer, in octas.
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+}
+
+/// Converts an amount from one decimal representation to another.
+pub fn convert_decimals(amount: U256, from_decimals: u8, to_decimals: u8) -> Option<U256> {
+    match from_decimals.cmp(&to_decimals) {
+        Ordering::Greater => {
+            let divisor = U256::from(10u64).checked_pow(U256::from(from_decimals - to_decimals));
+            divisor.and_then(|d| amount.checked_div(d))
+        }
+        Ordering::Less => {
+            let multiplier = U256::from(10u64).checked_pow(U256::from(to_decimals - from_decimals));
+            multiplier.and_then(|m| amount.checked_mul(m))
+        }
+        Ordering::Equal => Some(amount),
+    }
+}
+
+/// Kind of an Aptos token router and the asset it bridges
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AptosTokenType {
+    /// Bridges APT, held in the router's beneficiary account
+    Native,
+    /// Mints and burns a fungible asset created by the router
+    Synthetic(AptosSyntheticMetadata),
+    /// Locks an existing fungible asset in the router's escrow account
+    Collateral(AptosCollateralInfo),
+}
+
+/// Fungible asset created by a synthetic token router
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AptosSyntheticMetadata {
+    /// Name of the asset
+    pub name: String,
+    /// Symbol of the asset, also the seed of its metadata object
+    pub symbol: String,
+    /// Decimals of the asset
+    pub decimals: u8,
+    /// Decimals of the token on the other chains, if they differ
+    pub remote_decimals: Option<u8>,
+}
+
+impl AptosSyntheticMetadata {
+    /// Decimals amounts are sent to other chains in
+    pub fn remote_decimals(&self) -> u8 {
+        self.remote_decimals.unwrap_or(self.decimals)
+    }
+}
+
+/// Fungible asset held by a collateral token router
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AptosCollateralInfo {
+    /// Address of the asset's metadata object
+    pub metadata: H256,
+    /// Decimals of the token on the other chains
+    pub remote_decimals: u8,
+}
+
+impl AptosTokenType {
+    /// Name of the Move module implementing the router
+    pub fn module_name(&self) -> &'static str {
+        match self {
+            Self::Native => "native_token",
+            Self::Synthetic(_) => "synthetic_token",
+            Self::Collateral(_) => "collateral_token",
+        }
+    }
+
+    /// Name of the struct the router is registered under in `hp_router`
+    pub fn struct_name(&self) -> &'static str {
+        match self {
+            Self::Native => "NativeToken",
+            Self::Synthetic(_) => "SyntheticToken",
+            Self::Collateral(_) => "CollateralToken",
+        }
+    }
+
+    /// Type argument of the `hp_router::router` calls made for a router
+    /// published at `package_address`
+    pub fn router_type_tag(&self, package_address: AccountAddress) -> TypeTag {
+        TypeTag::Struct(Box::new(StructTag {
+            address: package_address,
+            module: Identifier::new(self.module_name()).unwrap(),
+            name: Identifier::new(self.struct_name()).unwrap(),
+            type_params: vec![],
+        }))
+    }
+}
+
+/// A reference to a token router package on an Aptos chain
+#[derive(Debug)]
+pub struct AptosWarpRoute {
+    aptos_client: AptosClient,
+    package_address: AccountAddress,
+    router_address: AccountAddress,
+    token_type: AptosTokenType,
+    domain: HyperlaneDomain,
+}
+
+impl AptosWarpRoute {
+    /// Create a new Aptos warp route.
+    /// `router_address` is the address of the `hp_router` package
+    /// the token router registers itself with.
+    pub fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator,
+        router_address: H256,
+        token_type: AptosTokenType,
+    ) -> Self {
+        Self {
+            aptos_client: AptosClient::new(conf, &locator.domain),
+            package_address: AccountAddress::new(locator.address.0),
+            router_address: AccountAddress::new(router_address.0),
+            token_type,
+            domain: locator.domain.clone(),
+        }
+    }
+
+    /// Kind of the token router
+    pub fn token_type(&self) -> &AptosTokenType {
+        &self.token_type
+    }
+
+    /// Payload registering the router after its package is published.
+    /// Native routers register themselves on publish and need none.
+    pub fn initialize_payload(&self) -> Option<TransactionPayload> {
+        let args = match &self.token_type {
+            AptosTokenType::Native => return None,
+            AptosTokenType::Synthetic(metadata) => vec![
+                bcs::to_bytes(&metadata.name).unwrap(),
+                bcs::to_bytes(&metadata.symbol).unwrap(),
+                bcs::to_bytes(&metadata.decimals).unwrap(),
+                bcs::to_bytes(&metadata.remote_decimals()).unwrap(),
+            ],
+            AptosTokenType::Collateral(info) => vec![
+                bcs::to_bytes(&AccountAddress::new(info.metadata.0)).unwrap(),
+                bcs::to_bytes(&info.remote_decimals).unwrap(),
+            ],
+        };
+        Some(utils::make_aptos_payload(
+            self.package_address,
+            self.token_type.module_name(),
+            "initialize",
+            vec![],
+            args,
+        ))
+    }
+
+    /// Payload sending `amount` to `recipient` on `destination`
+    pub fn transfer_remote_payload(
+        &self,
+        destination: u32,
+        recipient: H256,
+        amount: u64,
+    ) -> TransactionPayload {
+        utils::make_aptos_payload(
+            self.package_address,
+            self.token_type.module_name(),
+            "transfer_remote",
+            vec![],
+            vec![
+                bcs::to_bytes(&destination).unwrap(),
+                bcs::to_bytes(&recipient.as_bytes().to_vec()).unwrap(),
+                bcs::to_bytes(&amount).unwrap(),
+            ],
+        )
+    }
+
+    /// Payload enrolling `routers` as the remote routers of their domains
+    pub fn enroll_remote_routers_payload(
+        &self,
+        routers: &BTreeMap<u32, H256>,
+    ) -> TransactionPayload {
+        let domains: Vec<u32> = routers.keys().copied().collect();
+        let routers: Vec<Vec<u8>> = routers
+            .values()
+            .map(|router| router.as_bytes().to_vec())
+            .collect();
+        utils::make_aptos_payload(
+            self.router_address,
+            "router",
+            "enroll_remote_routers",
+            vec![self.router_type_tag()],
+            vec![
+                bcs::to_bytes(&domains).unwrap(),
+                bcs::to_bytes(&routers).unwrap(),
+            ],
+        )
+    }
+
+    /// Payload setting the ISM the router's messages are verified with
+    pub fn set_recipient_ism_payload(&self, ism: H256) -> TransactionPayload {
+        utils::make_aptos_payload(
+            self.router_address,
+            "router",
+            "set_recipient_ism",
+            vec![self.router_type_tag()],
+            vec![bcs::to_bytes(&AccountAddress::new(ism.0)).unwrap()],
+        )
+    }
+
+    /// Whether the router is registered with `hp_router`
+    pub async fn is_initialized(&self) -> ChainResult<bool> {
+        let view_response = utils::send_view_request(
+            &self.aptos_client,
+            self.router_address.to_hex_literal(),
+            "router".to_string(),
+            "fetch_module_name".to_string(),
+            vec![],
+            vec![serde_json::json!(self.package_address.to_hex_literal())],
+        )
+        .await?;
+        let module_name = utils::decode_view_bytes("router::fetch_module_name", &view_response, 0)?;
+        Ok(!module_name.is_empty())
+    }
+
+    /// The remote routers currently enrolled, by domain
+    pub async fn remote_routers(&self) -> ChainResult<BTreeMap<u32, H256>> {
+        let domains = self.router_view("get_domains").await?;
+        let routers = self.router_view("get_routers").await?;
+        let domains: Vec<u32> =
+            serde_json::from_value(domains).map_err(ChainCommunicationError::from_other)?;
+        let routers: Vec<String> =
+            serde_json::from_value(routers).map_err(ChainCommunicationError::from_other)?;
+        if domains.len() != routers.len() {
+            return Err(ChainCommunicationError::ParseError {
+                msg: format!(
+                    "Got {} domains but {} remote routers",
+                    domains.len(),
+                    routers.len()
+                ),
+            });
+        }
+
+        domains
+            .into_iter()
+            .zip(routers)
+            .map(|(domain, router)| {
+                let router = hex::decode(router.trim_start_matches("0x"))
+                    .map_err(ChainCommunicationError::from_other)?;
+                if router.len() != H256::len_bytes() {
+                    return Err(ChainCommunicationError::ParseError {
+                        msg: format!("Remote router of domain {domain} is not 32 bytes"),
+                    });
+                }
+                Ok((domain, H256::from_slice(&router)))
+            })
+            .collect()
+    }
+
+    /// Initialize the router if needed and enroll the `remote_routers`
+    /// that are missing or differ from the ones on chain.
+    /// Returns the outcomes of the submitted transactions, which are
+    /// none if the router is already configured.
+    pub async fn configure(
+        &self,
+        signer: &AptosNonceManager,
+        overrides: &TransactionOverrides,
+        remote_routers: &BTreeMap<u32, H256>,
+    ) -> ChainResult<Vec<TxOutcome>> {
+        let mut outcomes = vec![];
+
+        let enrolled = if self.is_initialized().await? {
+            self.remote_routers().await?
+        } else {
+            let payload = self.initialize_payload().ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Native token router is not registered, was its package published?",
+                )
+            })?;
+            info!(package = %self.package_address, "Initializing Aptos token router");
+            outcomes.push(self.submit(signer, overrides, payload).await?);
+            BTreeMap::new()
+        };
+
+        let missing: BTreeMap<u32, H256> = remote_routers
+            .iter()
+            .filter(|(domain, router)| enrolled.get(domain) != Some(router))
+            .map(|(domain, router)| (*domain, *router))
+            .collect();
+        if !missing.is_empty() {
+            info!(package = %self.package_address, routers = ?missing, "Enrolling remote routers");
+            let payload = self.enroll_remote_routers_payload(&missing);
+            outcomes.push(self.submit(signer, overrides, payload).await?);
+        }
+
+        Ok(outcomes)
+    }
+
+    fn router_type_tag(&self) -> TypeTag {
+        self.token_type.router_type_tag(self.package_address)
+    }
+
+    async fn router_view(&self, function_name: &str) -> ChainResult<serde_json::Value> {
+        let mut view_response = utils::send_view_request(
+            &self.aptos_client,
+            self.router_address.to_hex_literal(),
+            "router".to_string(),
+            function_name.to_string(),
+            vec![MoveType::from(&self.router_type_tag())],
+            vec![],
+        )
+        .await?;
+        if view_response.is_empty() {
+            return Err(ChainCommunicationError::ParseError {
+                msg: format!("Empty response from router::{function_name}"),
+            });
+        }
+        Ok(view_response.swap_remove(0))
+    }
+
+    async fn submit(
+        &self,
+        signer: &AptosNonceManager,
+        overrides: &TransactionOverrides,
+        payload: TransactionPayload,
+    ) -> ChainResult<TxOutcome> {
+        let transaction = send_aptos_transaction(&self.aptos_client, signer, payload, overrides)
+            .await
+            .map_err(ChainCommunicationError::from)?;
+        utils::tx_outcome_from_transaction(&transaction)
+    }
+}
+
+impl HyperlaneContract for AptosWarpRoute {
+    fn address(&self) -> H256 {
+        H256(self.package_address.into_bytes())
+    }
+}
+
+impl HyperlaneChain for AptosWarpRoute {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn hyperlane_core::HyperlaneProvider> {
+        Box::new(crate::AptosHpProvider::new(
+            self.domain.clone(),
+            self.aptos_client.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_message_matches_move_layout() {
+        let recipient = H256::from_low_u64_be(0xb0b);
+        let message = AptosTokenMessage::new(recipient, U256::from(12), vec![]);
+        let bytes = message.to_vec();
+
+        // recipient followed by the big-endian u256 amount,
+        // as built by msg_utils::format_warp_token_message
+        let mut expected = recipient.as_bytes().to_vec();
+        expected.extend_from_slice(&[0; 31]);
+        expected.push(12);
+        assert_eq!(bytes, expected);
+
+        let decoded = AptosTokenMessage::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.aptos_amount(8, 8), Some(12));
+    }
+
+    #[test]
+    fn test_token_message_amount_in_remote_decimals() {
+        let message = AptosTokenMessage::new(H256::zero(), U256::from(120_000_000_001_u64), vec![]);
+        // 18 decimals EVM tokens to 8 decimals on Aptos, dropping the dust
+        assert_eq!(message.aptos_amount(18, 8), Some(12));
+        assert_eq!(
+            convert_decimals(U256::from(12), 8, 18),
+            Some(U256::from(120_000_000_000_u64))
+        );
+        assert_eq!(convert_decimals(U256::from(12), 0, 200), None);
+    }
+
+    #[test]
+    fn test_native_token_message_matches_move_layout() {
+        // as built by msg_utils::format_token_message
+        let message = AptosNativeTokenMessage::new(12);
+        let bytes = message.to_vec();
+        assert_eq!(bytes, [12, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            AptosNativeTokenMessage::read_from(&mut bytes.as_slice()).unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn test_token_message_amount_overflowing_u64() {
+        let message =
+            AptosTokenMessage::new(H256::zero(), U256::from(u64::MAX) + 1, b"metadata".to_vec());
+        let decoded = AptosTokenMessage::read_from(&mut message.to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.metadata(), b"metadata");
+        assert_eq!(decoded.aptos_amount(8, 8), None);
+    }
+
+    #[test]
+    fn test_token_type_config() {
+        let token_type: AptosTokenType = serde_json::from_value(serde_json::json!({
+            "type": "synthetic",
+            "name": "Wrapped Ether",
+            "symbol": "WETH",
+            "decimals": 8,
+            "remoteDecimals": 18,
+        }))
+        .unwrap();
+        assert_eq!(
+            token_type,
+            AptosTokenType::Synthetic(AptosSyntheticMetadata {
+                name: "Wrapped Ether".to_owned(),
+                symbol: "WETH".to_owned(),
+                decimals: 8,
+                remote_decimals: Some(18),
+            })
+        );
+
+        let package = AccountAddress::from_hex_literal("0x5147").unwrap();
+        match token_type.router_type_tag(package) {
+            TypeTag::Struct(tag) => {
+                assert_eq!(tag.address, package);
+                assert_eq!(tag.module.as_str(), "synthetic_token");
+                assert_eq!(tag.name.as_str(), "SyntheticToken");
+            }
+            tag => panic!("Expected a struct type tag, got {tag:?}"),
+        }
+    }
+}
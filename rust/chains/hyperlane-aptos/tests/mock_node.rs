@@ -0,0 +1,1526 @@
+//! Tests of the Aptos contracts and indexers against a mock Aptos node
+
+use std::collections::BTreeMap;
+use std::num::NonZeroU64;
+
+use aptos_sdk::types::{account_address::AccountAddress, transaction::TransactionPayload};
+use serde_json::{json, Value};
+use url::Url;
+
+use hyperlane_aptos::signers::AptosSigner;
+use hyperlane_aptos::{
+    AptosAggregationIsm, AptosClient, AptosClientMetrics, AptosHpProvider, AptosIndexerBackend,
+    AptosInterchainGasPaymaster, AptosInterchainGasPaymasterIndexer, AptosInterchainSecurityModule,
+    AptosMailbox, AptosMailboxIndexer, AptosMerkleTreeHookIndexer, AptosMultisigISM,
+    AptosNonceManager, AptosRoutingIsm, AptosSyntheticMetadata, AptosTokenType,
+    AptosValidatorAnnounce, AptosWarpRoute, ConnectionConf, TransactionOverrides,
+    APTOS_REQUEST_LABELS,
+};
+use hyperlane_aptos_test_utils::{MockAptosNode, MockBlock, TransactionOutcome};
+use hyperlane_core::{
+    AggregationIsm, Announcement, ChainCommunicationError, ContractLocator, Encode,
+    FixedPointNumber, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, IndexMode, Indexer,
+    InterchainGasPaymaster, InterchainGasPayment, InterchainSecurityModule, Mailbox,
+    MerkleTreeHook, MerkleTreeInsertion, ModuleType, MultisigIsm, RoutingIsm, SequenceAwareIndexer,
+    Signature, SignedType, ValidatorAnnounce, H160, H256, U256,
+};
+use prometheus::{opts, IntCounterVec};
+
+const PACKAGE: &str = "0x6bbae7820a27ff21f28ba5a4b64c8b746cdd95e2b3264a686dd15651ef90a2a1";
+
+fn package() -> H256 {
+    PACKAGE.parse().unwrap()
+}
+
+fn package_address() -> AccountAddress {
+    AccountAddress::new(package().0)
+}
+
+fn mailbox_state_tag() -> String {
+    format!("{PACKAGE}::mailbox::MailBoxState")
+}
+
+fn igp_state_tag() -> String {
+    format!("{PACKAGE}::igps::IgpState")
+}
+
+fn conf(node: &MockAptosNode) -> ConnectionConf {
+    ConnectionConf {
+        urls: vec![node.url()],
+        transaction_overrides: Default::default(),
+        external_submission: None,
+        client_metrics: Default::default(),
+        indexer_backend: Default::default(),
+    }
+}
+
+fn domain() -> HyperlaneDomain {
+    HyperlaneDomain::new_test_domain("aptostest")
+}
+
+fn locator(domain: &HyperlaneDomain) -> ContractLocator<'_> {
+    ContractLocator {
+        domain,
+        address: package(),
+    }
+}
+
+/// A signer with an on-chain account at `sequence_number`.
+/// Every test uses its own seed so signers never share a nonce manager.
+fn funded_signer(node: &MockAptosNode, seed: u8, sequence_number: u64) -> AptosSigner {
+    let signer = AptosSigner::from_encoded_key(&hex::encode([seed; 32])).unwrap();
+    node.state()
+        .set_sequence_number(signer.account_address(), sequence_number);
+    signer
+}
+
+fn message(nonce: u32) -> HyperlaneMessage {
+    HyperlaneMessage {
+        nonce,
+        origin: 14402,
+        destination: 14477,
+        sender: H256::from_low_u64_be(0xa11ce),
+        recipient: H256::from_low_u64_be(0xb0b),
+        body: b"hello".to_vec(),
+        ..Default::default()
+    }
+}
+
+fn h256(hash: &aptos_sdk::crypto::HashValue) -> H256 {
+    H256::from_slice(&hash.to_vec())
+}
+
+/// Emit a `DispatchEvent` for `message` in a new block
+fn dispatch(node: &MockAptosNode, message: &HyperlaneMessage) -> MockBlock {
+    let mut state = node.state();
+    let block = state.add_block(1);
+    state.emit_event(
+        package_address(),
+        &mailbox_state_tag(),
+        "dispatch_events",
+        block.first_version,
+        &format!("{PACKAGE}::mailbox::DispatchEvent"),
+        json!({
+            "dest_domain": message.destination,
+            "message": format!("0x{}", hex::encode(message.to_vec())),
+            "message_id": format!("{:?}", message.id()),
+            "recipient": format!("{:?}", message.recipient),
+            "block_height": block.height.to_string(),
+            "transaction_hash": block.hash.to_hex_literal(),
+            "sender": format!("{:?}", message.sender),
+        }),
+    );
+    block
+}
+
+/// Emit a `ProcessEvent` for `message` in a new block
+fn deliver(node: &MockAptosNode, message: &HyperlaneMessage) -> MockBlock {
+    let mut state = node.state();
+    let block = state.add_block(1);
+    state.emit_event(
+        package_address(),
+        &mailbox_state_tag(),
+        "process_events",
+        block.first_version,
+        &format!("{PACKAGE}::mailbox::ProcessEvent"),
+        json!({
+            "message_id": format!("{:?}", message.id()),
+            "origin_domain": message.origin,
+            "sender": format!("{:?}", message.sender),
+            "recipient": format!("{:?}", message.recipient),
+            "block_height": block.height.to_string(),
+            "transaction_hash": block.hash.to_hex_literal(),
+        }),
+    );
+    block
+}
+
+/// Script the view of the merkle tree to hold `count` leaves
+fn set_tree_count(node: &MockAptosNode, count: u32) {
+    let branch: Vec<Value> = (0..32)
+        .map(|_| json!(format!("{:?}", H256::zero())))
+        .collect();
+    node.state().set_view(
+        &format!("{PACKAGE}::mailbox::outbox_get_tree"),
+        vec![json!({ "branch": branch, "count": count.to_string() })],
+    );
+}
+
+/// Script every recipient to be a `hello_world` module
+fn set_recipient_module(node: &MockAptosNode) {
+    node.state().set_view(
+        &format!("{PACKAGE}::mailbox::recipient_module_name"),
+        vec![json!(format!("0x{}", hex::encode("hello_world")))],
+    );
+}
+
+/// Store the ISM state of `module` at `address`, the ISM package itself or
+/// an account hosting an ISM of it
+fn host_ism(node: &MockAptosNode, address: AccountAddress, module: &str, name: &str) {
+    node.state().set_resource(
+        address,
+        &format!("{PACKAGE}::{module}::{name}"),
+        json!({ "owner": address.to_hex_literal() }),
+    );
+}
+
+fn entry_function_name(payload: &TransactionPayload) -> String {
+    match payload {
+        TransactionPayload::EntryFunction(entry_function) => format!(
+            "{}::{}",
+            entry_function.module().name(),
+            entry_function.function()
+        ),
+        _ => panic!("Expected an entry function payload"),
+    }
+}
+
+#[tokio::test]
+async fn test_mailbox_views() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), None).unwrap();
+
+    let delivered_id = message(0).id();
+    let default_ism = H256::from_low_u64_be(0x15);
+    {
+        let mut state = node.state();
+        state.set_view_fn(&format!("{PACKAGE}::mailbox::delivered"), move |args| {
+            vec![json!(
+                args[0] == json!(hex::encode(delivered_id.as_bytes()))
+            )]
+        });
+        state.set_view(
+            &format!("{PACKAGE}::mailbox::get_default_ism"),
+            vec![json!(format!("{default_ism:?}"))],
+        );
+        state.set_view(
+            &format!("{PACKAGE}::mailbox::recipient_ism"),
+            vec![json!("0x0")],
+        );
+    }
+    set_tree_count(&node, 3);
+
+    assert!(mailbox.delivered(delivered_id).await.unwrap());
+    assert!(!mailbox.delivered(message(1).id()).await.unwrap());
+    assert_eq!(mailbox.default_ism().await.unwrap(), default_ism);
+    assert_eq!(
+        mailbox.recipient_ism(message(0).recipient).await.unwrap(),
+        default_ism
+    );
+    assert_eq!(Mailbox::count(&mailbox, None).await.unwrap(), 3);
+    assert_eq!(mailbox.latest_checkpoint(None).await.unwrap().index, 2);
+}
+
+#[tokio::test]
+async fn test_igp_quote_views() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let igp = AptosInterchainGasPaymaster::new(&conf(&node), &locator(&domain));
+
+    {
+        let mut state = node.state();
+        state.set_view_fn(&format!("{PACKAGE}::igps::quote_gas_payment"), |args| {
+            // 2 octas per gas with an overhead of 1000 gas to domain 97
+            let gas: u64 = args[1].as_str().unwrap().parse().unwrap();
+            let quote = if args[0] == json!(97) {
+                (gas + 1000) * 2
+            } else {
+                0
+            };
+            vec![json!(quote.to_string())]
+        });
+        state.set_view(
+            &format!("{PACKAGE}::gas_oracle::get_exchange_rate_and_gas_price"),
+            vec![json!("20000000000"), json!("1")],
+        );
+        state.set_view(
+            &format!("{PACKAGE}::igps::get_destination_gas_overhead"),
+            vec![json!("1000")],
+        );
+    }
+
+    assert_eq!(
+        igp.quote_gas_payment(97, U256::from(5000)).await.unwrap(),
+        Some(U256::from(12000))
+    );
+    assert_eq!(
+        igp.quote_gas_payment(56, U256::from(5000)).await.unwrap(),
+        Some(U256::zero())
+    );
+    assert_eq!(
+        igp.destination_gas_overhead(97).await.unwrap(),
+        Some(U256::from(1000))
+    );
+    let gas_data = igp.remote_gas_data(97).await.unwrap();
+    assert_eq!(gas_data.token_exchange_rate, 20_000_000_000);
+    assert_eq!(gas_data.gas_price, 1);
+}
+
+#[tokio::test]
+async fn test_malformed_view_responses_are_errors() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), None).unwrap();
+    let igp = AptosInterchainGasPaymaster::new(&conf(&node), &locator(&domain));
+    let ism = AptosMultisigISM::new(&conf(&node), locator(&domain), None);
+    host_ism(&node, package_address(), "multisig_ism", "ISM");
+    {
+        let mut state = node.state();
+        state.set_view(
+            &format!("{PACKAGE}::mailbox::delivered"),
+            vec![json!("yes")],
+        );
+        state.set_view(&format!("{PACKAGE}::mailbox::get_default_ism"), vec![]);
+        state.set_view(
+            &format!("{PACKAGE}::mailbox::outbox_get_tree"),
+            vec![json!({ "branch": ["not a hash"], "count": "1" })],
+        );
+        state.set_view(
+            &format!("{PACKAGE}::igps::quote_gas_payment"),
+            vec![json!({ "value": "12" })],
+        );
+        state.set_view(
+            &format!("{PACKAGE}::multisig_ism::validators_and_threshold"),
+            vec![json!(["0x1"]), json!("many")],
+        );
+    }
+
+    assert!(mailbox.delivered(message(0).id()).await.is_err());
+    assert!(mailbox.default_ism().await.is_err());
+    assert!(mailbox.latest_checkpoint(None).await.is_err());
+    assert!(igp.quote_gas_payment(97, U256::from(5000)).await.is_err());
+    assert!(ism.validators_and_threshold(&message(0)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_provider_unknown_transaction() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let provider = AptosHpProvider::new(domain.clone(), AptosClient::new(&conf(&node), &domain));
+
+    let err = provider
+        .get_txn_by_hash(&H256::from_low_u64_be(0xdead))
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("Could not find object"));
+}
+
+#[tokio::test]
+async fn test_provider_block_by_hash() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let client = AptosClient::new(&conf(&node), &domain);
+    let provider = AptosHpProvider::new(domain.clone(), client.clone());
+    let block = {
+        let mut state = node.state();
+        state.add_blocks(10);
+        state.block(5).unwrap().clone()
+    };
+    let hash = h256(&block.hash);
+
+    // Without an indexer, only blocks seen while indexing are known by hash
+    let err = provider.get_block_by_hash(&hash).await.unwrap_err();
+    assert!(err.to_string().contains("Could not find object"));
+
+    // The indexer knows blocks this process hasn't seen
+    let graphql_provider = AptosHpProvider::new(
+        domain.clone(),
+        AptosClient::new(&graphql_conf(&node), &domain),
+    );
+    let info = graphql_provider.get_block_by_hash(&hash).await.unwrap();
+    assert_eq!(info.number, 5);
+    assert_eq!(info.hash, hash);
+    assert_eq!(
+        node.state().graphql_operations(),
+        ["BlockByHash".to_owned()]
+    );
+
+    // The block it found is cached for every client of the node
+    let info = provider.get_block_by_hash(&hash).await.unwrap();
+    assert_eq!(info.number, 5);
+
+    let err = graphql_provider
+        .get_block_by_hash(&H256::repeat_byte(0xff))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Could not find object"));
+}
+
+/// Url of a local port nothing listens on
+fn unreachable_url() -> Url {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{addr}/v1").parse().unwrap()
+}
+
+#[tokio::test]
+async fn test_client_falls_back_to_next_endpoint() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let dead_url = unreachable_url();
+    let metrics = AptosClientMetrics {
+        request_count: Some(
+            IntCounterVec::new(opts!("request_count", "help"), APTOS_REQUEST_LABELS).unwrap(),
+        ),
+        request_duration_seconds: None,
+    };
+    let conf = ConnectionConf {
+        urls: vec![dead_url.clone(), node.url()],
+        client_metrics: metrics.clone(),
+        ..conf(&node)
+    };
+    let mailbox = AptosMailbox::new(&conf, locator(&domain), None).unwrap();
+
+    let default_ism = H256::from_low_u64_be(0x15);
+    node.state().set_view(
+        &format!("{PACKAGE}::mailbox::get_default_ism"),
+        vec![json!(format!("{default_ism:?}"))],
+    );
+
+    assert_eq!(mailbox.default_ism().await.unwrap(), default_ism);
+
+    let request_count = metrics.request_count.unwrap();
+    let count = |url: &Url, status: &str| {
+        let host = format!("{}:{}", url.host_str().unwrap(), url.port().unwrap());
+        request_count
+            .with_label_values(&[&host, "aptostest", "view", status])
+            .get()
+    };
+    assert_eq!(count(&dead_url, "failure"), 1);
+    assert_eq!(count(&node.url(), "success"), 1);
+}
+
+#[tokio::test]
+async fn test_client_fails_after_all_endpoints_fail() {
+    let domain = domain();
+    let conf = ConnectionConf {
+        urls: vec![unreachable_url(), unreachable_url()],
+        transaction_overrides: Default::default(),
+        external_submission: None,
+        client_metrics: Default::default(),
+        indexer_backend: Default::default(),
+    };
+    let mailbox = AptosMailbox::new(&conf, locator(&domain), None).unwrap();
+
+    assert!(mailbox.default_ism().await.is_err());
+}
+
+#[tokio::test]
+async fn test_client_does_not_retry_client_errors() {
+    let node = MockAptosNode::spawn();
+    let other_node = MockAptosNode::spawn();
+    let domain = domain();
+    let conf = ConnectionConf {
+        urls: vec![node.url(), other_node.url()],
+        ..conf(&node)
+    };
+    let mailbox = AptosMailbox::new(&conf, locator(&domain), None).unwrap();
+    other_node.state().set_view(
+        &format!("{PACKAGE}::mailbox::get_default_ism"),
+        vec![json!(format!("{:?}", H256::from_low_u64_be(0x15)))],
+    );
+
+    // The first node doesn't know the view function and answers with a 400
+    assert!(mailbox.default_ism().await.is_err());
+    assert_eq!(node.state().view_calls().len(), 1);
+    assert!(other_node.state().view_calls().is_empty());
+}
+
+#[tokio::test]
+async fn test_merkle_tree_hook_lagged_reads() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), None).unwrap();
+    node.state().add_blocks(10);
+    set_tree_count(&node, 5);
+
+    let lag = NonZeroU64::new(3);
+    assert_eq!(MerkleTreeHook::count(&mailbox, lag).await.unwrap(), 5);
+    assert_eq!(mailbox.latest_checkpoint(lag).await.unwrap().index, 4);
+    assert_eq!(MerkleTreeHook::count(&mailbox, None).await.unwrap(), 5);
+
+    let state = node.state();
+    let lagged_version = state.block(7).unwrap().last_version;
+    let ledger_versions: Vec<Option<u64>> = state
+        .view_calls()
+        .iter()
+        .map(|call| call.ledger_version)
+        .collect();
+    assert_eq!(
+        ledger_versions,
+        vec![Some(lagged_version), Some(lagged_version), None]
+    );
+}
+
+#[tokio::test]
+async fn test_mailbox_process_submits_handle_message() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 1, 3);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer.clone())).unwrap();
+    set_recipient_module(&node);
+    node.state().gas_used = 1234;
+
+    let outcome = mailbox
+        .process(&message(0), b"metadata", None)
+        .await
+        .unwrap();
+
+    assert!(outcome.executed);
+    assert_eq!(outcome.gas_used, U256::from(1234));
+    let state = node.state();
+    let submitted = state.submitted_transactions();
+    assert_eq!(submitted.len(), 1);
+    assert_eq!(submitted[0].sender(), signer.account_address());
+    assert_eq!(submitted[0].sequence_number(), 3);
+    assert_eq!(
+        entry_function_name(submitted[0].payload()),
+        "hello_world::handle_message"
+    );
+    assert_eq!(state.sequence_number(signer.account_address()), Some(4));
+}
+
+#[tokio::test]
+async fn test_mailbox_process_reports_move_abort_as_not_executed() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 2, 0);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer)).unwrap();
+    set_recipient_module(&node);
+    node.state()
+        .push_submit_outcome(TransactionOutcome::Failure {
+            vm_status: "Move abort in 0xb0b::hello_world: 0x1".to_owned(),
+        });
+
+    let outcome = mailbox.process(&message(0), &[], None).await.unwrap();
+
+    assert!(!outcome.executed);
+    assert_eq!(outcome.gas_used, U256::from(1000));
+}
+
+#[tokio::test]
+async fn test_mailbox_process_reports_mempool_rejection() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 2, 0);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer)).unwrap();
+    set_recipient_module(&node);
+    node.state()
+        .push_submit_outcome(TransactionOutcome::Rejection {
+            error_code: "mempool_is_full".to_owned(),
+            message: "Mempool is full".to_owned(),
+        });
+
+    let err = mailbox.process(&message(0), &[], None).await.unwrap_err();
+
+    assert!(err.to_string().contains("rejected by mempool"));
+    assert!(node.state().submitted_transactions().is_empty());
+}
+
+#[tokio::test]
+async fn test_mailbox_process_resyncs_stale_sequence_number() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 3, 0);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer.clone())).unwrap();
+    set_recipient_module(&node);
+
+    mailbox.process(&message(0), &[], None).await.unwrap();
+    // another process uses the same key
+    node.state()
+        .set_sequence_number(signer.account_address(), 5);
+    let outcome = mailbox.process(&message(1), &[], None).await.unwrap();
+
+    assert!(outcome.executed);
+    let state = node.state();
+    let sequence_numbers: Vec<u64> = state
+        .submitted_transactions()
+        .iter()
+        .map(|tx| tx.sequence_number())
+        .collect();
+    assert_eq!(sequence_numbers, vec![0, 5]);
+}
+
+#[tokio::test]
+async fn test_mailbox_process_estimate_costs_simulates() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 4, 7);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer)).unwrap();
+    set_recipient_module(&node);
+    node.state().gas_used = 4321;
+
+    let estimate = mailbox
+        .process_estimate_costs(&message(0), &[])
+        .await
+        .unwrap();
+
+    // 20% buffer on top of the simulated gas, priced at the node's estimate
+    assert_eq!(estimate.gas_limit, U256::from(5185));
+    assert_eq!(estimate.gas_price, FixedPointNumber::from(100u64));
+    let state = node.state();
+    assert_eq!(state.simulated_transactions().len(), 1);
+    assert_eq!(state.simulated_transactions()[0].sequence_number(), 7);
+    assert!(state.submitted_transactions().is_empty());
+}
+
+#[tokio::test]
+async fn test_mailbox_process_estimate_costs_overrides() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 4, 0);
+    let conf = ConnectionConf {
+        transaction_overrides: TransactionOverrides {
+            gas_unit_price: Some(150),
+            gas_buffer_percent: Some(50),
+            ..Default::default()
+        },
+        ..conf(&node)
+    };
+    let mailbox = AptosMailbox::new(&conf, locator(&domain), Some(signer)).unwrap();
+    set_recipient_module(&node);
+    node.state().gas_used = 1000;
+
+    let estimate = mailbox
+        .process_estimate_costs(&message(0), &[])
+        .await
+        .unwrap();
+
+    assert_eq!(estimate.gas_limit, U256::from(1500));
+    assert_eq!(estimate.gas_price, FixedPointNumber::from(150u64));
+    assert_eq!(
+        node.state().simulated_transactions()[0].gas_unit_price(),
+        150
+    );
+}
+
+#[tokio::test]
+async fn test_mailbox_process_estimate_costs_would_revert() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 4, 0);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer)).unwrap();
+    set_recipient_module(&node);
+    node.state()
+        .push_simulation_outcome(TransactionOutcome::Failure {
+            vm_status: format!("Move abort in {PACKAGE}::mailbox: 0x7"),
+        });
+
+    let err = mailbox
+        .process_estimate_costs(&message(0), &[])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        ChainCommunicationError::TransactionWouldRevert(_)
+    ));
+    assert!(err.to_string().contains("with code 7"));
+}
+
+#[tokio::test]
+async fn test_mailbox_process_estimate_costs_out_of_gas() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 4, 0);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer)).unwrap();
+    set_recipient_module(&node);
+    node.state()
+        .push_simulation_outcome(TransactionOutcome::Failure {
+            vm_status: "OUT_OF_GAS".to_owned(),
+        });
+
+    let err = mailbox
+        .process_estimate_costs(&message(0), &[])
+        .await
+        .unwrap_err();
+
+    // Running out of gas says nothing about the message, it's a gas estimation error
+    assert!(matches!(err, ChainCommunicationError::Other(_)));
+    assert!(err.to_string().contains("OUT_OF_GAS"));
+}
+
+#[tokio::test]
+async fn test_mailbox_process_estimate_costs_without_recipient_module() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 4, 0);
+    let mailbox = AptosMailbox::new(&conf(&node), locator(&domain), Some(signer)).unwrap();
+    node.state().set_view(
+        &format!("{PACKAGE}::mailbox::recipient_module_name"),
+        vec![json!("0x")],
+    );
+
+    let err = mailbox
+        .process_estimate_costs(&message(0), &[])
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("has no module registered"));
+    assert!(node.state().simulated_transactions().is_empty());
+}
+
+#[tokio::test]
+async fn test_mailbox_indexer() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let conf = conf(&node);
+    let indexer = AptosMailboxIndexer::new(&conf, locator(&domain), IndexMode::Block).unwrap();
+
+    let blocks: Vec<MockBlock> = (0..3)
+        .map(|nonce| dispatch(&node, &message(nonce)))
+        .collect();
+    let delivery = deliver(&node, &message(0));
+    node.state().add_blocks(2);
+    set_tree_count(&node, 3);
+
+    let dispatched = Indexer::<HyperlaneMessage>::fetch_logs_in_range(
+        &indexer,
+        blocks[1].height as u32..=blocks[2].height as u32,
+    )
+    .await
+    .unwrap();
+    assert_eq!(dispatched.len(), 2);
+    for ((indexed, meta), (nonce, block)) in
+        dispatched.iter().zip([(1, &blocks[1]), (2, &blocks[2])])
+    {
+        assert_eq!(indexed.inner(), &message(nonce));
+        assert_eq!(indexed.sequence, Some(nonce));
+        assert_eq!(meta.block_number, block.height);
+        assert_eq!(meta.block_hash, h256(&block.hash));
+        assert_eq!(meta.address, package());
+    }
+
+    let delivered = Indexer::<H256>::fetch_logs_in_range(
+        &indexer,
+        delivery.height as u32..=delivery.height as u32,
+    )
+    .await
+    .unwrap();
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].0.inner(), &message(0).id());
+
+    let tip = node.state().latest_block().height as u32;
+    assert_eq!(
+        SequenceAwareIndexer::<HyperlaneMessage>::latest_sequence_count_and_tip(&indexer)
+            .await
+            .unwrap(),
+        (Some(3), tip)
+    );
+    assert_eq!(
+        SequenceAwareIndexer::<H256>::latest_sequence_count_and_tip(&indexer)
+            .await
+            .unwrap(),
+        (Some(1), tip)
+    );
+}
+
+fn graphql_conf(node: &MockAptosNode) -> ConnectionConf {
+    ConnectionConf {
+        indexer_backend: AptosIndexerBackend::Graphql(node.graphql_url()),
+        ..conf(node)
+    }
+}
+
+#[tokio::test]
+async fn test_mailbox_indexer_graphql_matches_rest() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let graphql_indexer =
+        AptosMailboxIndexer::new(&graphql_conf(&node), locator(&domain), IndexMode::Block).unwrap();
+    let rest_indexer =
+        AptosMailboxIndexer::new(&conf(&node), locator(&domain), IndexMode::Block).unwrap();
+
+    let first = dispatch(&node, &message(0));
+    dispatch(&node, &message(1));
+    let delivery = deliver(&node, &message(0));
+    let last = dispatch(&node, &message(2));
+    node.state().add_blocks(2);
+    let range = first.height as u32..=last.height as u32;
+
+    // the GraphQL indexer runs first so blocks aren't cached yet
+    let dispatched =
+        Indexer::<HyperlaneMessage>::fetch_logs_in_range(&graphql_indexer, range.clone())
+            .await
+            .unwrap();
+    assert_eq!(
+        node.state().graphql_operations(),
+        ["Events".to_owned(), "BlockHashes".to_owned()]
+    );
+    assert_eq!(dispatched.len(), 3);
+    assert_eq!(
+        dispatched,
+        Indexer::<HyperlaneMessage>::fetch_logs_in_range(&rest_indexer, range.clone())
+            .await
+            .unwrap()
+    );
+
+    let delivered = Indexer::<H256>::fetch_logs_in_range(&graphql_indexer, range.clone())
+        .await
+        .unwrap();
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].1.block_number, delivery.height);
+    assert_eq!(
+        delivered,
+        Indexer::<H256>::fetch_logs_in_range(&rest_indexer, range)
+            .await
+            .unwrap()
+    );
+
+    // blocks outside the range hold no events
+    assert!(Indexer::<HyperlaneMessage>::fetch_logs_in_range(
+        &graphql_indexer,
+        last.height as u32 + 1..=last.height as u32 + 2
+    )
+    .await
+    .unwrap()
+    .is_empty());
+}
+
+#[tokio::test]
+async fn test_mailbox_indexer_sequence_mode() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let block_indexer =
+        AptosMailboxIndexer::new(&conf(&node), locator(&domain), IndexMode::Block).unwrap();
+
+    let first = dispatch(&node, &message(0));
+    let delivery = deliver(&node, &message(0));
+    dispatch(&node, &message(1));
+    let last = dispatch(&node, &message(2));
+    node.state().add_blocks(2);
+    let by_block = Indexer::<HyperlaneMessage>::fetch_logs_in_range(
+        &block_indexer,
+        first.height as u32..=last.height as u32,
+    )
+    .await
+    .unwrap();
+
+    for conf in [conf(&node), graphql_conf(&node)] {
+        let indexer =
+            AptosMailboxIndexer::new(&conf, locator(&domain), IndexMode::Sequence).unwrap();
+
+        let dispatched = Indexer::<HyperlaneMessage>::fetch_logs_in_range(&indexer, 1..=2)
+            .await
+            .unwrap();
+        assert_eq!(dispatched, by_block[1..]);
+        assert_eq!(
+            dispatched
+                .iter()
+                .map(|(indexed, _)| indexed.sequence)
+                .collect::<Vec<_>>(),
+            [Some(1), Some(2)]
+        );
+
+        let delivered = Indexer::<H256>::fetch_logs_in_range(&indexer, 0..=0)
+            .await
+            .unwrap();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].0.inner(), &message(0).id());
+        assert_eq!(delivered[0].1.block_number, delivery.height);
+
+        // sequences past the last event hold no events
+        assert!(
+            Indexer::<HyperlaneMessage>::fetch_logs_in_range(&indexer, 3..=5)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+    assert_eq!(
+        node.state().graphql_operations(),
+        [
+            "EventsBySequence".to_owned(),
+            "EventsBySequence".to_owned(),
+            "EventsBySequence".to_owned(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_graphql_indexer_errors() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let conf = ConnectionConf {
+        indexer_backend: AptosIndexerBackend::Graphql(node.url().join("missing").unwrap()),
+        ..conf(&node)
+    };
+    let indexer = AptosMailboxIndexer::new(&conf, locator(&domain), IndexMode::Block).unwrap();
+    let block = dispatch(&node, &message(0));
+
+    assert!(Indexer::<HyperlaneMessage>::fetch_logs_in_range(
+        &indexer,
+        block.height as u32..=block.height as u32
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_graphql_indexer_tip_follows_lagging_indexer() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let indexer =
+        AptosMailboxIndexer::new(&graphql_conf(&node), locator(&domain), IndexMode::Block).unwrap();
+    let rest_indexer =
+        AptosMailboxIndexer::new(&conf(&node), locator(&domain), IndexMode::Block).unwrap();
+    let indexed = dispatch(&node, &message(0));
+    let partially_indexed = node.state().add_block(3);
+    let unindexed = dispatch(&node, &message(1));
+    set_tree_count(&node, 2);
+    let node_tip = node.state().latest_block().height as u32;
+
+    // the indexer stopped in the middle of a block
+    node.state().indexed_version = Some(partially_indexed.first_version + 1);
+    let tip = Indexer::<HyperlaneMessage>::get_finalized_block_number(&indexer)
+        .await
+        .unwrap();
+    assert_eq!(tip, indexed.height as u32);
+    assert_eq!(
+        SequenceAwareIndexer::<HyperlaneMessage>::latest_sequence_count_and_tip(&indexer)
+            .await
+            .unwrap(),
+        (Some(2), tip)
+    );
+    assert_eq!(
+        node.state().graphql_operations(),
+        ["ProcessorStatus".to_owned(), "ProcessorStatus".to_owned()]
+    );
+    // the node is ahead, indexing up to its tip would skip the unindexed message
+    assert_eq!(
+        Indexer::<HyperlaneMessage>::get_finalized_block_number(&rest_indexer)
+            .await
+            .unwrap(),
+        node_tip
+    );
+    assert!(Indexer::<HyperlaneMessage>::fetch_logs_in_range(
+        &indexer,
+        unindexed.height as u32..=unindexed.height as u32
+    )
+    .await
+    .unwrap()
+    .is_empty());
+
+    // the indexer caught up with the block
+    node.state().indexed_version = Some(partially_indexed.last_version);
+    assert_eq!(
+        Indexer::<HyperlaneMessage>::get_finalized_block_number(&indexer)
+            .await
+            .unwrap(),
+        partially_indexed.height as u32
+    );
+
+    // the indexer caught up with the node
+    node.state().indexed_version = None;
+    assert_eq!(
+        Indexer::<HyperlaneMessage>::get_finalized_block_number(&indexer)
+            .await
+            .unwrap(),
+        node_tip
+    );
+    let dispatched = Indexer::<HyperlaneMessage>::fetch_logs_in_range(
+        &indexer,
+        unindexed.height as u32..=unindexed.height as u32,
+    )
+    .await
+    .unwrap();
+    assert_eq!(dispatched.len(), 1);
+    assert_eq!(dispatched[0].0.inner(), &message(1));
+}
+
+#[tokio::test]
+async fn test_merkle_tree_hook_indexer() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let conf = conf(&node);
+    let indexer = AptosMerkleTreeHookIndexer::new(
+        AptosMailboxIndexer::new(&conf, locator(&domain), IndexMode::Block).unwrap(),
+    );
+    let first = dispatch(&node, &message(0));
+    let last = dispatch(&node, &message(1));
+
+    let insertions = Indexer::<MerkleTreeInsertion>::fetch_logs_in_range(
+        &indexer,
+        first.height as u32..=last.height as u32,
+    )
+    .await
+    .unwrap();
+
+    let insertions: Vec<(u32, H256)> = insertions
+        .iter()
+        .map(|(insertion, _)| (insertion.inner().index(), insertion.inner().message_id()))
+        .collect();
+    assert_eq!(insertions, vec![(0, message(0).id()), (1, message(1).id())]);
+}
+
+#[tokio::test]
+async fn test_interchain_gas_paymaster_indexer() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let indexer =
+        AptosInterchainGasPaymasterIndexer::new(&conf(&node), locator(&domain), IndexMode::Block);
+    let block = {
+        let mut state = node.state();
+        state.add_blocks(2);
+        let block = state.add_block(1);
+        state.emit_event(
+            package_address(),
+            &igp_state_tag(),
+            "gas_payment_events",
+            block.first_version,
+            &format!("{PACKAGE}::igps::GasPaymentEvent"),
+            json!({
+                "dest_domain": 14477,
+                "message_id": format!("{:?}", message(0).id()),
+                "gas_amount": "50000",
+                "required_payment": "2500000",
+                "block_height": block.height.to_string(),
+                "transaction_hash": block.hash.to_hex_literal(),
+            }),
+        );
+        block
+    };
+
+    // blocks before the payment hold no events
+    assert!(indexer.fetch_logs_in_range(1..=2).await.unwrap().is_empty());
+    let payments = indexer
+        .fetch_logs_in_range(1..=block.height as u32)
+        .await
+        .unwrap();
+
+    assert_eq!(payments.len(), 1);
+    assert_eq!(
+        payments[0].0.inner(),
+        &InterchainGasPayment {
+            message_id: message(0).id(),
+            destination: 14477,
+            payment: U256::from(2_500_000),
+            gas_amount: U256::from(50_000),
+        }
+    );
+    assert_eq!(payments[0].1.block_number, block.height);
+    assert_eq!(
+        indexer.latest_sequence_count_and_tip().await.unwrap(),
+        (Some(1), block.height as u32)
+    );
+}
+
+#[tokio::test]
+async fn test_interchain_gas_paymaster_indexer_graphql() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let indexer = AptosInterchainGasPaymasterIndexer::new(
+        &graphql_conf(&node),
+        locator(&domain),
+        IndexMode::Block,
+    );
+    let block = {
+        let mut state = node.state();
+        let block = state.add_block(1);
+        state.emit_event(
+            package_address(),
+            &igp_state_tag(),
+            "gas_payment_events",
+            block.first_version,
+            &format!("{PACKAGE}::igps::GasPaymentEvent"),
+            json!({
+                "dest_domain": 14477,
+                "message_id": format!("{:?}", message(0).id()),
+                "gas_amount": "50000",
+                "required_payment": "2500000",
+                "block_height": block.height.to_string(),
+                "transaction_hash": block.hash.to_hex_literal(),
+            }),
+        );
+        block
+    };
+
+    let payments = indexer
+        .fetch_logs_in_range(1..=block.height as u32)
+        .await
+        .unwrap();
+
+    assert_eq!(payments.len(), 1);
+    assert_eq!(payments[0].0.inner().message_id, message(0).id());
+    assert_eq!(payments[0].0.sequence, Some(0));
+    assert_eq!(payments[0].1.block_hash, h256(&block.hash));
+    assert_eq!(
+        payments,
+        AptosInterchainGasPaymasterIndexer::new(&conf(&node), locator(&domain), IndexMode::Block)
+            .fetch_logs_in_range(1..=block.height as u32)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_interchain_gas_paymaster_indexer_sequence_mode() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let blocks: Vec<MockBlock> = (0..3)
+        .map(|nonce| {
+            let mut state = node.state();
+            let block = state.add_block(1);
+            state.emit_event(
+                package_address(),
+                &igp_state_tag(),
+                "gas_payment_events",
+                block.first_version,
+                &format!("{PACKAGE}::igps::GasPaymentEvent"),
+                json!({
+                    "dest_domain": 14477,
+                    "message_id": format!("{:?}", message(nonce).id()),
+                    "gas_amount": "50000",
+                    "required_payment": "2500000",
+                    "block_height": block.height.to_string(),
+                    "transaction_hash": block.hash.to_hex_literal(),
+                }),
+            );
+            block
+        })
+        .collect();
+
+    for conf in [conf(&node), graphql_conf(&node)] {
+        let indexer =
+            AptosInterchainGasPaymasterIndexer::new(&conf, locator(&domain), IndexMode::Sequence);
+        let payments = indexer.fetch_logs_in_range(1..=2).await.unwrap();
+
+        assert_eq!(
+            payments
+                .iter()
+                .map(|(payment, meta)| (
+                    payment.inner().message_id,
+                    payment.sequence,
+                    meta.block_number
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (message(1).id(), Some(1), blocks[1].height),
+                (message(2).id(), Some(2), blocks[2].height),
+            ]
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_validator_announce() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 5, 0);
+    let validator_announce =
+        AptosValidatorAnnounce::new(&conf(&node), locator(&domain), Some(signer));
+    node.state().set_view(
+        &format!("{PACKAGE}::validator_announce::get_announced_storage_locations"),
+        vec![json!([["s3://hyperlane-validator/aptostest"]])],
+    );
+
+    let validator = H256::from(H160::from_low_u64_be(0xdead));
+    assert_eq!(
+        validator_announce
+            .get_announced_storage_locations(&[validator])
+            .await
+            .unwrap(),
+        vec![vec!["s3://hyperlane-validator/aptostest".to_owned()]]
+    );
+
+    let announcement = SignedType {
+        value: Announcement {
+            validator: H160::from_low_u64_be(0xdead),
+            mailbox_address: package(),
+            mailbox_domain: domain.id(),
+            storage_location: "file:///tmp/checkpoints".to_owned(),
+        },
+        signature: Signature {
+            r: U256::one(),
+            s: U256::one(),
+            v: 27,
+        },
+    };
+    let outcome = validator_announce.announce(announcement).await.unwrap();
+
+    assert!(outcome.executed);
+    let state = node.state();
+    assert_eq!(
+        entry_function_name(state.submitted_transactions()[0].payload()),
+        "validator_announce::announce"
+    );
+}
+
+#[tokio::test]
+async fn test_multisig_ism_validators_and_threshold() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let ism = AptosMultisigISM::new(&conf(&node), locator(&domain), None);
+    host_ism(&node, package_address(), "multisig_ism", "ISM");
+    let validators = vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)];
+    node.state().set_view_fn(
+        &format!("{PACKAGE}::multisig_ism::validators_and_threshold"),
+        |args| {
+            // validators are configured per origin domain
+            if args != [json!(14402)] {
+                return vec![json!([]), json!("0")];
+            }
+            vec![
+                json!([
+                    "0x0000000000000000000000000000000000000000000000000000000000000001",
+                    "0x2"
+                ]),
+                json!("2"),
+            ]
+        },
+    );
+
+    assert_eq!(
+        ism.validators_and_threshold(&message(0)).await.unwrap(),
+        (validators, 2)
+    );
+}
+
+#[tokio::test]
+async fn test_hosted_multisig_ism_validators_and_threshold() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let hosted = H256::from_low_u64_be(0xb0b);
+    host_ism(&node, AccountAddress::new(hosted.0), "multisig_ism", "ISM");
+    let ism = AptosMultisigISM::new(
+        &conf(&node),
+        ContractLocator {
+            domain: &domain,
+            address: hosted,
+        },
+        None,
+    );
+    node.state().set_view(
+        &format!("{PACKAGE}::multisig_ism::validators_and_threshold_at"),
+        vec![json!(["0x1"]), json!("1")],
+    );
+
+    assert_eq!(
+        ism.validators_and_threshold(&message(0)).await.unwrap(),
+        (vec![H256::from_low_u64_be(1)], 1)
+    );
+    // hosted ISMs are viewed through the package, with their address first
+    let calls = node.state().view_calls().to_vec();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(
+        calls[0].arguments,
+        vec![
+            json!(AccountAddress::new(hosted.0).to_hex_literal()),
+            json!(14402)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_routing_ism_route() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let ism = AptosRoutingIsm::new(&conf(&node), locator(&domain));
+    host_ism(&node, package_address(), "domain_routing_ism", "RoutingIsm");
+    node.state()
+        .set_view_fn(&format!("{PACKAGE}::domain_routing_ism::route"), |args| {
+            // the message is passed hex encoded
+            let message = hex::encode(message(0).to_vec());
+            if args != [json!(message)] {
+                return vec![json!("0x0")];
+            }
+            vec![json!("0xb0b")]
+        });
+
+    assert_eq!(
+        ism.route(&message(0)).await.unwrap(),
+        H256::from_low_u64_be(0xb0b)
+    );
+
+    // malformed responses are errors, not a zero address
+    for response in [vec![], vec![json!("not an address")], vec![json!(1)]] {
+        node.state()
+            .set_view(&format!("{PACKAGE}::domain_routing_ism::route"), response);
+        let err = ism.route(&message(0)).await.unwrap_err();
+        assert!(err.to_string().contains("domain_routing_ism::route"));
+    }
+}
+
+#[tokio::test]
+async fn test_hosted_routing_ism_route() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let hosted = H256::from_low_u64_be(0xa11ce);
+    host_ism(
+        &node,
+        AccountAddress::new(hosted.0),
+        "domain_routing_ism",
+        "RoutingIsm",
+    );
+    let ism = AptosRoutingIsm::new(
+        &conf(&node),
+        ContractLocator {
+            domain: &domain,
+            address: hosted,
+        },
+    );
+    node.state().set_view(
+        &format!("{PACKAGE}::domain_routing_ism::route_at"),
+        vec![json!("0xb0b")],
+    );
+
+    assert_eq!(
+        ism.route(&message(0)).await.unwrap(),
+        H256::from_low_u64_be(0xb0b)
+    );
+    let calls = node.state().view_calls().to_vec();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(
+        calls[0].arguments,
+        vec![
+            json!(AccountAddress::new(hosted.0).to_hex_literal()),
+            json!(hex::encode(message(0).to_vec()))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_aggregation_ism_modules_and_threshold() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let ism = AptosAggregationIsm::new(&conf(&node), locator(&domain));
+    host_ism(
+        &node,
+        package_address(),
+        "aggregation_ism",
+        "AggregationIsm",
+    );
+    node.state().set_view(
+        &format!("{PACKAGE}::aggregation_ism::modules_and_threshold"),
+        vec![json!(["0xa", "0xb"]), json!("1")],
+    );
+
+    assert_eq!(
+        ism.modules_and_threshold(&message(0)).await.unwrap(),
+        (
+            vec![H256::from_low_u64_be(0xa), H256::from_low_u64_be(0xb)],
+            1
+        )
+    );
+
+    // malformed responses are errors
+    for response in [
+        vec![json!(["0xa"])],
+        vec![json!("0xa"), json!("1")],
+        vec![json!(["0xa", "not an address"]), json!("1")],
+        vec![json!(["0xa"]), json!("256")],
+        vec![json!(["0xa"]), json!(true)],
+    ] {
+        node.state().set_view(
+            &format!("{PACKAGE}::aggregation_ism::modules_and_threshold"),
+            response,
+        );
+        let err = ism.modules_and_threshold(&message(0)).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("aggregation_ism::modules_and_threshold"));
+    }
+}
+
+#[tokio::test]
+async fn test_hosted_aggregation_ism_modules_and_threshold() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let hosted = H256::from_low_u64_be(0xa99);
+    host_ism(
+        &node,
+        AccountAddress::new(hosted.0),
+        "aggregation_ism",
+        "AggregationIsm",
+    );
+    let ism = AptosAggregationIsm::new(
+        &conf(&node),
+        ContractLocator {
+            domain: &domain,
+            address: hosted,
+        },
+    );
+    node.state().set_view_fn(
+        &format!("{PACKAGE}::aggregation_ism::modules_and_threshold_at"),
+        move |args| {
+            if args.first() != Some(&json!(AccountAddress::new(hosted.0).to_hex_literal())) {
+                return vec![json!([]), json!("0")];
+            }
+            vec![json!(["0xa"]), json!(1)]
+        },
+    );
+
+    assert_eq!(
+        ism.modules_and_threshold(&message(0)).await.unwrap(),
+        (vec![H256::from_low_u64_be(0xa)], 1)
+    );
+}
+
+#[tokio::test]
+async fn test_ism_dry_run_verify_simulates() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let hosted = H256::from_low_u64_be(0xa99);
+    let hosted_locator = || ContractLocator {
+        domain: &domain,
+        address: hosted,
+    };
+    host_ism(
+        &node,
+        AccountAddress::new(hosted.0),
+        "aggregation_ism",
+        "AggregationIsm",
+    );
+    node.state().gas_used = 4321;
+
+    // verification gas can't be estimated without simulating
+    let ism = AptosInterchainSecurityModule::new(&conf(&node), hosted_locator(), None);
+    assert!(matches!(
+        ism.dry_run_verify(&message(0), b"metadata").await,
+        Err(ChainCommunicationError::SignerUnavailable)
+    ));
+
+    let signer = funded_signer(&node, 7, 0);
+    let ism = AptosInterchainSecurityModule::new(&conf(&node), hosted_locator(), Some(signer));
+    assert_eq!(
+        ism.dry_run_verify(&message(0), b"metadata").await.unwrap(),
+        Some(U256::from(4321))
+    );
+    node.state()
+        .push_simulation_outcome(TransactionOutcome::Failure {
+            vm_status: format!("Move abort in {PACKAGE}::ism: 0x1"),
+        });
+    assert_eq!(
+        ism.dry_run_verify(&message(0), b"metadata").await.unwrap(),
+        None
+    );
+
+    let state = node.state();
+    assert_eq!(state.simulated_transactions().len(), 2);
+    assert_eq!(
+        entry_function_name(state.simulated_transactions()[0].payload()),
+        "ism::assert_verified_at"
+    );
+}
+
+#[tokio::test]
+async fn test_ism_module_type_without_ism_module() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let ism = AptosInterchainSecurityModule::new(&conf(&node), locator(&domain), None);
+    host_ism(&node, package_address(), "multisig_ism", "ISM");
+    node.state().set_view(
+        &format!("{PACKAGE}::multisig_ism::validators_and_threshold"),
+        vec![json!([]), json!("0")],
+    );
+
+    // packages published before the `ism` module only have the multisig ISM
+    assert_eq!(
+        ism.module_type().await.unwrap(),
+        ModuleType::MessageIdMultisig
+    );
+
+    // other failures of the view are not hidden once the module is published
+    node.state()
+        .set_view(&format!("{PACKAGE}::ism::verify"), vec![json!(true)]);
+    assert!(ism.module_type().await.is_err());
+
+    node.state().set_view(
+        &format!("{PACKAGE}::ism::get_module_type"),
+        vec![json!("1")],
+    );
+    assert_eq!(ism.module_type().await.unwrap(), ModuleType::Routing);
+}
+
+#[tokio::test]
+async fn test_warp_route_configure_enrolls_missing_routers() {
+    let node = MockAptosNode::spawn();
+    let domain = domain();
+    let signer = funded_signer(&node, 6, 0);
+    let nonce_manager =
+        AptosNonceManager::for_signer(&AptosClient::new(&conf(&node), &domain), &signer);
+    let warp_route = AptosWarpRoute::new(
+        &conf(&node),
+        ContractLocator {
+            domain: &domain,
+            address: H256::from_low_u64_be(0x5147),
+        },
+        package(),
+        AptosTokenType::Synthetic(AptosSyntheticMetadata {
+            name: "Wrapped Ether".to_owned(),
+            symbol: "WETH".to_owned(),
+            decimals: 8,
+            remote_decimals: Some(18),
+        }),
+    );
+    let enrolled_router = H256::from_low_u64_be(0xbeef);
+    let new_router = H256::from_low_u64_be(0xcafe);
+    {
+        let mut state = node.state();
+        state.set_view(
+            &format!("{PACKAGE}::router::fetch_module_name"),
+            vec![json!(format!("0x{}", hex::encode("synthetic_token")))],
+        );
+        state.set_view(
+            &format!("{PACKAGE}::router::get_domains"),
+            vec![json!([14411])],
+        );
+        state.set_view(
+            &format!("{PACKAGE}::router::get_routers"),
+            vec![json!([format!("{enrolled_router:?}")])],
+        );
+    }
+    let routers = BTreeMap::from([(14411, enrolled_router), (14477, new_router)]);
+
+    // only the router missing on chain is enrolled
+    let outcomes = warp_route
+        .configure(&nonce_manager, &Default::default(), &routers)
+        .await
+        .unwrap();
+    assert_eq!(outcomes.len(), 1);
+    {
+        let state = node.state();
+        let payload = state.submitted_transactions()[0].payload().clone();
+        assert_eq!(
+            entry_function_name(&payload),
+            "router::enroll_remote_routers"
+        );
+        let TransactionPayload::EntryFunction(entry_function) = payload else {
+            unreachable!()
+        };
+        assert_eq!(
+            entry_function.args()[0],
+            bcs::to_bytes(&vec![14477u32]).unwrap()
+        );
+        assert_eq!(
+            entry_function.args()[1],
+            bcs::to_bytes(&vec![new_router.as_bytes().to_vec()]).unwrap()
+        );
+    }
+
+    // an unregistered router is initialized before all routers are enrolled
+    node.state().set_view(
+        &format!("{PACKAGE}::router::fetch_module_name"),
+        vec![json!("0x")],
+    );
+    let outcomes = warp_route
+        .configure(&nonce_manager, &Default::default(), &routers)
+        .await
+        .unwrap();
+    assert_eq!(outcomes.len(), 2);
+    let state = node.state();
+    let names: Vec<String> = state.submitted_transactions()[1..]
+        .iter()
+        .map(|tx| entry_function_name(tx.payload()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "synthetic_token::initialize",
+            "router::enroll_remote_routers"
+        ]
+    );
+}
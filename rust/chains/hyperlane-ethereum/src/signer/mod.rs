@@ -9,7 +9,9 @@ use hyperlane_core::{
 };
 
 mod singleton;
+mod web3signer;
 pub use singleton::*;
+pub use web3signer::*;
 
 /// Ethereum-supported signer types
 #[derive(Debug, Clone)]
@@ -18,6 +20,9 @@ pub enum Signers {
     Local(LocalWallet),
     /// A signer using a key stored in aws kms
     Aws(AwsSigner),
+    /// A signer using a key held by a Web3Signer-compatible remote signer.
+    /// Can't sign transactions.
+    Web3Signer(Web3Signer),
 }
 
 impl From<LocalWallet> for Signers {
@@ -32,6 +37,12 @@ impl From<AwsSigner> for Signers {
     }
 }
 
+impl From<Web3Signer> for Signers {
+    fn from(s: Web3Signer) -> Self {
+        Signers::Web3Signer(s)
+    }
+}
+
 #[async_trait]
 impl Signer for Signers {
     type Error = SignersError;
@@ -43,6 +54,7 @@ impl Signer for Signers {
         match self {
             Signers::Local(signer) => Ok(signer.sign_message(message).await?),
             Signers::Aws(signer) => Ok(signer.sign_message(message).await?),
+            Signers::Web3Signer(signer) => Ok(signer.sign_message(message.as_ref()).await?),
         }
     }
 
@@ -50,6 +62,7 @@ impl Signer for Signers {
         match self {
             Signers::Local(signer) => Ok(signer.sign_transaction(message).await?),
             Signers::Aws(signer) => Ok(signer.sign_transaction(message).await?),
+            Signers::Web3Signer(_) => Err(Web3SignerError::Unsupported("transactions").into()),
         }
     }
 
@@ -60,6 +73,7 @@ impl Signer for Signers {
         match self {
             Signers::Local(signer) => Ok(signer.sign_typed_data(payload).await?),
             Signers::Aws(signer) => Ok(signer.sign_typed_data(payload).await?),
+            Signers::Web3Signer(_) => Err(Web3SignerError::Unsupported("typed data").into()),
         }
     }
 
@@ -67,6 +81,7 @@ impl Signer for Signers {
         match self {
             Signers::Local(signer) => signer.address(),
             Signers::Aws(signer) => signer.address(),
+            Signers::Web3Signer(signer) => signer.address(),
         }
     }
 
@@ -74,6 +89,7 @@ impl Signer for Signers {
         match self {
             Signers::Local(signer) => signer.chain_id(),
             Signers::Aws(signer) => signer.chain_id(),
+            Signers::Web3Signer(signer) => signer.chain_id(),
         }
     }
 
@@ -81,6 +97,7 @@ impl Signer for Signers {
         match self {
             Signers::Local(signer) => signer.with_chain_id(chain_id).into(),
             Signers::Aws(signer) => signer.with_chain_id(chain_id).into(),
+            Signers::Web3Signer(signer) => signer.with_chain_id(chain_id.into()).into(),
         }
     }
 }
@@ -109,6 +126,9 @@ pub enum SignersError {
     /// Wallet Signer Error
     #[error("{0}")]
     WalletError(#[from] WalletError),
+    /// Web3Signer Error
+    #[error("{0}")]
+    Web3SignerError(#[from] Web3SignerError),
 }
 
 impl From<std::convert::Infallible> for SignersError {
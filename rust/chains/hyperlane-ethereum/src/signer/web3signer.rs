@@ -0,0 +1,259 @@
+use ethers::core::k256::ecdsa::VerifyingKey;
+use ethers::prelude::{Address, Signature};
+use ethers::utils::{hash_message, keccak256};
+use serde_json::json;
+use url::Url;
+
+/// Path of the eth1 signing endpoint, relative to the remote signer url
+const ETH1_SIGN_PATH: &str = "api/v1/eth1/sign/";
+
+/// A signer whose key is held by a remote signer implementing the
+/// [Web3Signer](https://docs.web3signer.consensys.io/reference/api/rest)
+/// eth1 signing API.
+///
+/// The remote signer signs the keccak256 hash of the data it is sent, so
+/// messages are sent with their EIP-191 prefix to get the same signature a
+/// local wallet produces. Every signature is checked against the key's
+/// address before being used.
+#[derive(Debug, Clone)]
+pub struct Web3Signer {
+    client: reqwest::Client,
+    url: Url,
+    public_key: String,
+    address: Address,
+    chain_id: u64,
+}
+
+impl Web3Signer {
+    /// Signer of the key with `public_key` held by the remote signer at
+    /// `url`. The public key is hex encoded, compressed or uncompressed, with
+    /// or without the `0x04` prefix of uncompressed keys.
+    pub fn new(url: Url, public_key: &str) -> Result<Self, Web3SignerError> {
+        let bytes = hex::decode(public_key.trim_start_matches("0x"))
+            .map_err(|_| Web3SignerError::InvalidPublicKey(public_key.to_owned()))?;
+        let bytes = match bytes.len() {
+            64 => [&[0x04], bytes.as_slice()].concat(),
+            _ => bytes,
+        };
+        let key = VerifyingKey::from_sec1_bytes(&bytes)
+            .map_err(|_| Web3SignerError::InvalidPublicKey(public_key.to_owned()))?;
+        let point = key.to_encoded_point(false);
+        // The API identifies keys by their uncompressed public key, without prefix
+        let public_key = &point.as_bytes()[1..];
+        let address = Address::from_slice(&keccak256(public_key)[12..]);
+
+        let mut url = url;
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url,
+            public_key: format!("0x{}", hex::encode(public_key)),
+            address,
+            chain_id: 1,
+        })
+    }
+
+    /// Address of the remote key
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Chain id used for signing transactions
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Set the chain id used for signing transactions
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Sign an EIP-191 message with the remote key
+    pub async fn sign_message(&self, message: &[u8]) -> Result<Signature, Web3SignerError> {
+        let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+        data.extend_from_slice(message);
+
+        let url = self
+            .url
+            .join(&format!("{ETH1_SIGN_PATH}{}", self.public_key))
+            .map_err(|err| Web3SignerError::Request(err.to_string()))?;
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(json!({ "data": format!("0x{}", hex::encode(&data)) }).to_string())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| Web3SignerError::Request(err.to_string()))?
+            .text()
+            .await
+            .map_err(|err| Web3SignerError::Request(err.to_string()))?;
+
+        let encoded = response.trim().trim_matches('"').trim_start_matches("0x");
+        let bytes = hex::decode(encoded)
+            .map_err(|_| Web3SignerError::InvalidSignature(response.clone()))?;
+        let mut signature = Signature::try_from(bytes.as_slice())
+            .map_err(|_| Web3SignerError::InvalidSignature(response.clone()))?;
+        // Remote signers may return the bare recovery id
+        if signature.v < 27 {
+            signature.v += 27;
+        }
+
+        let signer = signature
+            .recover(hash_message(message))
+            .map_err(|_| Web3SignerError::InvalidSignature(response.clone()))?;
+        if signer != self.address {
+            return Err(Web3SignerError::WrongSigner {
+                expected: self.address,
+                actual: signer,
+            });
+        }
+        Ok(signature)
+    }
+}
+
+/// Error types for Web3Signer
+#[derive(Debug, thiserror::Error)]
+pub enum Web3SignerError {
+    /// The configured public key can't be parsed
+    #[error("Invalid remote signer public key {0}")]
+    InvalidPublicKey(String),
+    /// The request to the remote signer failed
+    #[error("Remote signer request failed: {0}")]
+    Request(String),
+    /// The remote signer returned something that isn't a signature
+    #[error("Invalid signature returned by remote signer: {0}")]
+    InvalidSignature(String),
+    /// The remote signer signed with another key
+    #[error("Remote signer signed with {actual:?} instead of {expected:?}")]
+    WrongSigner {
+        /// Address of the configured key
+        expected: Address,
+        /// Address recovered from the signature
+        actual: Address,
+    },
+    /// The remote signer API has no endpoint for this kind of signature
+    #[error("Remote signer can't sign {0}")]
+    Unsupported(&'static str),
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{extract::Path, routing::post, Json, Router};
+    use ethers::signers::{LocalWallet, Signer};
+    use hyperlane_core::{
+        Checkpoint, CheckpointWithMessageId, HyperlaneSigner, HyperlaneSignerExt, H160, H256,
+    };
+    use serde_json::Value;
+
+    use super::*;
+    use crate::Signers;
+
+    const PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n32";
+
+    fn wallet(key: &str) -> LocalWallet {
+        key.parse().unwrap()
+    }
+
+    fn public_key(wallet: &LocalWallet, compress: bool) -> String {
+        let point = wallet.signer().verifying_key().to_encoded_point(compress);
+        format!("0x{}", hex::encode(point.as_bytes()))
+    }
+
+    /// Serves the eth1 signing endpoint, signing with `wallet` whatever key
+    /// is requested
+    async fn remote_signer(wallet: LocalWallet) -> Url {
+        let app = Router::new().route(
+            "/api/v1/eth1/sign/:identifier",
+            post(
+                move |Path(identifier): Path<String>, Json(body): Json<Value>| {
+                    let wallet = wallet.clone();
+                    async move {
+                        assert_eq!(identifier.len(), 2 + 128);
+                        let data =
+                            hex::decode(body["data"].as_str().unwrap().trim_start_matches("0x"))
+                                .unwrap();
+                        assert_eq!(&data[..PREFIX.len()], PREFIX);
+                        let signature = wallet.sign_message(&data[PREFIX.len()..]).await.unwrap();
+                        format!("0x{}", hex::encode(signature.to_vec()))
+                    }
+                },
+            ),
+        );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let url = format!("http://{}/signer", server.local_addr())
+            .parse()
+            .unwrap();
+        tokio::spawn(server);
+        url
+    }
+
+    fn checkpoint() -> CheckpointWithMessageId {
+        CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(2),
+                mailbox_domain: 5,
+                root: H256::repeat_byte(1),
+                index: 123,
+            },
+            message_id: H256::repeat_byte(3),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signs_like_a_local_wallet() {
+        let local = wallet("1111111111111111111111111111111111111111111111111111111111111111");
+        let url = remote_signer(local.clone()).await;
+        let remote: Signers = Web3Signer::new(url, &public_key(&local, false))
+            .unwrap()
+            .into();
+        assert_eq!(remote.eth_address(), H160::from(local.address()));
+
+        let signed = remote.sign(checkpoint()).await.unwrap();
+        signed.verify(remote.eth_address()).unwrap();
+        let local: Signers = local.into();
+        assert_eq!(signed, local.sign(checkpoint()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_signatures_of_other_keys() {
+        let configured = wallet("1111111111111111111111111111111111111111111111111111111111111111");
+        let other = wallet("2222222222222222222222222222222222222222222222222222222222222222");
+        let url = remote_signer(other.clone()).await;
+        let remote = Web3Signer::new(url, &public_key(&configured, false)).unwrap();
+
+        let err = remote
+            .sign_message(H256::zero().as_bytes())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Web3SignerError::WrongSigner { expected, actual }
+                if expected == configured.address() && actual == other.address()
+        ));
+    }
+
+    #[test]
+    fn test_public_key_formats() {
+        let wallet = wallet("1111111111111111111111111111111111111111111111111111111111111111");
+        let url: Url = "http://localhost:9000".parse().unwrap();
+        let uncompressed = public_key(&wallet, false);
+        for key in [
+            uncompressed.clone(),
+            format!("0x{}", &uncompressed[4..]),
+            public_key(&wallet, true),
+        ] {
+            let signer = Web3Signer::new(url.clone(), &key).unwrap();
+            assert_eq!(signer.address(), wallet.address());
+        }
+        assert!(matches!(
+            Web3Signer::new(url, "0x1234"),
+            Err(Web3SignerError::InvalidPublicKey(_))
+        ));
+    }
+}
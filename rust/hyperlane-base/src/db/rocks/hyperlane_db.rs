@@ -35,6 +35,12 @@ const MERKLE_LEAF_INDEX_BY_MESSAGE_ID: &str = "merkle_leaf_index_by_message_id_"
 const MERKLE_TREE_INSERTION_BLOCK_NUMBER_BY_LEAF_INDEX: &str =
     "merkle_tree_insertion_block_number_by_leaf_index_";
 const LATEST_INDEXED_GAS_PAYMENT_BLOCK: &str = "latest_indexed_gas_payment_block";
+const SIGNED_CHECKPOINT_DIGEST_BY_INDEX: &str = "signed_checkpoint_digest_by_index_";
+const HIGHEST_SIGNED_CHECKPOINT_INDEX: &str = "highest_signed_checkpoint_index_";
+// Roots signed before the signing hashes were recorded, only read to import them
+const SIGNED_CHECKPOINT_ROOT_BY_INDEX: &str = "signed_checkpoint_root_by_index_";
+const SIGNED_CHECKPOINT_ROOTS_IMPORTED: &str = "signed_checkpoint_roots_imported_";
+const HIGHEST_AUDITED_CHECKPOINT_INDEX: &str = "highest_audited_checkpoint_index_";
 
 /// Rocks DB result type
 pub type DbResult<T> = std::result::Result<T, DbError>;
@@ -129,6 +135,60 @@ impl HyperlaneRocksDB {
         self.retrieve_highest_seen_message_nonce_number(&Default::default())
     }
 
+    /// Record the signing hash of the checkpoint the validator signed at
+    /// `index`
+    pub fn store_signed_checkpoint_digest(&self, index: u32, digest: &H256) -> DbResult<()> {
+        self.store_signed_checkpoint_digest_by_index(&index, digest)?;
+        if self
+            .retrieve_highest_signed_checkpoint_index()?
+            .map_or(true, |highest| index > highest)
+        {
+            self.store_highest_signed_checkpoint_index_number(&Default::default(), &index)?;
+        }
+        Ok(())
+    }
+
+    /// Retrieve the signing hash of the checkpoint the validator signed at
+    /// `index`
+    pub fn retrieve_signed_checkpoint_digest(&self, index: u32) -> DbResult<Option<H256>> {
+        self.retrieve_signed_checkpoint_digest_by_index(&index)
+    }
+
+    /// Retrieve the highest index the validator signed a checkpoint at
+    pub fn retrieve_highest_signed_checkpoint_index(&self) -> DbResult<Option<u32>> {
+        self.retrieve_highest_signed_checkpoint_index_number(&Default::default())
+    }
+
+    /// Record the highest index up to which the validator audited its
+    /// published checkpoints
+    pub fn store_highest_audited_checkpoint_index(&self, index: u32) -> DbResult<()> {
+        self.store_highest_audited_checkpoint_index_number(&Default::default(), &index)
+    }
+
+    /// Retrieve the highest index up to which the validator audited its
+    /// published checkpoints
+    pub fn retrieve_highest_audited_checkpoint_index(&self) -> DbResult<Option<u32>> {
+        self.retrieve_highest_audited_checkpoint_index_number(&Default::default())
+    }
+
+    /// Retrieve the root the validator signed at `index` before the signing
+    /// hashes of checkpoints were recorded
+    pub fn retrieve_legacy_signed_checkpoint_root(&self, index: u32) -> DbResult<Option<H256>> {
+        self.retrieve_keyed_decodable(SIGNED_CHECKPOINT_ROOT_BY_INDEX, &index)
+    }
+
+    /// Whether the legacy signed roots were imported as signing hashes
+    pub fn retrieve_signed_checkpoint_roots_imported(&self) -> DbResult<bool> {
+        Ok(self
+            .retrieve_signed_checkpoint_roots_imported_flag(&Default::default())?
+            .unwrap_or(false))
+    }
+
+    /// Record that the legacy signed roots were imported as signing hashes
+    pub fn store_signed_checkpoint_roots_imported(&self) -> DbResult<()> {
+        self.store_signed_checkpoint_roots_imported_flag(&Default::default(), &true)
+    }
+
     /// If the provided gas payment, identified by its metadata, has not been
     /// processed, processes the gas payment and records it as processed.
     /// Returns whether the gas payment was processed for the first time.
@@ -545,6 +605,10 @@ make_store_and_retrieve!(
     u32,
     u64
 );
+make_store_and_retrieve!(, signed_checkpoint_digest_by_index, SIGNED_CHECKPOINT_DIGEST_BY_INDEX, u32, H256);
 // There's no unit struct Encode/Decode impl, so just use `bool`, have visibility be private (by omitting the first argument), and wrap
 // with a function that always uses the `Default::default()` key
 make_store_and_retrieve!(, highest_seen_message_nonce_number, HIGHEST_SEEN_MESSAGE_NONCE, bool, u32);
+make_store_and_retrieve!(, highest_signed_checkpoint_index_number, HIGHEST_SIGNED_CHECKPOINT_INDEX, bool, u32);
+make_store_and_retrieve!(, highest_audited_checkpoint_index_number, HIGHEST_AUDITED_CHECKPOINT_INDEX, bool, u32);
+make_store_and_retrieve!(, signed_checkpoint_roots_imported_flag, SIGNED_CHECKPOINT_ROOTS_IMPORTED, bool, bool);
@@ -0,0 +1,22 @@
+use eyre::Result;
+use hyperlane_aptos::{
+    AptosClientMetrics, APTOS_REQUEST_COUNT_HELP, APTOS_REQUEST_DURATION_SECONDS_HELP,
+    APTOS_REQUEST_LABELS,
+};
+
+use crate::CoreMetrics;
+
+pub(crate) fn create_aptos_client_metrics(metrics: &CoreMetrics) -> Result<AptosClientMetrics> {
+    Ok(AptosClientMetrics {
+        request_count: Some(metrics.new_int_counter(
+            "aptos_request_count",
+            APTOS_REQUEST_COUNT_HELP,
+            APTOS_REQUEST_LABELS,
+        )?),
+        request_duration_seconds: Some(metrics.new_counter(
+            "aptos_request_duration_seconds",
+            APTOS_REQUEST_DURATION_SECONDS_HELP,
+            APTOS_REQUEST_LABELS,
+        )?),
+    })
+}
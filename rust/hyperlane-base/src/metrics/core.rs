@@ -13,9 +13,11 @@ use prometheus::{
 use tokio::sync::RwLock;
 
 use ethers_prometheus::{json_rpc_client::JsonRpcClientMetrics, middleware::MiddlewareMetrics};
+use hyperlane_aptos::AptosClientMetrics;
 
 use crate::metrics::{
-    json_rpc_client::create_json_rpc_client_metrics, provider::create_provider_metrics,
+    aptos_client::create_aptos_client_metrics, json_rpc_client::create_json_rpc_client_metrics,
+    provider::create_provider_metrics,
 };
 
 /// Macro to prefix a string with the namespace.
@@ -51,6 +53,9 @@ pub struct CoreMetrics {
     /// Set of provider-specific metrics. These only need to get created once.
     provider_metrics: OnceLock<MiddlewareMetrics>,
 
+    /// Set of metrics that wrap the requests made to Aptos REST endpoints.
+    aptos_client_metrics: OnceLock<AptosClientMetrics>,
+
     /// Metrics that are used to observe validator sets.
     pub validator_metrics: ValidatorObservabilityMetricManager,
 }
@@ -186,6 +191,7 @@ impl CoreMetrics {
 
             json_rpc_client_metrics: OnceLock::new(),
             provider_metrics: OnceLock::new(),
+            aptos_client_metrics: OnceLock::new(),
 
             validator_metrics: ValidatorObservabilityMetricManager::new(
                 observed_validator_latest_index.clone(),
@@ -212,6 +218,16 @@ impl CoreMetrics {
             .clone()
     }
 
+    /// Create the Aptos REST client metrics attached to this core metrics
+    /// instance.
+    pub fn aptos_client_metrics(&self) -> AptosClientMetrics {
+        self.aptos_client_metrics
+            .get_or_init(|| {
+                create_aptos_client_metrics(self).expect("Failed to create aptos client metrics!")
+            })
+            .clone()
+    }
+
     /// Create and register a new int gauge.
     pub fn new_int_gauge(
         &self,
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use hyperlane_aptos::signers::AptosRemoteSigner;
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use rusoto_kms::{GetPublicKeyRequest, Kms, KmsClient, SignRequest};
+
+/// DER prefix of an Ed25519 SubjectPublicKeyInfo, followed by the 32 byte key
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+/// KMS signing algorithm for pure Ed25519 over the raw message
+const ED25519_SIGNING_ALGORITHM: &str = "ED25519_SHA_512";
+
+/// Signs Aptos transactions with an Ed25519 key held in AWS KMS.
+/// Note that AWS credentials must be inserted into the env separately.
+pub(crate) struct AwsKmsAptosSigner {
+    client: KmsClient,
+    key_id: String,
+}
+
+impl AwsKmsAptosSigner {
+    pub fn new(client: KmsClient, key_id: String) -> Self {
+        Self { client, key_id }
+    }
+}
+
+impl std::fmt::Debug for AwsKmsAptosSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsKmsAptosSigner")
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl AptosRemoteSigner for AwsKmsAptosSigner {
+    async fn public_key(&self) -> ChainResult<[u8; 32]> {
+        let response = self
+            .client
+            .get_public_key(GetPublicKeyRequest {
+                key_id: self.key_id.clone(),
+                ..Default::default()
+            })
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let spki = response.public_key.unwrap_or_default();
+        spki.strip_prefix(ED25519_SPKI_PREFIX.as_slice())
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| ChainCommunicationError::ParseError {
+                msg: format!("KMS key {} is not an Ed25519 key", self.key_id),
+            })
+    }
+
+    async fn sign(&self, message: &[u8]) -> ChainResult<[u8; 64]> {
+        let response = self
+            .client
+            .sign(SignRequest {
+                key_id: self.key_id.clone(),
+                message: message.to_vec().into(),
+                message_type: Some("RAW".to_owned()),
+                signing_algorithm: ED25519_SIGNING_ALGORITHM.to_owned(),
+                ..Default::default()
+            })
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        response
+            .signature
+            .unwrap_or_default()
+            .as_ref()
+            .try_into()
+            .map_err(|_| ChainCommunicationError::ParseError {
+                msg: format!("KMS key {} returned a malformed signature", self.key_id),
+            })
+    }
+}
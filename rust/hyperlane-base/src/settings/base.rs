@@ -3,9 +3,10 @@ use std::{collections::HashMap, fmt::Debug, hash::Hash, sync::Arc};
 use eyre::{eyre, Context, Result};
 use futures_util::future::try_join_all;
 use hyperlane_core::{
-    HyperlaneChain, HyperlaneDomain, HyperlaneLogStore, HyperlaneProvider,
-    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, InterchainGasPaymaster,
-    Mailbox, MerkleTreeHook, MultisigIsm, SequenceAwareIndexer, ValidatorAnnounce, H256,
+    HyperlaneChain, HyperlaneDomain, HyperlaneDomainProtocol, HyperlaneLogStore, HyperlaneProvider,
+    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, IndexMode,
+    InterchainGasPaymaster, Mailbox, MerkleTreeHook, MultisigIsm, SequenceAwareIndexer,
+    ValidatorAnnounce, H256,
 };
 
 use crate::{
@@ -220,7 +221,16 @@ impl Settings {
         // TODO: parallelize these calls again
         let mut syncs = vec![];
         for domain in domains {
-            let sync = match T::indexing_cursor(domain.domain_protocol()) {
+            let cursor_type = match (
+                domain.domain_protocol(),
+                self.chain_setup(domain)?.index.mode,
+            ) {
+                // Aptos indexers in sequence mode fetch ranges of event sequence
+                // numbers, which only sequence aware cursors produce
+                (HyperlaneDomainProtocol::Aptos, IndexMode::Sequence) => CursorType::SequenceAware,
+                (protocol, _) => T::indexing_cursor(protocol),
+            };
+            let sync = match cursor_type {
                 CursorType::SequenceAware => self
                     .sequenced_contract_sync(
                         domain,
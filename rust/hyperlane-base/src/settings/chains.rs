@@ -200,7 +200,11 @@ impl ChainConf {
                 Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
             }
             ChainConnectionConf::Aptos(conf) => {
-                let provider = AptosHpProvider::new(locator.domain.clone(), conf.url.to_string());
+                let conf = self.aptos_connection_conf(conf, metrics);
+                let provider = AptosHpProvider::new(
+                    locator.domain.clone(),
+                    h_aptos::AptosClient::new(&conf, &locator.domain),
+                );
                 Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
             }
         }
@@ -236,6 +240,7 @@ impl ChainConf {
                     .map_err(Into::into)
             }
             ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
                 let keypair = self.aptos_signer().await.context(ctx)?;
                 h_aptos::AptosMailbox::new(conf, locator, keypair)
                     .map(|m| Box::new(m) as Box<dyn Mailbox>)
@@ -273,9 +278,12 @@ impl ChainConf {
 
                 Ok(Box::new(hook) as Box<dyn MerkleTreeHook>)
             }
-            ChainConnectionConf::Aptos(conf) => h_aptos::AptosMailbox::new(conf, locator, None)
-                .map(|m| Box::new(m) as Box<dyn MerkleTreeHook>)
-                .map_err(Into::into),
+            ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
+                h_aptos::AptosMailbox::new(conf, locator, None)
+                    .map(|m| Box::new(m) as Box<dyn MerkleTreeHook>)
+                    .map_err(Into::into)
+            }
         }
         .context(ctx)
     }
@@ -316,7 +324,12 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
             }
             ChainConnectionConf::Aptos(conf) => {
-                let indexer = Box::new(h_aptos::AptosMailboxIndexer::new(conf, locator)?);
+                let conf = &self.aptos_connection_conf(conf, metrics);
+                let indexer = Box::new(h_aptos::AptosMailboxIndexer::new(
+                    conf,
+                    locator,
+                    self.index.mode,
+                )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
             }
         }
@@ -359,7 +372,12 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
             }
             ChainConnectionConf::Aptos(conf) => {
-                let indexer = Box::new(h_aptos::AptosMailboxIndexer::new(conf, locator)?);
+                let conf = &self.aptos_connection_conf(conf, metrics);
+                let indexer = Box::new(h_aptos::AptosMailboxIndexer::new(
+                    conf,
+                    locator,
+                    self.index.mode,
+                )?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
             }
         }
@@ -402,6 +420,7 @@ impl ChainConf {
                 Ok(paymaster as Box<dyn InterchainGasPaymaster>)
             }
             ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
                 let paymaster = Box::new(h_aptos::AptosInterchainGasPaymaster::new(conf, &locator));
                 Ok(paymaster as Box<dyn InterchainGasPaymaster>)
             }
@@ -446,8 +465,11 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
             }
             ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
                 let indexer = Box::new(h_aptos::AptosInterchainGasPaymasterIndexer::new(
-                    conf, locator,
+                    conf,
+                    locator,
+                    self.index.mode,
                 ));
                 Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
             }
@@ -496,7 +518,9 @@ impl ChainConf {
                 Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
             }
             ChainConnectionConf::Aptos(conf) => {
-                let mailbox_indexer = Box::new(AptosMailboxIndexer::new(conf, locator)?);
+                let conf = &self.aptos_connection_conf(conf, metrics);
+                let mailbox_indexer =
+                    Box::new(AptosMailboxIndexer::new(conf, locator, self.index.mode)?);
                 let indexer = Box::new(h_aptos::AptosMerkleTreeHookIndexer::new(*mailbox_indexer));
                 Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
             }
@@ -532,6 +556,7 @@ impl ChainConf {
                 Ok(va as Box<dyn ValidatorAnnounce>)
             }
             ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
                 let keypair = self.aptos_signer().await.context("Announcing Validator")?;
                 let va = Box::new(h_aptos::AptosValidatorAnnounce::new(conf, locator, keypair));
                 Ok(va as Box<dyn ValidatorAnnounce>)
@@ -576,6 +601,7 @@ impl ChainConf {
                 Ok(ism as Box<dyn InterchainSecurityModule>)
             }
             ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
                 let keypair = self.aptos_signer().await.context(ctx)?;
                 let ism = Box::new(h_aptos::AptosInterchainSecurityModule::new(
                     conf, locator, keypair,
@@ -617,6 +643,7 @@ impl ChainConf {
                 Ok(ism as Box<dyn MultisigIsm>)
             }
             ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
                 let keypair = self.aptos_signer().await.context(ctx)?;
                 let ism = Box::new(h_aptos::AptosMultisigISM::new(conf, locator, keypair));
                 Ok(ism as Box<dyn MultisigIsm>)
@@ -655,8 +682,10 @@ impl ChainConf {
                 )?);
                 Ok(ism as Box<dyn RoutingIsm>)
             }
-            ChainConnectionConf::Aptos(_) => {
-                Err(eyre!("Aptos does not support routing ISM yet")).context(ctx)
+            ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
+                let ism = Box::new(h_aptos::AptosRoutingIsm::new(conf, locator));
+                Ok(ism as Box<dyn RoutingIsm>)
             }
         }
         .context(ctx)
@@ -693,8 +722,10 @@ impl ChainConf {
 
                 Ok(ism as Box<dyn AggregationIsm>)
             }
-            ChainConnectionConf::Aptos(_) => {
-                Err(eyre!("Aptos does not support aggregation ISM yet")).context(ctx)
+            ChainConnectionConf::Aptos(conf) => {
+                let conf = &self.aptos_connection_conf(conf, metrics);
+                let ism = Box::new(h_aptos::AptosAggregationIsm::new(conf, locator));
+                Ok(ism as Box<dyn AggregationIsm>)
             }
         }
         .context(ctx)
@@ -779,10 +810,22 @@ impl ChainConf {
         self.signer().await
     }
 
-    async fn aptos_signer(&self) -> Result<Option<h_aptos::Keypair>> {
+    async fn aptos_signer(&self) -> Result<Option<h_aptos::signers::AptosSigner>> {
         self.signer().await
     }
 
+    /// The Aptos connection conf with its REST client metrics attached
+    fn aptos_connection_conf(
+        &self,
+        conf: &h_aptos::ConnectionConf,
+        metrics: &CoreMetrics,
+    ) -> h_aptos::ConnectionConf {
+        h_aptos::ConnectionConf {
+            client_metrics: metrics.aptos_client_metrics(),
+            ..conf.clone()
+        }
+    }
+
     /// Try to build an agent metrics configuration from the chain config
     pub async fn agent_metrics_conf(&self, agent_name: String) -> Result<AgentMetricsConf> {
         let chain_signer_address = self.chain_signer().await?.map(|s| s.address_string());
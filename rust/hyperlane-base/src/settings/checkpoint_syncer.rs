@@ -1,12 +1,13 @@
 use crate::{
-    CheckpointSyncer, GcsStorageClientBuilder, LocalStorage, S3Storage, GCS_SERVICE_ACCOUNT_KEY,
-    GCS_USER_SECRET,
+    CheckpointSyncer, GcsStorageClientBuilder, HttpStorage, LocalStorage, S3Storage,
+    GCS_SERVICE_ACCOUNT_KEY, GCS_USER_SECRET,
 };
 use core::str::FromStr;
 use eyre::{eyre, Context, Report, Result};
 use prometheus::IntGauge;
 use rusoto_core::Region;
 use std::{env, path::PathBuf};
+use url::Url;
 use ya_gcp::{AuthFlow, ServiceAccountAuth};
 
 /// Checkpoint Syncer types
@@ -38,6 +39,11 @@ pub enum CheckpointSyncerConf {
         /// `gcloud auth application-default login`
         user_secrets: Option<String>,
     },
+    /// A checkpoint syncer on a plain HTTP(S) or WebDAV server
+    Http {
+        /// Base url, which may carry basic auth credentials for writing
+        url: Url,
+    },
 }
 
 impl FromStr for CheckpointSyncerConf {
@@ -90,6 +96,11 @@ impl FromStr for CheckpointSyncerConf {
                     })
                 }
             }
+            "http" | "https" => Ok(CheckpointSyncerConf::Http {
+                url: s
+                    .parse()
+                    .context("Invalid url when parsing storage location")?,
+            }),
             _ => Err(eyre!("Unknown storage location prefix `{prefix}`")),
         }
     }
@@ -136,6 +147,9 @@ impl CheckpointSyncerConf {
                         .await?,
                 )
             }
+            CheckpointSyncerConf::Http { url } => {
+                Box::new(HttpStorage::new(url.clone(), latest_index_gauge)?)
+            }
         })
     }
 }
@@ -76,6 +76,8 @@ mod envs {
     pub use hyperlane_sealevel as h_sealevel;
 }
 
+/// AWS KMS backed Aptos signer.
+mod aptos_kms_signer;
 /// AWS Credentials provider.
 pub(crate) mod aws_credentials;
 mod base;
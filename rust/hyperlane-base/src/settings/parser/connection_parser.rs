@@ -151,6 +151,90 @@ pub fn build_cosmos_connection_conf(
     }
 }
 
+pub fn build_aptos_connection_conf(
+    rpcs: &[Url],
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<ChainConnectionConf> {
+    if rpcs.is_empty() {
+        return None;
+    }
+
+    let transaction_overrides = chain
+        .get_opt_key("transactionOverrides")
+        .take_err(err, || &chain.cwp + "transaction_overrides")
+        .flatten()
+        .map(|value_parser| h_aptos::TransactionOverrides {
+            gas_unit_price: value_parser
+                .chain(err)
+                .get_opt_key("gasUnitPrice")
+                .parse_u64()
+                .end(),
+            max_gas_amount: value_parser
+                .chain(err)
+                .get_opt_key("maxGasAmount")
+                .parse_u64()
+                .end(),
+            expiration_secs: value_parser
+                .chain(err)
+                .get_opt_key("expirationSecs")
+                .parse_u64()
+                .end(),
+            gas_buffer_percent: value_parser
+                .chain(err)
+                .get_opt_key("gasBufferPercent")
+                .parse_u64()
+                .end(),
+        })
+        .unwrap_or_default();
+
+    let external_submission = chain
+        .get_opt_key("externalSubmission")
+        .take_err(err, || &chain.cwp + "external_submission")
+        .flatten()
+        .and_then(|value_parser| {
+            let path = value_parser
+                .chain(err)
+                .get_opt_key("path")
+                .parse_string()
+                .end();
+            let url = value_parser
+                .chain(err)
+                .get_opt_key("url")
+                .parse_from_str("Expected external submission url")
+                .end();
+            match (path, url) {
+                (Some(path), None) => Some(h_aptos::ExternalSubmission::File(path.into())),
+                (None, Some(url)) => Some(h_aptos::ExternalSubmission::Endpoint(url)),
+                _ => {
+                    err.push(
+                        value_parser.cwp.clone(),
+                        eyre!("Expected exactly one of `path` or `url` for external submission"),
+                    );
+                    None
+                }
+            }
+        });
+
+    let indexer_backend = chain
+        .chain(err)
+        .get_opt_key("graphqlIndexerUrl")
+        .parse_from_str("Expected GraphQL indexer url")
+        .end()
+        .map_or(
+            h_aptos::AptosIndexerBackend::Rest,
+            h_aptos::AptosIndexerBackend::Graphql,
+        );
+
+    Some(ChainConnectionConf::Aptos(h_aptos::ConnectionConf {
+        urls: rpcs.to_vec(),
+        transaction_overrides,
+        external_submission,
+        client_metrics: Default::default(),
+        indexer_backend,
+    }))
+}
+
 pub fn build_connection_conf(
     domain_protocol: HyperlaneDomainProtocol,
     rpcs: &[Url],
@@ -180,9 +264,6 @@ pub fn build_connection_conf(
         HyperlaneDomainProtocol::Cosmos => {
             build_cosmos_connection_conf(rpcs, chain, err, operation_batch)
         }
-        HyperlaneDomainProtocol::Aptos => rpcs
-            .iter()
-            .next()
-            .map(|url| ChainConnectionConf::Aptos(h_aptos::ConnectionConf { url: url.clone() })),
+        HyperlaneDomainProtocol::Aptos => build_aptos_connection_conf(rpcs, chain, err),
     }
 }
@@ -335,12 +335,54 @@ fn parse_signer(signer: ValueParser) -> ConfigResult<SignerConf> {
                 prefix: prefix.to_string(),
             })
         }};
+        (aptosKey) => {{
+            let key = signer
+                .chain(&mut err)
+                .get_key("key")
+                .parse_string()
+                .unwrap_or_default()
+                .to_owned();
+            err.into_result(SignerConf::AptosKey { key })
+        }};
+        (aptosProfile) => {{
+            let path = signer
+                .chain(&mut err)
+                .get_key("path")
+                .parse_string()
+                .unwrap_or_default()
+                .into();
+            let profile = signer
+                .chain(&mut err)
+                .get_opt_key("profile")
+                .parse_string()
+                .unwrap_or("default")
+                .to_owned();
+            err.into_result(SignerConf::AptosProfile { path, profile })
+        }};
+        (web3signer) => {{
+            let url = signer
+                .chain(&mut err)
+                .get_key("url")
+                .parse_from_str("Expected remote signer url")
+                .end();
+            let public_key = signer
+                .chain(&mut err)
+                .get_key("publicKey")
+                .parse_string()
+                .end()
+                .map(str::to_owned);
+            cfg_unwrap_all!(&signer.cwp, err: [url, public_key]);
+            err.into_result(SignerConf::Web3Signer { url, public_key })
+        }};
     }
 
     match signer_type {
         Some("hexKey") => parse_signer!(hexKey),
         Some("aws") => parse_signer!(aws),
         Some("cosmosKey") => parse_signer!(cosmosKey),
+        Some("aptosKey") => parse_signer!(aptosKey),
+        Some("aptosProfile") => parse_signer!(aptosProfile),
+        Some("web3signer") => parse_signer!(web3signer),
         Some(t) => {
             Err(eyre!("Unknown signer type `{t}`")).into_config_result(|| &signer.cwp + "type")
         }
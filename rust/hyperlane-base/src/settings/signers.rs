@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use ed25519_dalek::SecretKey;
 use ethers::prelude::{AwsSigner, LocalWallet};
@@ -8,7 +11,9 @@ use hyperlane_sealevel::Keypair;
 use rusoto_core::Region;
 use rusoto_kms::KmsClient;
 use tracing::instrument;
+use url::Url;
 
+use super::aptos_kms_signer::AwsKmsAptosSigner;
 use super::aws_credentials::AwsChainCredentialsProvider;
 use crate::types::utils;
 
@@ -35,6 +40,27 @@ pub enum SignerConf {
         /// Prefix for cosmos address
         prefix: String,
     },
+    /// Aptos private key, either hex or `ed25519-priv-0x...`
+    AptosKey {
+        /// Private key value
+        key: String,
+    },
+    /// A profile of an Aptos CLI config file
+    AptosProfile {
+        /// Path to the config file, usually `.aptos/config.yaml`
+        path: PathBuf,
+        /// Name of the profile
+        profile: String,
+    },
+    /// A key held by a remote signer implementing the Web3Signer eth1
+    /// signing API. Only signs checkpoints and announcements, not
+    /// transactions.
+    Web3Signer {
+        /// Base url of the remote signer
+        url: Url,
+        /// Hex encoded public key identifying the key in the remote signer
+        public_key: String,
+    },
     /// Assume node will sign on RPC calls
     #[default]
     Node,
@@ -83,9 +109,16 @@ impl BuildableWithSignerConf for hyperlane_ethereum::Signers {
                 let signer = AwsSigner::new(client, id, 0).await?;
                 hyperlane_ethereum::Signers::Aws(signer)
             }
+            SignerConf::Web3Signer { url, public_key } => hyperlane_ethereum::Signers::Web3Signer(
+                hyperlane_ethereum::Web3Signer::new(url.clone(), public_key)
+                    .context("Invalid web3signer configuration")?,
+            ),
             SignerConf::CosmosKey { .. } => {
                 bail!("cosmosKey signer is not supported by Ethereum")
             }
+            SignerConf::AptosKey { .. } | SignerConf::AptosProfile { .. } => {
+                bail!("aptos signers are not supported by Ethereum")
+            }
             SignerConf::Node => bail!("Node signer"),
         })
     }
@@ -163,14 +196,30 @@ impl ChainSigner for hyperlane_cosmos::Signer {
 #[async_trait]
 impl BuildableWithSignerConf for hyperlane_aptos::signers::AptosSigner {
     async fn build(conf: &SignerConf) -> Result<Self, Report> {
-        if let SignerConf::HexKey { key } = conf {
-            let secret = SecretKey::from_bytes(key.as_bytes())
-                .context("Invalid aptos ed25519 secret key")?;
-            use hyperlane_aptos::signers::AptosSigner;
-            Ok(AptosSigner::new(secret)?)
-        } else {
-            bail!(format!("{conf:?} key is not supported by aptos"));
-        }
+        use hyperlane_aptos::signers::AptosSigner;
+        Ok(match conf {
+            SignerConf::HexKey { key } => {
+                let secret = SecretKey::from_bytes(key.as_bytes())
+                    .context("Invalid aptos ed25519 secret key")?;
+                AptosSigner::new(secret)?
+            }
+            SignerConf::AptosKey { key } => AptosSigner::from_encoded_key(key)?,
+            SignerConf::AptosProfile { path, profile } => {
+                AptosSigner::from_cli_profile(path, profile)?
+            }
+            SignerConf::Aws { id, region } => {
+                let client = KmsClient::new_with_client(
+                    rusoto_core::Client::new_with(
+                        AwsChainCredentialsProvider::new(),
+                        utils::http_client_with_timeout().unwrap(),
+                    ),
+                    region.clone(),
+                );
+                AptosSigner::from_remote(Arc::new(AwsKmsAptosSigner::new(client, id.clone())))
+                    .await?
+            }
+            _ => bail!(format!("{conf:?} key is not supported by aptos")),
+        })
     }
 }
 
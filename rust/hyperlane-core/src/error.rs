@@ -79,6 +79,9 @@ pub enum ChainCommunicationError {
     /// A transaction submission timed out
     #[error("Transaction submission timed out")]
     TransactionTimeout(),
+    /// Simulating the transaction showed it would revert
+    #[error("Transaction would revert: {0}")]
+    TransactionWouldRevert(HyperlaneCustomErrorWrapper),
     /// No signer is available and was required for the operation
     #[error("Signer unavailable")]
     SignerUnavailable,
@@ -170,6 +173,12 @@ impl ChainCommunicationError {
         Self::Other(HyperlaneCustomErrorWrapper(err))
     }
 
+    /// Creates a chain communication error of the transaction would revert variant
+    /// from the error describing why it would
+    pub fn from_would_revert<E: HyperlaneCustomError>(err: E) -> Self {
+        Self::TransactionWouldRevert(HyperlaneCustomErrorWrapper(Box::new(err)))
+    }
+
     /// Creates a chain communication error of the other error variant from a static string
     pub fn from_other_str(err: &'static str) -> Self {
         #[derive(Debug)]
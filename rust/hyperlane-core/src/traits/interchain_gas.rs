@@ -3,10 +3,28 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 
-use crate::HyperlaneContract;
+use crate::{ChainResult, HyperlaneContract, U256};
 
 /// Interface for the InterchainGasPaymaster chain contract.
 /// Allows abstraction over different chains.
 #[async_trait]
 #[auto_impl(&, Box, Arc)]
-pub trait InterchainGasPaymaster: HyperlaneContract + Send + Sync + Debug {}
+pub trait InterchainGasPaymaster: HyperlaneContract + Send + Sync + Debug {
+    /// Quote the payment, in the origin's native token, the IGP currently
+    /// requires for `gas_amount` of gas on `destination`, including the
+    /// destination's gas overhead.
+    /// Returns `None` if the IGP can't quote payments on chain.
+    async fn quote_gas_payment(
+        &self,
+        _destination: u32,
+        _gas_amount: U256,
+    ) -> ChainResult<Option<U256>> {
+        Ok(None)
+    }
+
+    /// The gas the IGP adds to the gas amount of every payment for
+    /// `destination`. Returns `None` if the IGP has no such overhead.
+    async fn destination_gas_overhead(&self, _destination: u32) -> ChainResult<Option<U256>> {
+        Ok(None)
+    }
+}
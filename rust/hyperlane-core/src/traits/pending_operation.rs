@@ -197,6 +197,9 @@ pub enum ReprepareReason {
     #[strum(to_string = "Error estimating costs for process call")]
     /// Error estimating costs for process call
     ErrorEstimatingGas,
+    #[strum(to_string = "Delivery transaction would revert")]
+    /// Simulating the process call showed it would revert
+    WouldRevert,
     #[strum(to_string = "Error checking if message meets gas payment requirement")]
     /// Error checking if message meets gas payment requirement
     ErrorCheckingGasRequirement,
@@ -0,0 +1,181 @@
+//! Checks which validators of a multisig ISM signed a checkpoint and whether
+//! their signatures meet the ISM threshold.
+//!
+//! Validators and threshold are read from the ISM on the destination chain,
+//! storage locations from the validator announce contract of the origin
+//! chain. Each validator's signed checkpoint is fetched from its most
+//! recently announced location, its signer recovered and compared to the
+//! validator. Exits with a non-zero code if there is no quorum.
+
+use std::str::FromStr;
+
+use eyre::{bail, eyre, Result};
+use futures::future::join_all;
+use hyperlane_base::{settings::CheckpointSyncerConf, CheckpointSyncer, LoadableFromSettings};
+use hyperlane_core::{HyperlaneMessage, ModuleType, H160, H256};
+
+use crate::{
+    report::{QuorumReport, ValidatorReport, ValidatorStatus},
+    settings::{CheckpointTarget, InspectorSettings},
+};
+
+mod report;
+mod settings;
+
+/// A validator's checkpoint syncer and the location it was built from
+type LocatedSyncer = (String, Box<dyn CheckpointSyncer>);
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let settings = InspectorSettings::load()?;
+    let metrics = settings.metrics("checkpoint_inspector")?;
+    let origin_conf = settings.chain_setup(&settings.origin)?;
+    let destination_conf = settings.chain_setup(&settings.destination)?;
+
+    let ism = match settings.ism {
+        Some(ism) => ism,
+        None => {
+            destination_conf
+                .build_mailbox(&metrics)
+                .await?
+                .default_ism()
+                .await?
+        }
+    };
+    // Routing and aggregation ISMs don't have validators of their own
+    let module_type = destination_conf
+        .build_ism(ism, &metrics)
+        .await?
+        .module_type()
+        .await?;
+    if !matches!(
+        module_type,
+        ModuleType::LegacyMultisig | ModuleType::MerkleRootMultisig | ModuleType::MessageIdMultisig
+    ) {
+        bail!(
+            "ISM {ism:?} is a {module_type:?} ISM, only multisig ISMs are supported. \
+             Set `ism` to the multisig ISM verifying messages from {}",
+            settings.origin
+        );
+    }
+
+    // Multisig ISMs pick validators by origin, so the rest of the message is
+    // left empty
+    let message = HyperlaneMessage {
+        origin: settings.origin.id(),
+        destination: settings.destination.id(),
+        nonce: match settings.target {
+            CheckpointTarget::Index(index) => index,
+            CheckpointTarget::MessageId(_) => 0,
+        },
+        ..Default::default()
+    };
+    let (validators, threshold) = destination_conf
+        .build_multisig_ism(ism, &metrics)
+        .await?
+        .validators_and_threshold(&message)
+        .await?;
+
+    let storage_locations = origin_conf
+        .build_validator_announce(&metrics)
+        .await?
+        .get_announced_storage_locations(&validators)
+        .await?;
+    let syncers = join_all(
+        storage_locations
+            .iter()
+            .map(|locations| checkpoint_syncer(locations)),
+    )
+    .await;
+
+    let (index, message_id) = match settings.target {
+        CheckpointTarget::Index(index) => (index, None),
+        CheckpointTarget::MessageId(message_id) => (
+            find_message_index(&validators, &syncers, message_id, settings.lookback).await?,
+            Some(message_id),
+        ),
+    };
+
+    let validators = join_all(validators.iter().zip(syncers).map(
+        |(validator, syncer)| async move {
+            let validator = H160::from(*validator);
+            let Some((location, syncer)) = syncer else {
+                return ValidatorReport {
+                    validator,
+                    location: None,
+                    status: ValidatorStatus::NoStorageLocation,
+                };
+            };
+            let status = match syncer.fetch_checkpoint(index).await {
+                Ok(checkpoint) => ValidatorStatus::from_checkpoint(checkpoint),
+                Err(err) => ValidatorStatus::Unreachable(err.to_string()),
+            };
+            ValidatorReport {
+                validator,
+                location: Some(location),
+                status,
+            }
+        },
+    ))
+    .await;
+
+    let report = QuorumReport {
+        origin: settings.origin.clone(),
+        destination: settings.destination.clone(),
+        ism,
+        index,
+        message_id,
+        threshold,
+        validators,
+    };
+    print!("{report}");
+    if !report.has_quorum() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Build a checkpoint syncer from the most recently announced location that
+/// can be used, like the relayer does
+async fn checkpoint_syncer(locations: &[String]) -> Option<LocatedSyncer> {
+    for location in locations.iter().rev() {
+        let Ok(conf) = CheckpointSyncerConf::from_str(location) else {
+            continue;
+        };
+        if let Ok(syncer) = conf.build(None).await {
+            return Some((location.clone(), syncer));
+        }
+    }
+    None
+}
+
+/// Search the validators' storages for the checkpoint of `message_id`,
+/// starting from their latest checkpoint and going back `lookback` indices.
+/// Only checkpoints signed by the validator whose storage they're in count.
+async fn find_message_index(
+    validators: &[H256],
+    syncers: &[Option<LocatedSyncer>],
+    message_id: H256,
+    lookback: u32,
+) -> Result<u32> {
+    for (validator, syncer) in validators.iter().zip(syncers) {
+        let Some((_, syncer)) = syncer else {
+            continue;
+        };
+        let Ok(Some(latest_index)) = syncer.latest_index().await else {
+            continue;
+        };
+        let validator = H160::from(*validator);
+        for index in (latest_index.saturating_sub(lookback)..=latest_index).rev() {
+            if let Ok(Some(signed)) = syncer.fetch_checkpoint(index).await {
+                if signed.value.message_id == message_id && signed.recover().ok() == Some(validator)
+                {
+                    return Ok(index);
+                }
+            }
+        }
+    }
+    Err(eyre!(
+        "No validator signed a checkpoint of message {message_id:?} in its latest {lookback} checkpoints"
+    ))
+}
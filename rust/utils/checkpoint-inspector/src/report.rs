@@ -0,0 +1,295 @@
+//! Quorum report of the signed checkpoints of a multisig ISM's validators.
+
+use std::fmt;
+
+use hyperlane_core::{
+    CheckpointWithMessageId, HyperlaneDomain, SignedCheckpointWithMessageId, H160, H256,
+};
+
+/// What a validator's storage holds for the inspected checkpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidatorStatus {
+    /// The validator hasn't announced a storage location we can read
+    NoStorageLocation,
+    /// Reading the checkpoint from the storage failed
+    Unreachable(String),
+    /// The storage has no checkpoint at this index
+    NoCheckpoint,
+    /// The storage holds a signed checkpoint
+    Signed {
+        /// The signed checkpoint
+        checkpoint: CheckpointWithMessageId,
+        /// Signer recovered from the signature, if recovery succeeded
+        signer: Option<H160>,
+    },
+}
+
+impl ValidatorStatus {
+    /// Status of a checkpoint read from a validator's storage
+    pub fn from_checkpoint(checkpoint: Option<SignedCheckpointWithMessageId>) -> Self {
+        match checkpoint {
+            Some(signed) => Self::Signed {
+                checkpoint: signed.value,
+                signer: signed.recover().ok(),
+            },
+            None => Self::NoCheckpoint,
+        }
+    }
+}
+
+/// A validator of the ISM and what its storage holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorReport {
+    /// The validator address
+    pub validator: H160,
+    /// Storage location the checkpoint was read from
+    pub location: Option<String>,
+    /// What the storage holds
+    pub status: ValidatorStatus,
+}
+
+impl ValidatorReport {
+    /// The checkpoint, if it is signed by the validator itself
+    pub fn valid_checkpoint(&self) -> Option<&CheckpointWithMessageId> {
+        match &self.status {
+            ValidatorStatus::Signed {
+                checkpoint,
+                signer: Some(signer),
+            } if *signer == self.validator => Some(checkpoint),
+            _ => None,
+        }
+    }
+}
+
+/// The signatures of the ISM's validators over one checkpoint
+#[derive(Debug, Clone)]
+pub struct QuorumReport {
+    /// Chain the checkpoints are signed for
+    pub origin: HyperlaneDomain,
+    /// Chain the ISM is deployed on
+    pub destination: HyperlaneDomain,
+    /// The multisig ISM
+    pub ism: H256,
+    /// The checkpoint index
+    pub index: u32,
+    /// The message id the checkpoint is expected to commit to, if known
+    pub message_id: Option<H256>,
+    /// Number of signatures the ISM requires
+    pub threshold: u8,
+    /// The ISM's validators, in the order the ISM returned them
+    pub validators: Vec<ValidatorReport>,
+}
+
+impl QuorumReport {
+    /// Distinct checkpoints validly signed at this index, with their signers,
+    /// most signed first
+    pub fn signed_checkpoints(&self) -> Vec<(CheckpointWithMessageId, Vec<H160>)> {
+        let mut checkpoints: Vec<(CheckpointWithMessageId, Vec<H160>)> = vec![];
+        for report in &self.validators {
+            let Some(checkpoint) = report.valid_checkpoint() else {
+                continue;
+            };
+            match checkpoints.iter_mut().find(|(c, _)| c == checkpoint) {
+                Some((_, signers)) => signers.push(report.validator),
+                None => checkpoints.push((*checkpoint, vec![report.validator])),
+            }
+        }
+        checkpoints.sort_by(|(_, a), (_, b)| b.len().cmp(&a.len()));
+        checkpoints
+    }
+
+    /// The checkpoint with the most valid signatures, restricted to the
+    /// expected message id if there is one
+    pub fn leading_checkpoint(&self) -> Option<(CheckpointWithMessageId, Vec<H160>)> {
+        self.signed_checkpoints()
+            .into_iter()
+            .find(|(checkpoint, _)| {
+                self.message_id
+                    .map_or(true, |message_id| checkpoint.message_id == message_id)
+            })
+    }
+
+    /// Whether enough validators signed the same checkpoint for the ISM to
+    /// verify it
+    pub fn has_quorum(&self) -> bool {
+        self.leading_checkpoint().map_or(false, |(_, signers)| {
+            signers.len() >= self.threshold as usize
+        })
+    }
+}
+
+impl fmt::Display for QuorumReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Checkpoint {} of {} verified by ISM {:?} on {}",
+            self.index, self.origin, self.ism, self.destination
+        )?;
+        if let Some(message_id) = self.message_id {
+            writeln!(f, "Message id: {message_id:?}")?;
+        }
+        writeln!(
+            f,
+            "Threshold: {} of {} validators",
+            self.threshold,
+            self.validators.len()
+        )?;
+        writeln!(f)?;
+
+        let leading = self.leading_checkpoint().map(|(checkpoint, _)| checkpoint);
+        for report in &self.validators {
+            let status = match &report.status {
+                ValidatorStatus::NoStorageLocation => "no readable storage location".to_owned(),
+                ValidatorStatus::Unreachable(err) => format!("unreachable: {err}"),
+                ValidatorStatus::NoCheckpoint => "no checkpoint".to_owned(),
+                ValidatorStatus::Signed { checkpoint, signer } => {
+                    let verdict = match signer {
+                        None => "invalid signature".to_owned(),
+                        Some(signer) if *signer != report.validator => {
+                            format!("signed by {signer:?}")
+                        }
+                        Some(_) if Some(*checkpoint) != leading => "conflicting".to_owned(),
+                        Some(_) => "signed".to_owned(),
+                    };
+                    format!(
+                        "{verdict}, root {:?}, message id {:?}",
+                        checkpoint.root, checkpoint.message_id
+                    )
+                }
+            };
+            write!(f, "  {:?}  {status}", report.validator)?;
+            match &report.location {
+                Some(location) => writeln!(f, " ({location})")?,
+                None => writeln!(f)?,
+            }
+        }
+        writeln!(f)?;
+
+        match self.leading_checkpoint() {
+            Some((checkpoint, signers)) => writeln!(
+                f,
+                "{}: {} of {} required signatures for root {:?}, message id {:?}",
+                if self.has_quorum() {
+                    "Quorum reached"
+                } else {
+                    "No quorum"
+                },
+                signers.len(),
+                self.threshold,
+                checkpoint.root,
+                checkpoint.message_id
+            ),
+            None => writeln!(f, "No quorum: 0 of {} required signatures", self.threshold),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::{Checkpoint, KnownHyperlaneDomain, Signature, SignedType, U256};
+
+    use super::*;
+
+    fn checkpoint(root: u8) -> CheckpointWithMessageId {
+        CheckpointWithMessageId {
+            checkpoint: Checkpoint {
+                merkle_tree_hook_address: H256::repeat_byte(1),
+                mailbox_domain: 14402,
+                root: H256::repeat_byte(root),
+                index: 7,
+            },
+            message_id: H256::repeat_byte(0xaa),
+        }
+    }
+
+    fn signed(validator: u64, root: u8) -> ValidatorReport {
+        ValidatorReport {
+            validator: H160::from_low_u64_be(validator),
+            location: Some(format!("file:///tmp/{validator}")),
+            status: ValidatorStatus::Signed {
+                checkpoint: checkpoint(root),
+                signer: Some(H160::from_low_u64_be(validator)),
+            },
+        }
+    }
+
+    fn report(threshold: u8, validators: Vec<ValidatorReport>) -> QuorumReport {
+        QuorumReport {
+            origin: HyperlaneDomain::Known(KnownHyperlaneDomain::AptosTestnet),
+            destination: HyperlaneDomain::Known(KnownHyperlaneDomain::Sepolia),
+            ism: H256::repeat_byte(9),
+            index: 7,
+            message_id: None,
+            threshold,
+            validators,
+        }
+    }
+
+    #[test]
+    fn test_quorum_counts_matching_signatures() {
+        let missing = ValidatorReport {
+            validator: H160::from_low_u64_be(4),
+            location: None,
+            status: ValidatorStatus::NoStorageLocation,
+        };
+        let quorum = report(2, vec![signed(1, 2), signed(2, 3), signed(3, 2), missing]);
+
+        assert!(quorum.has_quorum());
+        let (leading, signers) = quorum.leading_checkpoint().unwrap();
+        assert_eq!(leading, checkpoint(2));
+        assert_eq!(
+            signers,
+            vec![H160::from_low_u64_be(1), H160::from_low_u64_be(3)]
+        );
+        assert_eq!(quorum.signed_checkpoints().len(), 2);
+        assert!(!report(3, quorum.validators.clone()).has_quorum());
+    }
+
+    #[test]
+    fn test_quorum_ignores_foreign_signatures() {
+        let mut impostor = signed(2, 2);
+        impostor.status = ValidatorStatus::Signed {
+            checkpoint: checkpoint(2),
+            signer: Some(H160::from_low_u64_be(99)),
+        };
+        let quorum = report(2, vec![signed(1, 2), impostor]);
+
+        assert!(!quorum.has_quorum());
+        assert!(quorum
+            .to_string()
+            .contains(&format!("signed by {:?}", H160::from_low_u64_be(99))));
+        assert!(quorum.to_string().contains("No quorum: 1 of 2"));
+    }
+
+    #[test]
+    fn test_quorum_requires_expected_message_id() {
+        let mut quorum = report(1, vec![signed(1, 2)]);
+        quorum.message_id = Some(H256::repeat_byte(0xbb));
+
+        assert!(quorum.leading_checkpoint().is_none());
+        assert!(!quorum.has_quorum());
+    }
+
+    #[test]
+    fn test_status_of_unrecoverable_signature() {
+        let signed = SignedType {
+            value: checkpoint(2),
+            signature: Signature {
+                r: U256::zero(),
+                s: U256::zero(),
+                v: 27,
+            },
+        };
+        assert_eq!(
+            ValidatorStatus::from_checkpoint(Some(signed)),
+            ValidatorStatus::Signed {
+                checkpoint: checkpoint(2),
+                signer: None,
+            }
+        );
+        assert_eq!(
+            ValidatorStatus::from_checkpoint(None),
+            ValidatorStatus::NoCheckpoint
+        );
+    }
+}
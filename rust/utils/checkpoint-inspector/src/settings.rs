@@ -0,0 +1,137 @@
+//! Checkpoint inspector configuration.
+//!
+//! Chains are configured like for the agents: the default configs in
+//! `config/` are loaded, so run it from the `rust` directory, and can be
+//! extended with `CONFIG_FILES` or `HYP_CHAINS_*` env vars. The checkpoint to
+//! inspect is given with program arguments:
+//!
+//! ```text
+//! checkpoint-inspector --origin aptostestnet --destination sepolia --index 42
+//! checkpoint-inspector --origin aptostestnet --destination sepolia --messageId 0x...
+//! ```
+
+use std::collections::HashSet;
+
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use eyre::eyre;
+use hyperlane_base::{
+    impl_loadable_from_settings,
+    settings::{
+        parser::{RawAgentConf, ValueParser},
+        Settings,
+    },
+};
+use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, H256};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// How many checkpoints below the latest one are searched for a message id
+const DEFAULT_LOOKBACK: u32 = 1000;
+
+/// The checkpoint to inspect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointTarget {
+    /// The checkpoint at this index of the origin merkle tree
+    Index(u32),
+    /// The checkpoint of the message with this id
+    MessageId(H256),
+}
+
+/// Settings for the checkpoint inspector
+#[derive(Debug, AsRef, AsMut, Deref, DerefMut)]
+pub struct InspectorSettings {
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    base: Settings,
+
+    /// Chain the checkpoints are signed for
+    pub origin: HyperlaneDomain,
+    /// Chain the multisig ISM verifying the checkpoints is deployed on
+    pub destination: HyperlaneDomain,
+    /// The checkpoint to inspect
+    pub target: CheckpointTarget,
+    /// Multisig ISM to read validators and threshold from. Defaults to the
+    /// default ISM of the destination mailbox.
+    pub ism: Option<H256>,
+    /// How many checkpoints below the latest one are searched for a message id
+    pub lookback: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct RawInspectorSettings(Value);
+
+impl_loadable_from_settings!(CheckpointInspector, RawInspectorSettings -> InspectorSettings);
+
+impl FromRawConf<RawInspectorSettings> for InspectorSettings {
+    fn from_config_filtered(
+        raw: RawInspectorSettings,
+        cwp: &ConfigPath,
+        _filter: (),
+    ) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+
+        let p = ValueParser::new(cwp.clone(), &raw.0);
+
+        let origin_name = p.chain(&mut err).get_key("origin").parse_string().end();
+        let destination_name = p
+            .chain(&mut err)
+            .get_key("destination")
+            .parse_string()
+            .end();
+
+        let index = p.chain(&mut err).get_opt_key("index").parse_u32().end();
+        let message_id = p
+            .chain(&mut err)
+            .get_opt_key("messageId")
+            .parse_address_hash()
+            .end();
+        let target = match (index, message_id) {
+            (Some(index), None) => Some(CheckpointTarget::Index(index)),
+            (None, Some(message_id)) => Some(CheckpointTarget::MessageId(message_id)),
+            _ => Err(eyre!("Expected exactly one of `index` and `messageId`"))
+                .take_err(&mut err, || cwp + "index"),
+        };
+
+        let ism = p
+            .chain(&mut err)
+            .get_opt_key("ism")
+            .parse_address_hash()
+            .end();
+        let lookback = p
+            .chain(&mut err)
+            .get_opt_key("lookback")
+            .parse_u32()
+            .unwrap_or(DEFAULT_LOOKBACK);
+
+        let chain_names: Option<HashSet<&str>> = origin_name
+            .zip(destination_name)
+            .map(|(origin, destination)| HashSet::from([origin, destination]));
+        let base = p
+            .parse_from_raw_config::<Settings, RawAgentConf, Option<&HashSet<&str>>>(
+                chain_names.as_ref(),
+                "Parsing base config",
+            )
+            .take_config_err(&mut err);
+
+        let mut lookup = |name: Option<&str>, key: &str| {
+            let (base, name) = (base.as_ref()?, name?);
+            base.lookup_domain(name).take_err(&mut err, || cwp + key)
+        };
+        let origin = lookup(origin_name, "origin");
+        let destination = lookup(destination_name, "destination");
+
+        cfg_unwrap_all!(cwp, err: [base, origin, destination, target]);
+
+        err.into_result(Self {
+            base,
+            origin,
+            destination,
+            target,
+            ism,
+            lookback,
+        })
+    }
+}
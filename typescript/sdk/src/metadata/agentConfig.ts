@@ -49,6 +49,9 @@ export enum AgentSignerKeyType {
   Hex = 'hexKey',
   Node = 'node',
   Cosmos = 'cosmosKey',
+  AptosKey = 'aptosKey',
+  AptosProfile = 'aptosProfile',
+  Web3Signer = 'web3signer',
 }
 
 const AgentSignerHexKeySchema = z
@@ -73,6 +76,35 @@ const AgentSignerCosmosKeySchema = z
     key: ZHash,
   })
   .describe('Cosmos key');
+const AgentSignerAptosKeySchema = z
+  .object({
+    type: z.literal(AgentSignerKeyType.AptosKey),
+    key: z
+      .string()
+      .describe('The private key, either hex or ed25519-priv-0x...'),
+  })
+  .describe('Aptos key');
+const AgentSignerAptosProfileSchema = z
+  .object({
+    type: z.literal(AgentSignerKeyType.AptosProfile),
+    path: z.string().describe('Path to an Aptos CLI config file'),
+    profile: z
+      .string()
+      .optional()
+      .describe('The profile to use, defaults to "default"'),
+  })
+  .describe('A profile of an Aptos CLI config file, e.g. .aptos/config.yaml');
+const AgentSignerWeb3SignerSchema = z
+  .object({
+    type: z.literal(AgentSignerKeyType.Web3Signer),
+    url: z.string().url().describe('The base URL of the remote signer'),
+    publicKey: z
+      .string()
+      .describe('The hex public key identifying the key in the remote signer'),
+  })
+  .describe(
+    'A key held by a Web3Signer-compatible remote signer. Can only be used as a validator signer.',
+  );
 const AgentSignerNodeSchema = z
   .object({
     type: z.literal(AgentSignerKeyType.Node),
@@ -83,6 +115,9 @@ const AgentSignerSchema = z.union([
   AgentSignerHexKeySchema,
   AgentSignerAwsKeySchema,
   AgentSignerCosmosKeySchema,
+  AgentSignerAptosKeySchema,
+  AgentSignerAptosProfileSchema,
+  AgentSignerWeb3SignerSchema,
   AgentSignerNodeSchema,
 ]);
 
@@ -385,6 +420,18 @@ export const ValidatorAgentConfigSchema = AgentConfigSchema.extend({
   interval: ZUint.optional().describe(
     'How long to wait between checking for new checkpoints in seconds.',
   ),
+  checkpointAudit: z
+    .enum(['off', 'report', 'enforce'])
+    .optional()
+    .describe(
+      'Whether to audit previously published checkpoints against the local merkle tree. `enforce` stops signing checkpoints if one fails the audit.',
+    ),
+  signingHistoryImport: z
+    .string()
+    .optional()
+    .describe(
+      'Path to a signing history exported from the `/signing_history` endpoint of another validator with the same key, imported at startup.',
+    ),
 });
 
 export type ValidatorConfig = z.infer<typeof ValidatorAgentConfigSchema>;